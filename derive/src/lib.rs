@@ -0,0 +1,162 @@
+// ======================================================================
+// DERIVE(DIALOGFORM)
+// ======================================================================
+// Generates a form dialog from a plain struct: one text field per struct
+// field, and the struct filled in from what was typed when the user
+// presses Ok. Re-exported by gpui_dialogs next to the DialogForm trait it
+// implements, so apps only depend on that crate:
+//
+//     #[derive(DialogForm)]
+//     struct Connection {
+//         #[dialog(label = "Host name", placeholder = "example.com")]
+//         host: String,
+//         #[dialog(default = "22", validate = "valid_port")]
+//         port: u16,
+//     }
+//
+// Every field's type has to implement FromStr. The attributes, all
+// optional:
+//
+//   - label:       shown above the input (default: the field name, so
+//                  `host_name` reads "Host name")
+//   - placeholder: gray hint text in the empty input
+//   - default:     the value the input starts with
+//   - validate:    path to a `fn(&str) -> Result<(), String>` that checks
+//                  the text before it's parsed; the error is shown to the
+//                  user, who gets to correct it
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Path};
+
+#[proc_macro_derive(DialogForm, attributes(dialog))]
+pub fn derive_dialog_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// What the #[dialog(...)] attribute on one field says
+#[derive(Default)]
+struct FieldOptions {
+    label: Option<LitStr>,
+    placeholder: Option<LitStr>,
+    default: Option<LitStr>,
+    validate: Option<Path>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "DialogForm needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "DialogForm can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut form_fields = Vec::new();
+    let mut parsed_fields = Vec::new();
+    for field in fields {
+        let Some(ident) = &field.ident else {
+            continue; // Named fields always have one
+        };
+        let options = field_options(field)?;
+        // r#type is the "type" field
+        let id = ident.unraw().to_string();
+        let label = match &options.label {
+            Some(label) => label.value(),
+            None => humanize(&id),
+        };
+
+        let placeholder = options
+            .placeholder
+            .as_ref()
+            .map(|placeholder| quote!(.placeholder(#placeholder)));
+        let default = options
+            .default
+            .as_ref()
+            .map(|default| quote!(.default_value(#default)));
+        form_fields.push(quote! {
+            ::gpui_dialogs::FormField::text(#id, #label) #placeholder #default
+        });
+
+        let validate = options.validate.as_ref().map(|validate| {
+            quote! {
+                #validate(text).map_err(|err| ::std::format!("{}: {}", #label, err))?;
+            }
+        });
+        parsed_fields.push(quote! {
+            #ident: {
+                let text = values.get(#id).map(::std::string::String::as_str).unwrap_or_default();
+                #validate
+                text.parse().map_err(|_| ::std::format!("{}: “{}” isn't valid", #label, text))?
+            }
+        });
+    }
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::gpui_dialogs::DialogForm for #name #type_generics #where_clause {
+            fn fields() -> ::std::vec::Vec<::gpui_dialogs::FormField> {
+                ::std::vec![#(#form_fields),*]
+            }
+
+            fn from_values(
+                values: &::std::collections::BTreeMap<::std::string::String, ::std::string::String>,
+            ) -> ::std::result::Result<Self, ::std::string::String> {
+                ::std::result::Result::Ok(Self {
+                    #(#parsed_fields),*
+                })
+            }
+        }
+    })
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in &field.attrs {
+        if !attr.path().is_ident("dialog") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                options.label = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("placeholder") {
+                options.placeholder = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("default") {
+                options.default = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("validate") {
+                let path: LitStr = meta.value()?.parse()?;
+                options.validate = Some(path.parse()?);
+            } else {
+                return Err(meta.error("expected `label`, `placeholder`, `default` or `validate`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+// "host_name" → "Host name"
+fn humanize(name: &str) -> String {
+    let words = name.trim_start_matches("r#").replace('_', " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
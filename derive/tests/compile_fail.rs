@@ -0,0 +1,9 @@
+// What derive(DialogForm) refuses, and what it says about it. After a
+// change to the messages, TRYBUILD=overwrite cargo test updates the
+// .stderr files.
+
+#[test]
+fn refuses_what_isnt_a_struct_with_named_fields() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use gpui_dialogs_derive::DialogForm;
+
+#[derive(DialogForm)]
+enum Protocol {
+    Ssh,
+    Sftp,
+}
+
+fn main() {}
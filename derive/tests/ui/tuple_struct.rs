@@ -0,0 +1,6 @@
+use gpui_dialogs_derive::DialogForm;
+
+#[derive(DialogForm)]
+struct Connection(String, u16);
+
+fn main() {}
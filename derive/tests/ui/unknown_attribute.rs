@@ -0,0 +1,9 @@
+use gpui_dialogs_derive::DialogForm;
+
+#[derive(DialogForm)]
+struct Connection {
+    #[dialog(hint = "example.com")]
+    host: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// Command lines as a script might pass them: unknown flags, missing and
+// malformed values are reported, never a panic
+#![no_main]
+
+use gpui_dialogs::cli::Args;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|args: Vec<String>| {
+    let _ = Args::parse(args);
+});
@@ -0,0 +1,25 @@
+// Dialogs as a script might describe them: whatever's wrong with the
+// fields or buttons comes back as InvalidSpec, never a panic
+#![no_main]
+
+use gpui_dialogs::{Dialog, DialogButton, DialogError, FormField};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Vec<(&str, &str)>, Vec<&str>)| {
+    let (fields, buttons) = input;
+    let dialog = Dialog::form(
+        "",
+        fields
+            .into_iter()
+            .map(|(id, label)| FormField::text(id.to_string(), label.to_string())),
+    )
+    .buttons(
+        buttons
+            .into_iter()
+            .map(|label| DialogButton::secondary(label.to_string())),
+    );
+    match dialog.validate() {
+        Ok(()) | Err(DialogError::InvalidSpec(_)) => {}
+        Err(err) => panic!("{err}"),
+    }
+});
@@ -0,0 +1,12 @@
+// Colors typed into the picker or given in theme.json. Whatever parses
+// has to come back the same through to_hex().
+#![no_main]
+
+use gpui_dialogs::components::color_swatches::{parse_hex, to_hex};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Some(color) = parse_hex(text) {
+        assert_eq!(parse_hex(&to_hex(color)), Some(color), "{text:?}");
+    }
+});
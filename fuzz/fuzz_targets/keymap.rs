@@ -0,0 +1,10 @@
+// keymap.toml as anyone might write it: bad sections, values and chords
+// are reported, never a panic
+#![no_main]
+
+use gpui_dialogs::keymap;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = keymap::check_text(text);
+});
@@ -0,0 +1,20 @@
+// Page ranges typed into the print dialog. Whatever parses is sorted,
+// without repeats, and within the document when its length is known.
+#![no_main]
+
+use gpui_dialogs::presets::print::parse_page_range;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, Option<u16>)| {
+    let (text, page_count) = input;
+    let page_count = page_count.map(usize::from);
+    if let Ok(pages) = parse_page_range(text, page_count) {
+        assert!(pages.windows(2).all(|pair| pair[0] < pair[1]), "{text:?}");
+        assert!(
+            pages
+                .iter()
+                .all(|&page| page >= 1 && page_count.is_none_or(|count| page <= count)),
+            "{text:?}"
+        );
+    }
+});
@@ -0,0 +1,10 @@
+// theme.json as anyone might write it: check_text() must report what's
+// wrong with it, never panic
+#![no_main]
+
+use gpui_dialogs::theme;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = theme::check_text(text);
+});
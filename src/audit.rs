@@ -0,0 +1,270 @@
+// ======================================================================
+// AUDIT LOG
+// ======================================================================
+// Confirmations that matter for compliance ("Delete the production
+// database?") can leave an append-only record of how they were answered:
+//
+//     Dialog::question(message)
+//         .audit("drop-database", FileSink::new("/var/log/app/audit.jsonl"))
+//         .show(cx, ...);
+//
+// One record is written when the dialog resolves, holding:
+//
+//     timestamp    when it was answered, RFC 3339 in UTC
+//     dialog       the id given to audit()
+//     message      SHA-256 of the message as shown, so the log proves
+//                  what was asked without repeating it
+//     outcome      button, dismissed, completed, timed_out or expired
+//     button       label of the chosen button (null if none)
+//     user         the account we run as
+//
+// Where it goes is up to the sink: FileSink appends one JSON object per
+// line, SyslogSink hands it to the system log. Anything implementing
+// AuditSink works. A record that can't be written is logged as an error
+// and doesn't keep the dialog from closing.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::result::{DialogResult, Outcome};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditRecord {
+    pub timestamp: SystemTime,
+    pub dialog: String,
+    // Hex-encoded SHA-256 of the message
+    pub message_hash: String,
+    pub outcome: &'static str,
+    pub button: Option<String>,
+    pub user: String,
+}
+
+impl AuditRecord {
+    pub(crate) fn new(dialog: &str, message: &str, result: &DialogResult) -> Self {
+        let outcome = match result.outcome {
+            Outcome::Button { .. } => "button",
+            Outcome::Dismissed => "dismissed",
+            Outcome::Completed => "completed",
+            Outcome::TimedOut => "timed_out",
+            Outcome::Expired => "expired",
+        };
+        Self {
+            timestamp: SystemTime::now(),
+            dialog: dialog.to_string(),
+            message_hash: Sha256::digest(message.as_bytes())
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            outcome,
+            button: result.button().map(str::to_string),
+            user: current_user(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "timestamp": rfc3339(self.timestamp),
+            "dialog": self.dialog,
+            "message_sha256": self.message_hash,
+            "outcome": self.outcome,
+            "button": self.button,
+            "user": self.user,
+        })
+    }
+}
+
+pub trait AuditSink {
+    fn write(&self, record: &AuditRecord) -> io::Result<()>;
+}
+
+// Appends a JSON line per record. The file is opened for appending on
+// every record, so rotating it away between dialogs is safe.
+pub struct FileSink {
+    path: PathBuf,
+}
+
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl AuditSink for FileSink {
+    fn write(&self, record: &AuditRecord) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        // One write per line, so records from several processes don't
+        // interleave
+        file.write_all(format!("{}\n", record.to_json()).as_bytes())
+    }
+}
+
+// Sends records to the local syslog daemon (journald takes these too),
+// under the authpriv facility that security logs go to
+#[derive(Default)]
+pub struct SyslogSink {
+    tag: Option<String>,
+}
+
+impl SyslogSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The program name records are filed under (ours by default)
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+}
+
+impl AuditSink for SyslogSink {
+    #[cfg(unix)]
+    fn write(&self, record: &AuditRecord) -> io::Result<()> {
+        use std::os::unix::net::UnixDatagram;
+
+        // authpriv (10) at notice (5)
+        const PRIORITY: u32 = 10 * 8 + 5;
+        let tag = self.tag.clone().unwrap_or_else(program_name);
+        let line = format!(
+            "<{PRIORITY}>{tag}[{}]: {}",
+            std::process::id(),
+            record.to_json()
+        );
+        let socket = UnixDatagram::unbound()?;
+        // Linux and most BSDs listen on /dev/log, macOS on /var/run/syslog
+        socket
+            .send_to(line.as_bytes(), "/dev/log")
+            .or_else(|_| socket.send_to(line.as_bytes(), "/var/run/syslog"))
+            .map(drop)
+    }
+
+    #[cfg(not(unix))]
+    fn write(&self, _record: &AuditRecord) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "there is no syslog on this system",
+        ))
+    }
+}
+
+// Called when an audited dialog resolves
+pub(crate) fn record(sink: &dyn AuditSink, dialog: &str, message: &str, result: &DialogResult) {
+    if let Err(err) = sink.write(&AuditRecord::new(dialog, message, result)) {
+        log::error!("couldn't write the audit record for \"{dialog}\": {err}");
+    }
+}
+
+fn current_user() -> String {
+    ["USER", "USERNAME", "LOGNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(unix)]
+fn program_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "gpui-dialogs".to_string())
+}
+
+// "2024-05-01T12:34:56Z", without pulling in a date library
+fn rfc3339(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+
+    // Days since 1970 to a civil date (Howard Hinnant's algorithm)
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rest / 3600,
+        rest / 60 % 60,
+        rest % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use serde_json::json;
+
+    use super::{rfc3339, AuditRecord};
+    use crate::dialog::ButtonRole;
+    use crate::result::{DialogResult, Outcome};
+
+    fn at(seconds: u64) -> String {
+        rfc3339(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn timestamps_are_civil_dates_in_utc() {
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(1_714_566_896), "2024-05-01T12:34:56Z");
+        // Leap days, and 2100 not having one
+        assert_eq!(at(951_782_399), "2000-02-28T23:59:59Z");
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(4_107_542_400), "2100-03-01T00:00:00Z");
+        assert_eq!(at(4_107_542_400 - 1), "2100-02-28T23:59:59Z");
+    }
+
+    #[test]
+    fn records_hash_the_message() {
+        let result = DialogResult::new(Outcome::Button {
+            label: "Delete".into(),
+            role: ButtonRole::Primary,
+        });
+        let mut record = AuditRecord::new("drop-database", "abc", &result);
+        assert_eq!(
+            record.message_hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        record.timestamp = UNIX_EPOCH;
+        record.user = "alice".to_string();
+        assert_eq!(
+            record.to_json(),
+            json!({
+                "timestamp": "1970-01-01T00:00:00Z",
+                "dialog": "drop-database",
+                "message_sha256": "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                "outcome": "button",
+                "button": "Delete",
+                "user": "alice",
+            })
+        );
+    }
+
+    #[test]
+    fn records_without_a_button_say_how_it_closed() {
+        let record = AuditRecord::new("tip", "", &DialogResult::new(Outcome::TimedOut));
+        let json = record.to_json();
+        assert_eq!(json["outcome"], "timed_out");
+        assert!(json["button"].is_null());
+    }
+}
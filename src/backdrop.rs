@@ -0,0 +1,196 @@
+// ======================================================================
+// BACKDROP COMPONENT
+// ======================================================================
+// The Backdrop creates a semi-transparent overlay behind the dialog.
+// This is a common pattern in modal dialogs to dim the background and
+// focus user attention on the dialog itself.
+//
+// Both are pop-up windows at the same level, so which one is on top
+// comes down to the order they were raised. Some window managers raise
+// the backdrop when it's clicked or when focus comes back from another
+// app, hiding the dialog behind it. The backdrop watches its own
+// activation and raises its dialog again whenever it comes to the front.
+//
+// Over a remote desktop (RDP, VNC, X forwarded through SSH), dimming the
+// whole screen sends the whole screen over the wire, and again on every
+// repaint. There the backdrop only dims a thin frame around the edges of
+// the screen, which still marks the dialog as modal. The mode can also be
+// set by the app, down to no backdrop at all:
+//
+//     backdrop::set_mode(BackdropMode::Off, cx);
+
+use std::env;
+use std::rc::Rc;
+
+use gpui::*;
+
+use crate::error::DialogError;
+use crate::testing::{Kind, Live};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BackdropMode {
+    // Dim the whole screen
+    #[default]
+    Dim,
+    // Dim a 4px band along the edges of the screen
+    Frame,
+    // Don't open a backdrop
+    Off,
+}
+
+impl Global for BackdropMode {}
+
+pub fn mode(cx: &App) -> BackdropMode {
+    cx.try_global::<BackdropMode>().copied().unwrap_or_default()
+}
+
+pub fn set_mode(mode: BackdropMode, cx: &mut App) {
+    cx.set_global(mode);
+}
+
+// Whether we're being shown over a remote desktop connection
+pub fn remote_session() -> bool {
+    let set = |name: &str| env::var_os(name).is_some_and(|value| !value.is_empty());
+    // xrdp, TigerVNC and TightVNC, and X11 forwarded through SSH
+    set("XRDP_SESSION")
+        || set("VNCDESKTOP")
+        || (set("SSH_CONNECTION") && set("DISPLAY"))
+        // Windows names remote sessions RDP-Tcp#<n>
+        || env::var("SESSIONNAME").is_ok_and(|name| name.starts_with("RDP-"))
+}
+
+// Frame the screen instead of dimming it when remote
+pub(crate) fn detect(cx: &mut App) {
+    if remote_session() {
+        set_mode(BackdropMode::Frame, cx);
+    }
+}
+
+pub struct Backdrop {
+    // The dialog that belongs above this backdrop, once it's open
+    above: Option<AnyWindowHandle>,
+    mode: BackdropMode,
+    _activation: Subscription,
+    _live: Live,
+}
+
+impl Backdrop {
+    fn new(mode: BackdropMode, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let activation = cx.observe_window_activation(window, |this, window, cx| {
+            if window.is_window_active() {
+                if let Some(dialog) = this.above {
+                    raise(dialog, cx);
+                }
+            }
+        });
+        Backdrop {
+            above: None,
+            mode,
+            _activation: activation,
+            _live: Live::new(Kind::Backdrop),
+        }
+    }
+}
+
+// The Render trait is required for all GPUI components that display UI.
+// It has one method: render(), which returns the component's visual representation.
+
+impl Render for Backdrop {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        // div() creates a container element (similar to HTML's <div>)
+        // GPUI uses a builder pattern where you chain methods to configure the element.
+        let dim = hsla(0.0, 0.0, 0.0, 0.3); // Semi-transparent black (30% opacity)
+                                            // hsla(hue, saturation, lightness, alpha)
+        let backdrop = div().size_full(); // Full width and height
+        if self.mode == BackdropMode::Frame {
+            // Only the edges, leaving the rest of the screen as it was
+            backdrop.border_4().border_color(dim)
+        } else {
+            backdrop.bg(dim)
+        }
+    }
+}
+
+// The display dialogs are shown on: the first one reported by the platform
+pub(crate) fn primary_display(cx: &App) -> Result<Rc<dyn PlatformDisplay>, DialogError> {
+    let displays = cx.displays(); // Get all connected displays
+    displays
+        .first()
+        .cloned()
+        .ok_or(DialogError::DisplayNotFound) // Use the primary display
+}
+
+// ==================================================
+// CREATE BACKDROP WINDOW
+// ==================================================
+// This creates a full-screen, semi transparent overlay behind the dialog.
+// It dims the background and gives the dialog a modal appearance.
+// Nothing is opened in BackdropMode::Off.
+
+pub(crate) fn open_backdrop(cx: &mut App) -> Result<Option<WindowHandle<Backdrop>>, DialogError> {
+    let mode = mode(cx);
+    if mode == BackdropMode::Off {
+        return Ok(None);
+    }
+    let display = primary_display(cx)?;
+    let screen_size = display.bounds().size; // Get the dimensions of the display
+
+    cx.open_window(
+        // WindowOptions configures how the window behaves and appears
+        WindowOptions {
+            // Position and size: full screen (covers entire display)
+            window_bounds: Some(WindowBounds::Windowed(Bounds {
+                origin: point(px(0.0), px(0.0)), // Top-left corner
+                size: screen_size,
+            })),
+
+            titlebar: None, // No titlebar (we want a borderless window)
+            focus: false,   // Don't steal focus (the dialog should be focused)
+            show: true,     // Make window visible immediately
+
+            // WindowKind::PopUp creates a floating window without OS chrome
+            kind: WindowKind::PopUp,
+
+            is_movable: false,              // User can't drag this window
+            display_id: Some(display.id()), // Show on the primary display
+
+            // Transparent background lets the backdrop's semi-transparent
+            // color show through
+            window_background: WindowBackgroundAppearance::Transparent,
+
+            ..Default::default() // Use default values for other options
+        },
+        // Window content factory: creates the Backdrop component
+        // |_, cx| receives (WindowHandle, Context)
+        // cx.new() creates a new component instance
+        |window, cx| cx.new(|cx| Backdrop::new(mode, window, cx)),
+    )
+    .map(Some)
+    .map_err(|err| DialogError::WindowCreation(err.to_string()))
+}
+
+// Put `dialog` above the backdrop, and keep it there until another
+// dialog takes its place (the next step of a flow)
+pub(crate) fn keep_above(backdrop: WindowHandle<Backdrop>, dialog: AnyWindowHandle, cx: &mut App) {
+    backdrop
+        .update(cx, |this, _, _| this.above = Some(dialog))
+        .ok();
+    raise(dialog, cx);
+}
+
+// Bring `dialog` to the front. Deferred, so the platform is done with
+// the activation that called for it first.
+fn raise(dialog: AnyWindowHandle, cx: &mut App) {
+    cx.defer(move |cx| {
+        dialog
+            .update(cx, |_, window, _| window.activate_window())
+            .ok(); // Closed in the meantime
+    });
+}
+
+// Close a backdrop window opened by open_backdrop()
+pub(crate) fn close_backdrop(backdrop: WindowHandle<Backdrop>, cx: &mut App) {
+    backdrop
+        .update(cx, |_, window, _| window.remove_window())
+        .ok(); // Already gone: nothing to do
+}
@@ -0,0 +1,645 @@
+// ======================================================================
+// COMPONENT GALLERY
+// ======================================================================
+// Every component and dialog preset in one window, for trying them out
+// by hand while working on them:
+//
+//     cargo run --bin gallery
+//
+// Tabs along the top group the components. Knobs above them change the
+// props live (the value shown by the progress bar and meters, a badge's
+// tone, an avatar's size, ...). The Dialogs tab opens each kind of dialog
+// and preset, and the status bar at the bottom shows how the last one
+// was answered.
+
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use gpui_dialogs::components::{
+    Accordion, Avatar, AvatarSize, Badge, Breadcrumb, Button, ButtonStyle, CheckState, Checkbox,
+    ColorSwatches, EmptyState, Gauge, Kbd, ListView, Meter, ProgressBar, Skeleton, Sparkline,
+    StatusBar, TagInput, TextInput, Tone, Zone,
+};
+use gpui_dialogs::{layout, theme};
+use gpui_dialogs::{
+    log_channel, progress_channel, AboutDialog, ColorPicker, CommandPalette, Dialog, DialogButton,
+    DialogError, DialogFlow, DialogHandle, DialogResult, DirectoryPicker, EmojiPicker, FormField,
+    ListItems, PrintDialog, ProgressUpdate, ShortcutsDialog, ThemeChoice,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Display,
+    Inputs,
+    Charts,
+    Dialogs,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Display, Tab::Inputs, Tab::Charts, Tab::Dialogs];
+
+    fn label(self) -> &'static str {
+        match self {
+            Tab::Display => "Display",
+            Tab::Inputs => "Inputs",
+            Tab::Charts => "Charts",
+            Tab::Dialogs => "Dialogs",
+        }
+    }
+}
+
+const TONES: [Tone; 5] = [
+    Tone::Neutral,
+    Tone::Accent,
+    Tone::Success,
+    Tone::Warning,
+    Tone::Critical,
+];
+const AVATAR_SIZES: [AvatarSize; 4] = [
+    AvatarSize::Small,
+    AvatarSize::Medium,
+    AvatarSize::Large,
+    AvatarSize::ExtraLarge,
+];
+const CHECK_STATES: [CheckState; 3] = [
+    CheckState::Unchecked,
+    CheckState::Checked,
+    CheckState::Indeterminate,
+];
+
+struct Gallery {
+    tab: Tab,
+    // Knobs
+    value: f32,
+    tone: usize,
+    avatar_size: usize,
+    check: usize,
+    count: usize,
+    loading: bool,
+    // Components that keep state between frames
+    text: Entity<TextInput>,
+    tags: Entity<TagInput>,
+    swatches: Entity<ColorSwatches>,
+    list: Entity<ListView>,
+    breadcrumb: Entity<Breadcrumb>,
+    accordion: Entity<Accordion>,
+    line_chart: Entity<Sparkline>,
+    bar_chart: Entity<Sparkline>,
+    status: Entity<StatusBar>,
+}
+
+impl Gallery {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let text = cx.new(|cx| TextInput::new(cx).placeholder("Type something…"));
+        let notes = cx.new(|cx| TextInput::new(cx).placeholder("Notes"));
+        let values = vec![3.0, 5.0, 4.0, 8.0, 6.0, 9.0, 7.0, 11.0, 10.0, 12.0];
+        Self {
+            tab: Tab::Display,
+            value: 0.6,
+            tone: 1,
+            avatar_size: 1,
+            check: 1,
+            count: 3,
+            loading: false,
+            text: text.clone(),
+            tags: cx.new(|cx| {
+                TagInput::new(cx).suggestions(["design", "bug", "docs"].map(SharedString::from))
+            }),
+            swatches: cx.new(ColorSwatches::new),
+            list: cx.new(|cx| {
+                ListView::new(
+                    ["Apples", "Bananas", "Cherries", "Dates", "Elderberries"]
+                        .map(SharedString::from)
+                        .to_vec(),
+                    true,
+                    cx,
+                )
+                .reorderable(true)
+            }),
+            breadcrumb: cx
+                .new(|_| Breadcrumb::new(["Home", "Users", "me", "Projects", "gpui", "src"])),
+            accordion: cx.new(|cx| {
+                Accordion::new(cx)
+                    .section("General", text)
+                    .section("Advanced", notes)
+                    .open(0)
+            }),
+            line_chart: cx.new(|_| Sparkline::new(values.clone()).baseline(7.0)),
+            bar_chart: cx.new(|_| Sparkline::new(values).bars()),
+            status: cx.new(|_| StatusBar::new()),
+        }
+    }
+
+    // ==================================================
+    // KNOBS
+    // ==================================================
+
+    fn knob(
+        &self,
+        label: &'static str,
+        value: String,
+        change: impl Fn(&mut Self, bool) + 'static,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let change = std::rc::Rc::new(change);
+        let less = change.clone();
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .child(div().text_color(rgb(0x4D4D4D)).child(label))
+            .child(
+                Button::new("−").on_click(cx.listener(move |this, _, _, cx| {
+                    less(this, false);
+                    cx.notify();
+                })),
+            )
+            .child(div().min_w(px(72.0)).text_center().child(value))
+            .child(
+                Button::new("+").on_click(cx.listener(move |this, _, _, cx| {
+                    change(this, true);
+                    cx.notify();
+                })),
+            )
+    }
+
+    fn render_knobs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        // Step through a list of options, wrapping at the ends
+        fn step(index: &mut usize, len: usize, up: bool) {
+            *index = if up { *index + 1 } else { *index + len - 1 } % len;
+        }
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_4()
+            .p_3()
+            .border_b_1()
+            .border_color(rgb(0xD8D8D8))
+            .child(self.knob(
+                "Value",
+                format!("{:.0}%", self.value * 100.0),
+                |this, up| {
+                    let delta = if up { 0.1 } else { -0.1 };
+                    this.value = (this.value + delta).clamp(0.0, 1.0);
+                },
+                cx,
+            ))
+            .child(self.knob(
+                "Tone",
+                format!("{:?}", TONES[self.tone]),
+                |this, up| step(&mut this.tone, TONES.len(), up),
+                cx,
+            ))
+            .child(self.knob(
+                "Avatar",
+                format!("{:?}", AVATAR_SIZES[self.avatar_size]),
+                |this, up| step(&mut this.avatar_size, AVATAR_SIZES.len(), up),
+                cx,
+            ))
+            .child(self.knob(
+                "Check",
+                format!("{:?}", CHECK_STATES[self.check]),
+                |this, up| step(&mut this.check, CHECK_STATES.len(), up),
+                cx,
+            ))
+            .child(self.knob(
+                "Count",
+                self.count.to_string(),
+                |this, up| {
+                    this.count = if up {
+                        this.count + 1
+                    } else {
+                        this.count.saturating_sub(1)
+                    }
+                },
+                cx,
+            ))
+            .child(self.knob(
+                "Loading",
+                self.loading.to_string(),
+                |this, _| this.loading = !this.loading,
+                cx,
+            ))
+    }
+
+    // ==================================================
+    // TABS
+    // ==================================================
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .gap_1()
+            .px_3()
+            .pt_3()
+            .children(Tab::ALL.map(|tab| {
+                let style = if tab == self.tab {
+                    ButtonStyle::Primary
+                } else {
+                    ButtonStyle::Secondary
+                };
+                Button::new(tab.label()).style(style).on_click(cx.listener(
+                    move |this, _, _, cx| {
+                        this.tab = tab;
+                        cx.notify();
+                    },
+                ))
+            }))
+    }
+
+    fn render_display(&self) -> impl IntoElement {
+        let tone = TONES[self.tone];
+        let avatar_size = AVATAR_SIZES[self.avatar_size];
+        let skeleton = div()
+            .flex()
+            .gap_2()
+            .items_center()
+            .w(px(320.0))
+            .child(Skeleton::circle(px(32.0)).id("avatar"))
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        Skeleton::line()
+                            .id("title")
+                            .until((!self.loading).then(|| div().child("Loaded content"))),
+                    )
+                    .child(Skeleton::line().id("line").width(relative(0.6))),
+            );
+        let progress = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .w(px(320.0))
+            .child(ProgressBar::new(self.value))
+            .child(Meter::new(self.value).label("Disk"));
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(section(
+                "Badges",
+                [
+                    Badge::new("New").tone(tone).into_any_element(),
+                    Badge::new("Subtle").tone(tone).subtle().into_any_element(),
+                    Badge::count(self.count).tone(tone).into_any_element(),
+                ],
+            ))
+            .child(section(
+                "Avatars",
+                ["Ada Lovelace", "Grace Hopper", "Linus"]
+                    .map(|name| Avatar::new(name).size(avatar_size).into_any_element()),
+            ))
+            .child(section(
+                "Buttons",
+                [
+                    Button::new("Primary").style(ButtonStyle::Primary),
+                    Button::new("Secondary"),
+                    Button::new("Inbox").badge(Badge::count(self.count)),
+                ]
+                .map(IntoElement::into_any_element),
+            ))
+            .child(section(
+                "Checkbox and keys",
+                [Checkbox::new(CHECK_STATES[self.check]).into_any_element()]
+                    .into_iter()
+                    .chain(["⌘", "⇧", "P"].map(|key| Kbd::new(key).into_any_element())),
+            ))
+            .child(section("Progress", [progress.into_any_element()]))
+            .child(section(
+                "Gauge",
+                [Gauge::new(self.value).label("CPU").into_any_element()],
+            ))
+            .child(section("Skeleton", [skeleton.into_any_element()]))
+            .child(section(
+                "Empty state",
+                [EmptyState::new("No Results")
+                    .icon("🔍")
+                    .hint("Try a different search")
+                    .into_any_element()],
+            ))
+    }
+
+    fn render_inputs(&self) -> impl IntoElement {
+        let sized = |view: AnyView| div().w(px(320.0)).child(view).into_any_element();
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(section("Text input", [sized(self.text.clone().into())]))
+            .child(section("Tags", [sized(self.tags.clone().into())]))
+            .child(section(
+                "Colors",
+                [self.swatches.clone().into_any_element()],
+            ))
+            .child(section(
+                "Breadcrumb (the … menu takes arrows and letters)",
+                [self.breadcrumb.clone().into_any_element()],
+            ))
+            .child(section(
+                "List (multi-select, drag to reorder)",
+                [div()
+                    .w(px(320.0))
+                    .h(px(160.0))
+                    .child(self.list.clone())
+                    .into_any_element()],
+            ))
+            .child(section("Accordion", [sized(self.accordion.clone().into())]))
+    }
+
+    fn render_charts(&self) -> impl IntoElement {
+        let sized = |view: AnyView| div().w(px(320.0)).child(view).into_any_element();
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(section(
+                "Line, with baseline",
+                [sized(self.line_chart.clone().into())],
+            ))
+            .child(section("Bars", [sized(self.bar_chart.clone().into())]))
+    }
+
+    // ==================================================
+    // DIALOGS
+    // ==================================================
+
+    // A button that opens a dialog and reports its answer in the status bar
+    fn launcher(&self, label: &'static str, open: Launch, cx: &mut Context<Self>) -> AnyElement {
+        Button::new(label)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                let status = this.status.clone();
+                let on_resolve = Box::new(move |result: DialogResult, cx: &mut App| {
+                    status.update(cx, |status, cx| {
+                        status.set_text(Zone::Left, format!("{label}: {:?}", result.outcome), cx)
+                    });
+                });
+                if let Err(err) = open(cx, on_resolve) {
+                    this.status.update(cx, |status, cx| {
+                        status.set_text(Zone::Left, format!("{label}: {err}"), cx)
+                    });
+                }
+            }))
+            .into_any_element()
+    }
+
+    fn render_dialogs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(section(
+                "Dialog kinds",
+                [
+                    self.launcher(
+                        "Question",
+                        |cx, done| shown(Dialog::question("Delete this file?").show(cx, done)),
+                        cx,
+                    ),
+                    self.launcher(
+                        "Hold to delete",
+                        |cx, done| {
+                            let delete = DialogButton::primary("Delete").hold_to_confirm();
+                            shown(
+                                Dialog::question("Delete the whole folder?")
+                                    .buttons([DialogButton::cancel("Cancel"), delete])
+                                    .show(cx, done),
+                            )
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "Form",
+                        |cx, done| {
+                            let fields = [
+                                FormField::text("name", "Name").placeholder("Your name"),
+                                FormField::color("color", "Favorite color"),
+                                FormField::tags("tags", "Tags"),
+                            ];
+                            shown(Dialog::form("Tell us about yourself", fields).show(cx, done))
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "Progress",
+                        |cx, done| {
+                            let (progress, updates) = progress_channel();
+                            cx.spawn(async move |cx| {
+                                for step in 1..=20 {
+                                    cx.background_executor()
+                                        .timer(Duration::from_millis(150))
+                                        .await;
+                                    let fraction = ProgressUpdate::Fraction(step as f32 / 20.0);
+                                    if progress.unbounded_send(fraction).is_err() {
+                                        return; // Cancelled
+                                    }
+                                }
+                                progress.unbounded_send(ProgressUpdate::Finished).ok();
+                            })
+                            .detach();
+                            shown(
+                                Dialog::progress("Copying files…", updates)
+                                    .collapsible()
+                                    .show(cx, done),
+                            )
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "Log",
+                        |cx, done| {
+                            let (lines, receiver) = log_channel();
+                            for line in ["Compiling gpui-dialogs", "Finished in 2.3s"] {
+                                lines.unbounded_send(line.into()).ok();
+                            }
+                            shown(Dialog::log("Build output", receiver).show(cx, done))
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "List",
+                        |cx, done| {
+                            let items = ListItems::new(["Small", "Medium", "Large"]);
+                            shown(Dialog::list("Pick a size", items).show(cx, done))
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "Flow",
+                        |cx, done| {
+                            DialogFlow::new(Dialog::question("Create a new project?"))
+                                .then(|result, _| {
+                                    result.is_confirmed().then(|| {
+                                        Dialog::form("Project", [FormField::text("name", "Name")])
+                                    })
+                                })
+                                .run(cx, done)
+                        },
+                        cx,
+                    ),
+                ],
+            ))
+            .child(section(
+                "Presets",
+                [
+                    self.launcher(
+                        "About",
+                        |cx, done| {
+                            let about = AboutDialog::new(
+                                "Gallery",
+                                "0.1.0",
+                                ["The gpui-dialogs authors"],
+                                "MIT",
+                            );
+                            shown(about.show(cx, done))
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "Color Picker",
+                        |cx, done| shown(ColorPicker::new().show(cx, done)),
+                        cx,
+                    ),
+                    self.launcher(
+                        "Command Palette",
+                        |cx, done| {
+                            let palette = CommandPalette::new()
+                                .command("Say Hello", |cx| set_status("Hello!", cx))
+                                .command("Quit", |cx| cx.quit());
+                            shown(palette.show(cx, done))
+                        },
+                        cx,
+                    ),
+                    self.launcher(
+                        "Directory",
+                        |cx, done| shown(DirectoryPicker::new().show(cx, done)),
+                        cx,
+                    ),
+                    self.launcher(
+                        "Emoji",
+                        |cx, done| shown(EmojiPicker::new().show(cx, done)),
+                        cx,
+                    ),
+                    self.launcher(
+                        "Print",
+                        |cx, done| shown(PrintDialog::new("Cargo.toml").show(cx, done)),
+                        cx,
+                    ),
+                    self.launcher(
+                        "Shortcuts",
+                        |cx, done| shown(ShortcutsDialog::new().show(cx, done)),
+                        cx,
+                    ),
+                ],
+            ))
+    }
+}
+
+type Launch = fn(&mut App, Box<dyn FnOnce(DialogResult, &mut App)>) -> Result<(), DialogError>;
+
+// Show `text` in the status bar of the gallery's window, on the right, as
+// the left is for how the last dialog was answered
+fn set_status(text: &'static str, cx: &mut App) {
+    let galleries: Vec<_> = cx
+        .windows()
+        .into_iter()
+        .filter_map(|window| window.downcast::<Gallery>())
+        .collect();
+    for gallery in galleries {
+        gallery
+            .update(cx, |gallery, _, cx| {
+                gallery
+                    .status
+                    .update(cx, |status, cx| status.set_text(Zone::Right, text, cx))
+            })
+            .ok();
+    }
+}
+
+// The launchers don't keep the handle
+fn shown(handle: Result<DialogHandle, DialogError>) -> Result<(), DialogError> {
+    handle.map(|_| ())
+}
+
+// A titled row of examples
+fn section(title: &'static str, items: impl IntoIterator<Item = AnyElement>) -> impl IntoElement {
+    div()
+        .flex()
+        .flex_col()
+        .gap_2()
+        .child(
+            div()
+                .text_size(px(11.0))
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(rgb(0x8E8E8E))
+                .child(title.to_uppercase()),
+        )
+        .child(
+            div()
+                .flex()
+                .flex_wrap()
+                .items_center()
+                .gap_3()
+                .children(items),
+        )
+}
+
+impl Render for Gallery {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        layout::apply_root_font_size(window, cx);
+        let content = match self.tab {
+            Tab::Display => self.render_display().into_any_element(),
+            Tab::Inputs => self.render_inputs().into_any_element(),
+            Tab::Charts => self.render_charts().into_any_element(),
+            Tab::Dialogs => self.render_dialogs(cx).into_any_element(),
+        };
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(rgb(0xECECEC))
+            .text_size(px(13.0))
+            .child(self.render_tabs(cx))
+            .when(self.tab != Tab::Dialogs, |this| {
+                this.child(self.render_knobs(cx))
+            })
+            .child(
+                div()
+                    .id("content")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .p_4()
+                    .child(content),
+            )
+            .child(self.status.clone())
+    }
+}
+
+fn main() {
+    Application::new().run(|cx: &mut App| {
+        gpui_dialogs::init(cx);
+        let bounds = Bounds::centered(None, size(px(820.0), px(640.0)), cx);
+        let opened = cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |window, cx| {
+                // The gallery's own background is always light
+                theme::set_window_theme(window, ThemeChoice::Light, cx);
+                cx.new(Gallery::new)
+            },
+        );
+        if let Err(err) = opened {
+            eprintln!("error: couldn't open the gallery: {err}");
+            cx.quit();
+        }
+        cx.activate(true);
+    });
+}
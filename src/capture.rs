@@ -0,0 +1,196 @@
+// ======================================================================
+// SCREENSHOTS
+// ======================================================================
+// A picture of the dialog as it is on screen, for documentation and bug
+// reports, saved as a PNG or put on the clipboard:
+//
+//     Dialog::question("Delete the file?")
+//         .capture(Capture::file("docs/delete@2x.png"), |result, _| {
+//             if let Err(err) = result {
+//                 eprintln!("screenshot failed: {err}");
+//             }
+//         })
+//         .show(cx, |_, _| {});
+//
+// GPUI can't read back what it drew, so the picture is taken by the
+// system's own screenshot tool over the window's bounds: screencapture
+// on macOS and ImageMagick's import on X11. Wayland doesn't tell apps
+// where their windows are, so there it fails as unsupported. The picture
+// is then scaled to the size asked for, 1x by default and from 0.25x to
+// 4x. A file name ending in "@2x" asks for 2x, the way macOS names high
+// resolution images.
+
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use gpui::{App, AppContext, Bounds, ClipboardItem, Pixels, Task, Window};
+use image::imageops::FilterType;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CaptureTarget {
+    File(PathBuf),
+    Clipboard,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capture {
+    target: CaptureTarget,
+    // Image pixels per window pixel
+    scale: f32,
+}
+
+impl Capture {
+    // Save a PNG; "name@2x.png" is saved at twice the window's size
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let scale = scale_from_name(&path).unwrap_or(1.0);
+        Self {
+            target: CaptureTarget::File(path),
+            scale: 1.0,
+        }
+        .scale(scale)
+    }
+
+    pub fn clipboard() -> Self {
+        Self {
+            target: CaptureTarget::Clipboard,
+            scale: 1.0,
+        }
+    }
+
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = if scale.is_nan() {
+            1.0
+        } else {
+            scale.clamp(0.25, 4.0)
+        };
+        self
+    }
+
+    pub fn target(&self) -> &CaptureTarget {
+        &self.target
+    }
+
+    // Take the screenshot of `window`. Only the final hand-off to the
+    // clipboard happens on the main thread.
+    pub fn take(self, window: &Window, cx: &mut App) -> Task<Result<(), String>> {
+        let bounds = window.bounds();
+        let scale_factor = window.scale_factor();
+        let png = cx.background_spawn(async move {
+            let shot = screenshot(bounds, scale_factor)?;
+            let width = (f32::from(bounds.size.width) * self.scale).round() as u32;
+            let height = (f32::from(bounds.size.height) * self.scale).round() as u32;
+            let shot = if shot.width() == width && shot.height() == height {
+                shot
+            } else {
+                shot.resize_exact(width, height, FilterType::Lanczos3)
+            };
+            let mut png = Vec::new();
+            shot.write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|err| err.to_string())?;
+            Ok::<_, String>(png)
+        });
+
+        cx.spawn(async move |cx| {
+            let png = png.await?;
+            match self.target {
+                CaptureTarget::File(path) => {
+                    fs::write(&path, png).map_err(|err| format!("{}: {err}", path.display()))
+                }
+                CaptureTarget::Clipboard => cx
+                    .update(|cx| {
+                        let image = gpui::Image::from_bytes(gpui::ImageFormat::Png, png);
+                        cx.write_to_clipboard(ClipboardItem::new_image(&image));
+                    })
+                    .map_err(|err| err.to_string()),
+            }
+        })
+    }
+}
+
+// 2.0 for "about@2x.png"
+fn scale_from_name(path: &Path) -> Option<f32> {
+    let stem = path.file_stem()?.to_str()?;
+    let (_, suffix) = stem.rsplit_once('@')?;
+    suffix.strip_suffix('x')?.parse().ok()
+}
+
+// Numbers the screenshot tool's files, for captures taken at once
+static SHOTS: AtomicUsize = AtomicUsize::new(0);
+
+// The screen inside `bounds` (screen coordinates, in logical pixels), as
+// the system's screenshot tool sees it
+fn screenshot(bounds: Bounds<Pixels>, scale_factor: f32) -> Result<image::DynamicImage, String> {
+    let file = std::env::temp_dir().join(format!(
+        "gpui-dialogs-{}-{}.png",
+        std::process::id(),
+        SHOTS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let (x, y) = (f32::from(bounds.origin.x), f32::from(bounds.origin.y));
+    let (width, height) = (f32::from(bounds.size.width), f32::from(bounds.size.height));
+
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("screencapture");
+        command
+            .arg("-x") // No shutter sound
+            .arg(format!("-R{x},{y},{width},{height}"))
+            .arg(&file);
+        command
+    } else if cfg!(target_os = "linux") && std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        // window.bounds() has no global origin there to crop to
+        return Err("screenshots aren't supported on Wayland".to_string());
+    } else if cfg!(target_os = "linux") {
+        // X11 works in device pixels
+        let device = |value: f32| (value * scale_factor).round() as i32;
+        let mut command = Command::new("import");
+        command
+            .args(["-silent", "-window", "root", "-crop"])
+            .arg(format!(
+                "{}x{}+{}+{}",
+                device(width),
+                device(height),
+                device(x),
+                device(y)
+            ))
+            .arg(&file);
+        command
+    } else {
+        return Err("screenshots aren't supported on this platform".to_string());
+    };
+
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command
+        .status()
+        .map_err(|err| format!("couldn't run {program}: {err}"))?;
+    if !status.success() {
+        return Err(format!("{program} failed: {status}"));
+    }
+    let shot = image::open(&file).map_err(|err| err.to_string());
+    fs::remove_file(&file).ok();
+    shot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capture;
+
+    #[test]
+    fn scale_from_the_file_name() {
+        assert_eq!(Capture::file("about.png").scale, 1.0);
+        assert_eq!(Capture::file("about@2x.png").scale, 2.0);
+        assert_eq!(Capture::file("about@1.5x.png").scale, 1.5);
+        assert_eq!(Capture::file("me@home.png").scale, 1.0);
+    }
+
+    #[test]
+    fn scale_stays_within_bounds() {
+        assert_eq!(Capture::file("shot@0x.png").scale, 0.25);
+        assert_eq!(Capture::file("shot@infx.png").scale, 4.0);
+        assert_eq!(Capture::file("shot@1e9x.png").scale, 4.0);
+        assert_eq!(Capture::file("shot@NaNx.png").scale, 1.0);
+        assert_eq!(Capture::clipboard().scale(-3.0).scale, 0.25);
+    }
+}
@@ -0,0 +1,221 @@
+// ======================================================================
+// COMMAND LINE ARGUMENTS
+// ======================================================================
+// The tutorial binary doubles as a small scripting tool: it shows a
+// dialog and reports what the user chose. These are the flags it
+// understands. Both `--flag value` and `--flag=value` work. They're parsed
+// here in the library, where the fuzz targets can reach them.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::backdrop::BackdropMode;
+
+pub const USAGE: &str = "\
+Usage: gpui [OPTIONS]
+
+Options:
+  --result-file <PATH>       Also write the result to PATH (atomically)
+  --result-format <FORMAT>   How to print the result: plain (default) or json
+  --on-ok <COMMAND>          Run COMMAND in the shell when the dialog is confirmed
+  --on-cancel <COMMAND>      Run COMMAND in the shell when the dialog is cancelled
+  --show-hook-output         Show the command's output in a follow-up log dialog
+  --answer-keys              Answer with a single key: Y or N, or the button's number
+  --timeout <SECONDS>        Close the dialog if it isn't answered in time
+  --screenshot <PATH>        Save a PNG of the dialog once it's shown (name@2x.png for 2x)
+  --audit-log <PATH>         Append an audit record of the answer to PATH (or `syslog`)
+  --backdrop <MODE>          Behind the dialog: dim (default), frame or off
+  --check-theme <PATH>       Check a theme file for invalid colors and poor contrast, then exit
+  --lite                     Draw plain rectangles: no shadows or rounded corners
+  --theme-preview            Show the dialog in light and dark side by side, redrawn as theme.json changes
+  --trace <PATH>             Record frame and event timings to PATH as a Chrome trace (for Perfetto)
+  --stress <N>               Open and answer N dialogs in a row, then report latency, heap growth and leaks
+  --stress-random            With --stress, vary the dialogs' theme, size and message length
+  --crash-report <PATH>      Show a crash report (used by the crash handler)
+  -h, --help                 Print this help";
+
+// How the result is printed to stdout and the result file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResultFormat {
+    // The button label on the first line, then one key=value line per value
+    #[default]
+    Plain,
+    // A single JSON object
+    Json,
+}
+
+impl FromStr for ResultFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown result format `{other}` (expected plain or json)"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Args {
+    pub result_file: Option<PathBuf>,
+    pub result_format: ResultFormat,
+    pub on_ok: Option<String>,
+    pub on_cancel: Option<String>,
+    pub show_hook_output: bool,
+    pub answer_keys: bool,
+    pub timeout: Option<Duration>,
+    pub screenshot: Option<PathBuf>,
+    pub audit_log: Option<String>,
+    pub theme_preview: bool,
+    pub check_theme: Option<PathBuf>,
+    pub lite: bool,
+    pub trace: Option<PathBuf>,
+    pub stress: Option<usize>,
+    pub stress_random: bool,
+    pub backdrop: Option<BackdropMode>,
+    pub crash_report: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // Split `--flag=value` into its two halves
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{flag} needs a value"))
+            };
+
+            match flag.as_str() {
+                "--result-file" => parsed.result_file = Some(PathBuf::from(value()?)),
+                "--result-format" => parsed.result_format = value()?.parse()?,
+                "--on-ok" => parsed.on_ok = Some(value()?),
+                "--on-cancel" => parsed.on_cancel = Some(value()?),
+                "--show-hook-output" => parsed.show_hook_output = true,
+                "--answer-keys" => parsed.answer_keys = true,
+                "--timeout" => parsed.timeout = Some(parse_seconds(&value()?)?),
+                "--screenshot" => parsed.screenshot = Some(PathBuf::from(value()?)),
+                "--audit-log" => parsed.audit_log = Some(value()?),
+                "--theme-preview" => parsed.theme_preview = true,
+                "--check-theme" => parsed.check_theme = Some(PathBuf::from(value()?)),
+                "--lite" => parsed.lite = true,
+                "--trace" => parsed.trace = Some(PathBuf::from(value()?)),
+                "--stress" => parsed.stress = Some(parse_count(&value()?)?),
+                "--stress-random" => parsed.stress_random = true,
+                "--backdrop" => parsed.backdrop = Some(parse_backdrop(&value()?)?),
+                "--crash-report" => parsed.crash_report = Some(PathBuf::from(value()?)),
+                "-h" | "--help" => parsed.help = true,
+                _ => return Err(format!("unknown argument `{flag}`")),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn parse_backdrop(value: &str) -> Result<BackdropMode, String> {
+    match value {
+        "dim" => Ok(BackdropMode::Dim),
+        "frame" => Ok(BackdropMode::Frame),
+        "off" => Ok(BackdropMode::Off),
+        other => Err(format!(
+            "unknown backdrop `{other}` (expected dim, frame or off)"
+        )),
+    }
+}
+
+// A positive whole number, of dialogs
+fn parse_count(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("`{value}` is not a positive whole number")),
+    }
+}
+
+// A positive number of seconds, fractions allowed ("2.5")
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("`{value}` is not a positive number of seconds")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{Args, ResultFormat};
+    use crate::backdrop::BackdropMode;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn no_arguments_means_the_defaults() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.result_format, ResultFormat::Plain);
+        assert_eq!(args.timeout, None);
+        assert!(!args.help);
+    }
+
+    #[test]
+    fn values_follow_the_flag_or_an_equals_sign() {
+        let args = parse(&[
+            "--result-file",
+            "out.txt",
+            "--result-format=json",
+            "--timeout=2.5",
+            "--on-ok",
+            "echo a=b",
+            "--backdrop",
+            "frame",
+            "--stress=3",
+            "--answer-keys",
+        ])
+        .unwrap();
+        assert_eq!(args.result_file, Some(PathBuf::from("out.txt")));
+        assert_eq!(args.result_format, ResultFormat::Json);
+        assert_eq!(args.timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(args.on_ok.as_deref(), Some("echo a=b"));
+        assert_eq!(args.backdrop, Some(BackdropMode::Frame));
+        assert_eq!(args.stress, Some(3));
+        assert!(args.answer_keys);
+    }
+
+    #[test]
+    fn rejects_bad_flags_and_values() {
+        assert_eq!(
+            parse(&["--frobnicate"]).unwrap_err(),
+            "unknown argument `--frobnicate`"
+        );
+        assert_eq!(
+            parse(&["--result-file"]).unwrap_err(),
+            "--result-file needs a value"
+        );
+        assert_eq!(
+            parse(&["--result-format=xml"]).unwrap_err(),
+            "unknown result format `xml` (expected plain or json)"
+        );
+        assert!(parse(&["--timeout", "0"]).is_err());
+        assert!(parse(&["--timeout", "inf"]).is_err());
+        assert!(parse(&["--stress", "-1"]).is_err());
+        assert!(parse(&["--backdrop", "blur"]).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+// ======================================================================
+// ACCORDION COMPONENT
+// ======================================================================
+// A stack of sections with clickable headers that fold their content
+// away, for dialogs with more options than fit on screen at once:
+//
+//     cx.new(|cx| {
+//         Accordion::new(cx)
+//             .section("General", general)
+//             .section("Advanced", advanced)
+//             .single_open()
+//     })
+//
+// Each section's content is a view (usually an entity holding inputs).
+// By default any number of sections can be open; with single_open()
+// opening one closes the others. Sections slide open and closed.
+//
+// Keyboard: Up/Down move between headers, Space or Enter toggles one.
+
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+const HEADER_HEIGHT: f32 = 32.0;
+const ANIMATION: Duration = Duration::from_millis(200);
+// Content is revealed up to this height while it animates, then shown in
+// full. Taller content just finishes its slide with a jump.
+const MAX_ANIMATED_HEIGHT: f32 = 400.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SectionState {
+    Closed,
+    Open,
+    // Still sliding shut; becomes Closed when the animation ends
+    Closing,
+}
+
+struct Section {
+    title: SharedString,
+    content: AnyView,
+    state: SectionState,
+    // Changes on every toggle, so the slide restarts (and a stale timer
+    // from an earlier close can tell it's stale)
+    generation: usize,
+}
+
+pub struct Accordion {
+    focus_handle: FocusHandle,
+    sections: Vec<Section>,
+    single_open: bool,
+    // Header under the keyboard cursor
+    cursor: usize,
+    generations: usize,
+}
+
+impl Accordion {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle().tab_stop(true),
+            sections: Vec::new(),
+            single_open: false,
+            cursor: 0,
+            generations: 0,
+        }
+    }
+
+    pub fn section(mut self, title: impl Into<SharedString>, content: impl Into<AnyView>) -> Self {
+        self.sections.push(Section {
+            title: title.into(),
+            content: content.into(),
+            state: SectionState::Closed,
+            generation: 0,
+        });
+        self
+    }
+
+    // Opening a section closes the one that was open
+    pub fn single_open(mut self) -> Self {
+        self.single_open = true;
+        self
+    }
+
+    // Start with a section open (without animating it)
+    pub fn open(mut self, index: usize) -> Self {
+        if self.single_open {
+            for section in &mut self.sections {
+                section.state = SectionState::Closed;
+            }
+        }
+        if let Some(section) = self.sections.get_mut(index) {
+            section.state = SectionState::Open;
+        }
+        self
+    }
+
+    pub fn is_open(&self, index: usize) -> bool {
+        self.sections
+            .get(index)
+            .is_some_and(|section| section.state == SectionState::Open)
+    }
+
+    pub fn toggle(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index >= self.sections.len() {
+            return;
+        }
+        if self.is_open(index) {
+            self.close(index, cx);
+        } else {
+            if self.single_open {
+                for other in 0..self.sections.len() {
+                    if other != index && self.is_open(other) {
+                        self.close(other, cx);
+                    }
+                }
+            }
+            self.generations += 1;
+            let section = &mut self.sections[index];
+            section.state = SectionState::Open;
+            section.generation = self.generations;
+        }
+        cx.notify();
+    }
+
+    fn close(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.generations += 1;
+        let generation = self.generations;
+        let section = &mut self.sections[index];
+        section.state = SectionState::Closing;
+        section.generation = generation;
+
+        cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(ANIMATION).await;
+            this.update(cx, |this, cx| {
+                let section = &mut this.sections[index];
+                // Unless it was reopened in the meantime
+                if section.generation == generation {
+                    section.state = SectionState::Closed;
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        // Keys typed into a section's content are none of our business
+        if !self.focus_handle.is_focused(window) || self.sections.is_empty() {
+            return;
+        }
+        let last = self.sections.len() - 1;
+        match event.keystroke.key.as_str() {
+            "down" => self.cursor = (self.cursor + 1).min(last),
+            "up" => self.cursor = self.cursor.saturating_sub(1),
+            "space" | "enter" => self.toggle(self.cursor, cx),
+            _ => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn render_section(
+        &self,
+        index: usize,
+        focused: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let section = &self.sections[index];
+        let open = section.state == SectionState::Open;
+        let under_cursor = focused && index == self.cursor;
+
+        let header = div()
+            .id(("header", index))
+            .flex()
+            .items_center()
+            .gap_2()
+            .h(px(HEADER_HEIGHT))
+            .px_2()
+            .bg(rgb(0xF5F5F5))
+            .font_weight(FontWeight::MEDIUM)
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(0xECECEC)))
+            .when(under_cursor, |this| {
+                this.border_1().border_dashed().border_color(rgb(0x007AFF))
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                window.focus(&this.focus_handle);
+                this.cursor = index;
+                this.toggle(index, cx);
+            }))
+            .child(
+                div()
+                    .w(px(10.0))
+                    .text_size(px(10.0))
+                    .text_color(rgb(0x8E8E8E))
+                    .child(if open { "▾" } else { "▸" }),
+            )
+            .child(section.title.clone());
+
+        let body = (section.state != SectionState::Closed).then(|| {
+            let body = div()
+                .overflow_hidden()
+                .child(div().p_3().child(section.content.clone()));
+            // Sections open from the start haven't been toggled yet
+            if section.generation == 0 {
+                return body.into_any_element();
+            }
+            body.with_animation(
+                ("body", section.generation),
+                Animation::new(ANIMATION).with_easing(ease_in_out),
+                move |body, delta| {
+                    let shown = if open { delta } else { 1.0 - delta };
+                    if shown >= 1.0 {
+                        body
+                    } else {
+                        body.max_h(px(MAX_ANIMATED_HEIGHT * shown)).opacity(shown)
+                    }
+                },
+            )
+            .into_any_element()
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .when(index > 0, |this| {
+                this.border_t_1().border_color(rgb(0xD8D8D8))
+            })
+            .child(header)
+            .children(body)
+    }
+}
+
+impl Focusable for Accordion {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for Accordion {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.focus_handle.is_focused(window);
+        let sections: Vec<_> = (0..self.sections.len())
+            .map(|index| self.render_section(index, focused, cx))
+            .collect();
+
+        div()
+            .flex()
+            .flex_col()
+            .bg(rgb(0xFFFFFF))
+            .border_1()
+            .border_color(rgb(0xB8B8B8))
+            .rounded(px(5.0))
+            .overflow_hidden()
+            .text_size(px(13.0))
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .children(sections)
+    }
+}
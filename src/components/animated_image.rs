@@ -0,0 +1,242 @@
+// ======================================================================
+// ANIMATED IMAGE COMPONENT
+// ======================================================================
+// An animated GIF or PNG that can be paused, for things like a short
+// demo clip in an update dialog. Each frame stays up as long as the file
+// says, and a button in the corner plays and pauses.
+//
+//     let clip = cx.new(|cx| {
+//         AnimatedImage::new("assets/whats-new.gif", window, cx)
+//             .size(px(320.0), px(180.0))
+//     });
+//
+// The animation holds still, whatever the button says, while the window
+// is in the background and when the user asked for reduced motion (see
+// motion.rs), and drops to a lower frame rate when saving power. Still pictures are shown as they are, without the button.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use image::{Frame, RgbaImage};
+
+use crate::components::{ImageSource, ImageState, Skeleton};
+use crate::{motion, power};
+
+// Browsers treat shorter frame delays as "unspecified" and use this
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+const MIN_DELAY: Duration = Duration::from_millis(20);
+// About 15 frames a second while saving power
+const SAVING_MIN_DELAY: Duration = Duration::from_millis(66);
+
+pub struct AnimatedImage {
+    source: ImageSource,
+    // Each frame on its own, so gpui draws the one we pick instead of
+    // animating by itself. Empty until the picture has loaded.
+    frames: Vec<(Arc<RenderImage>, Duration)>,
+    failed: bool,
+    frame: usize,
+    // What the play/pause button says
+    playing: bool,
+    window_active: bool,
+    width: Pixels,
+    height: Pixels,
+    _ticker: Option<Task<()>>,
+    _activation: Subscription,
+}
+
+impl AnimatedImage {
+    pub fn new(
+        source: impl Into<ImageSource>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let activation = cx.observe_window_activation(window, |this, window, cx| {
+            this.window_active = window.is_window_active();
+            this.update_ticker(cx);
+        });
+        Self {
+            source: source.into(),
+            frames: Vec::new(),
+            failed: false,
+            frame: 0,
+            playing: true,
+            window_active: window.is_window_active(),
+            width: px(320.0),
+            height: px(180.0),
+            _ticker: None,
+            _activation: activation,
+        }
+    }
+
+    pub fn size(mut self, width: Pixels, height: Pixels) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    // Start paused, for clips the user should choose to watch
+    pub fn paused(mut self) -> Self {
+        self.playing = false;
+        self
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn play(&mut self, cx: &mut Context<Self>) {
+        self.playing = true;
+        self.update_ticker(cx);
+        cx.notify();
+    }
+
+    pub fn pause(&mut self, cx: &mut Context<Self>) {
+        self.playing = false;
+        self.update_ticker(cx);
+        cx.notify();
+    }
+
+    pub fn toggle(&mut self, cx: &mut Context<Self>) {
+        if self.playing {
+            self.pause(cx);
+        } else {
+            self.play(cx);
+        }
+    }
+
+    fn animating(&self, cx: &App) -> bool {
+        self.playing && self.window_active && self.frames.len() > 1 && !motion::reduced_motion(cx)
+    }
+
+    // Run the ticker while animating, drop it otherwise. The frame shown
+    // stays where it is, so playing again carries on from there.
+    fn update_ticker(&mut self, cx: &mut Context<Self>) {
+        if !self.animating(cx) {
+            self._ticker = None;
+            return;
+        }
+        if self._ticker.is_some() {
+            return;
+        }
+        self._ticker = Some(cx.spawn(async move |this, cx| loop {
+            let Ok(delay) = this.read_with(cx, |this, cx| this.frame_delay(cx)) else {
+                return; // The image is gone
+            };
+            cx.background_executor().timer(delay).await;
+            let advanced = this.update(cx, |this, cx| {
+                this.frame = (this.frame + 1) % this.frames.len();
+                cx.notify();
+            });
+            if advanced.is_err() {
+                return;
+            }
+        }));
+    }
+
+    // How long the current frame stays up, stretched when saving power
+    fn frame_delay(&self, cx: &App) -> Duration {
+        let delay = self.frames[self.frame].1;
+        if power::saving(cx) {
+            delay.max(SAVING_MIN_DELAY)
+        } else {
+            delay
+        }
+    }
+
+    // Take the loaded picture apart into frames the first time it's ready
+    fn load(&mut self, cx: &mut Context<Self>) {
+        match self.source.load(cx) {
+            ImageState::Loading => {}
+            ImageState::Failed(_) => self.failed = true,
+            ImageState::Ready(image) => self.frames = split_frames(&image),
+        }
+    }
+
+    fn render_play_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("play-pause")
+            .absolute()
+            .bottom_2()
+            .left_2()
+            .flex()
+            .items_center()
+            .justify_center()
+            .size(px(24.0))
+            .rounded_full()
+            .bg(rgba(0x000000AA))
+            .text_color(rgb(0xFFFFFF))
+            .text_size(px(10.0))
+            .cursor_pointer()
+            .child(if self.playing { "❚❚" } else { "▶" })
+            .on_click(cx.listener(|this, _, _, cx| this.toggle(cx)))
+    }
+}
+
+// One single-frame image per frame, with how long it stays up. The
+// pixels are already BGRA, so they're copied as they are.
+fn split_frames(image: &Arc<RenderImage>) -> Vec<(Arc<RenderImage>, Duration)> {
+    if image.frame_count() <= 1 {
+        return vec![(image.clone(), DEFAULT_DELAY)];
+    }
+    (0..image.frame_count())
+        .filter_map(|index| {
+            let size = image.size(index);
+            let pixels = image.as_bytes(index)?.to_vec();
+            let buffer = RgbaImage::from_raw(size.width.0 as u32, size.height.0 as u32, pixels)?;
+            let delay = Duration::from(image.delay(index));
+            let delay = if delay < MIN_DELAY {
+                DEFAULT_DELAY
+            } else {
+                delay
+            };
+            Some((Arc::new(RenderImage::new([Frame::new(buffer)])), delay))
+        })
+        .collect()
+}
+
+impl Render for AnimatedImage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.frames.is_empty() && !self.failed {
+            self.load(cx);
+        }
+        // Reduced motion may have been switched since the last frame
+        self.update_ticker(cx);
+
+        let Some((frame, _)) = self.frames.get(self.frame) else {
+            return if self.failed {
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .w(self.width)
+                    .h(self.height)
+                    .rounded(px(6.0))
+                    .bg(rgb(0xE5E5EA))
+                    .text_color(rgb(0x8E8E8E))
+                    .child("⚠")
+                    .into_any_element()
+            } else {
+                Skeleton::rect(self.width, self.height).into_any_element()
+            };
+        };
+
+        div()
+            .relative()
+            .flex_none()
+            .w(self.width)
+            .h(self.height)
+            .child(
+                img(frame.clone())
+                    .size_full()
+                    .rounded(px(6.0))
+                    .object_fit(ObjectFit::Contain),
+            )
+            .when(
+                self.frames.len() > 1 && !motion::reduced_motion(cx),
+                |this| this.child(self.render_play_button(cx)),
+            )
+            .into_any_element()
+    }
+}
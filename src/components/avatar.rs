@@ -0,0 +1,119 @@
+// ======================================================================
+// AVATAR COMPONENT
+// ======================================================================
+// A person's picture in a circle, for login and "choose a user" dialogs.
+// Without a picture (or if it fails to load) the avatar shows their
+// initials on a color picked from their name, so the same person always
+// gets the same color. While the picture loads it's a skeleton circle.
+//
+//     Avatar::new("Ada Lovelace").image(photo_path).size(AvatarSize::Large)
+
+use gpui::*;
+
+use crate::components::{ImageSource, ImageState, Skeleton};
+
+// Initials backgrounds, from the macOS system colors that keep white text
+// readable
+const COLORS: [u32; 9] = [
+    0xFF3B30, 0xFF9500, 0x34C759, 0x00C7BE, 0x32ADE6, 0x007AFF, 0x5856D6, 0xAF52DE, 0xA2845E,
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AvatarSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl AvatarSize {
+    pub fn pixels(self) -> Pixels {
+        px(match self {
+            AvatarSize::Small => 24.0,
+            AvatarSize::Medium => 32.0,
+            AvatarSize::Large => 48.0,
+            AvatarSize::ExtraLarge => 64.0,
+        })
+    }
+}
+
+// "Ada Lovelace" -> "AL", "ada" -> "A"
+pub fn initials(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next().and_then(|word| word.chars().next());
+    let last = words.last().and_then(|word| word.chars().next());
+    let initials: String = first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+#[derive(IntoElement)]
+pub struct Avatar {
+    name: SharedString,
+    image: Option<ImageSource>,
+    size: AvatarSize,
+}
+
+impl Avatar {
+    pub fn new(name: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            image: None,
+            size: AvatarSize::default(),
+        }
+    }
+
+    // A file path, URL or bytes
+    pub fn image(mut self, image: impl Into<ImageSource>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    pub fn size(mut self, size: AvatarSize) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+fn render_initials(name: &str, size: Pixels) -> AnyElement {
+    let color = COLORS[name.bytes().map(usize::from).sum::<usize>() % COLORS.len()];
+    div()
+        .flex()
+        .flex_none()
+        .items_center()
+        .justify_center()
+        .size(size)
+        .rounded_full()
+        .bg(rgb(color))
+        .text_color(rgb(0xFFFFFF))
+        .text_size(size * 0.4)
+        .font_weight(FontWeight::SEMIBOLD)
+        .child(initials(name))
+        .into_any_element()
+}
+
+impl RenderOnce for Avatar {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let size = self.size.pixels();
+        let state = self.image.map(|image| image.load(cx));
+
+        match state {
+            Some(ImageState::Loading) => Skeleton::circle(size).into_any_element(),
+            Some(ImageState::Ready(image)) => img(image)
+                .flex_none()
+                .size(size)
+                .rounded_full()
+                .object_fit(ObjectFit::Cover)
+                .into_any_element(),
+            Some(ImageState::Failed(_)) | None => render_initials(&self.name, size),
+        }
+    }
+}
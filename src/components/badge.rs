@@ -0,0 +1,121 @@
+// ======================================================================
+// BADGE COMPONENT
+// ======================================================================
+// Small rounded labels that sit next to other content: a count bubble
+// ("3", "99+"), a "NEW" tag, or a severity pill ("Warning").
+//
+//     Badge::count(pending)
+//     Badge::new("NEW").tone(Tone::Accent)
+//     Badge::new("Critical").tone(Tone::Critical).subtle()
+//
+// Colors come from the tone, which maps onto the macOS system colors used
+// throughout the dialogs. Like Button it holds no state; Button::badge()
+// puts one inside a button.
+
+use gpui::*;
+
+use crate::restyle;
+
+// Counts above this show as "99+"
+const MAX_COUNT: usize = 99;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tone {
+    #[default]
+    Neutral,
+    Accent,
+    Success,
+    Warning,
+    Critical,
+}
+
+impl Tone {
+    pub fn color(self) -> Rgba {
+        rgb(match self {
+            Tone::Neutral => 0x8E8E93,
+            Tone::Accent => 0x007AFF,
+            Tone::Success => 0x34C759,
+            Tone::Warning => 0xFF9500,
+            Tone::Critical => 0xFF3B30,
+        })
+    }
+}
+
+#[derive(IntoElement)]
+pub struct Badge {
+    label: SharedString,
+    tone: Tone,
+    // Tinted background with colored text, instead of white on color
+    subtle: bool,
+    // A round bubble (counts) rather than a pill
+    bubble: bool,
+}
+
+impl Badge {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            tone: Tone::Neutral,
+            subtle: false,
+            bubble: false,
+        }
+    }
+
+    // A red count bubble, as on app icons
+    pub fn count(count: usize) -> Self {
+        let label = if count > MAX_COUNT {
+            format!("{MAX_COUNT}+")
+        } else {
+            count.to_string()
+        };
+        Self {
+            bubble: true,
+            ..Self::new(label).tone(Tone::Critical)
+        }
+    }
+
+    pub fn tone(mut self, tone: Tone) -> Self {
+        self.tone = tone;
+        self
+    }
+
+    pub fn subtle(mut self) -> Self {
+        self.subtle = true;
+        self
+    }
+}
+
+impl RenderOnce for Badge {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let color = self.tone.color();
+        let (bg, text_color) = if self.subtle {
+            (Rgba { a: 0.16, ..color }, color)
+        } else {
+            (color, rgb(0xFFFFFF))
+        };
+
+        let badge = div()
+            .flex()
+            .flex_none()
+            .items_center()
+            .justify_center()
+            .rounded_full()
+            .bg(bg)
+            .text_color(text_color)
+            .text_size(px(10.0))
+            .font_weight(FontWeight::SEMIBOLD)
+            .whitespace_nowrap();
+
+        let badge = if self.bubble {
+            // Round for one digit, stretching into a pill for more
+            badge
+                .min_w(px(16.0))
+                .h(px(16.0))
+                .px(px(4.0))
+                .child(self.label)
+        } else {
+            badge.h(px(18.0)).px_2().child(self.label)
+        };
+        restyle::apply("Badge", badge, cx)
+    }
+}
@@ -0,0 +1,179 @@
+// ======================================================================
+// BREADCRUMB COMPONENT
+// ======================================================================
+// Where you are, as a row of clickable segments: Home ▸ Documents ▸
+// Projects. Clicking a segment emits Navigate with its index, and the
+// owner goes there. The last segment is the current place, so it isn't a
+// link.
+//
+// Long paths keep their first segment and the last few; the ones in
+// between collapse into a "…" button that lists them in a menu, which
+// can be gone through with the keyboard too.
+
+use std::ops::Range;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::menu::{Menu, MenuEvent, MenuItem};
+use crate::components::popup::popup;
+
+// Emitted when a segment (or a collapsed one in the menu) is clicked
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Navigate(pub usize);
+
+pub struct Breadcrumb {
+    segments: Vec<SharedString>,
+    // Segments shown before the middle ones collapse into "…"
+    max_visible: usize,
+    // The open "…" menu
+    menu: Option<(Entity<Menu>, Subscription)>,
+}
+
+impl EventEmitter<Navigate> for Breadcrumb {}
+
+impl Breadcrumb {
+    pub fn new(segments: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        Self {
+            segments: segments.into_iter().map(Into::into).collect(),
+            max_visible: 4,
+            menu: None,
+        }
+    }
+
+    // At least two: the first segment and the current one
+    pub fn max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible.max(2);
+        self
+    }
+
+    pub fn set_segments(
+        &mut self,
+        segments: impl IntoIterator<Item = impl Into<SharedString>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.segments = segments.into_iter().map(Into::into).collect();
+        self.menu = None;
+        cx.notify();
+    }
+
+    // The segments collapsed into the "…" menu (empty when all fit)
+    fn collapsed(&self) -> Range<usize> {
+        let count = self.segments.len();
+        if count <= self.max_visible {
+            return 1..1;
+        }
+        1..count - (self.max_visible - 1)
+    }
+
+    fn navigate(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.menu = None;
+        cx.emit(Navigate(index));
+        cx.notify();
+    }
+
+    fn render_segment(&self, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let current = index + 1 == self.segments.len();
+        div()
+            .id(("segment", index))
+            .flex_none()
+            .max_w(px(160.0))
+            .px_1()
+            .rounded(px(3.0))
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .text_ellipsis()
+            .when(current, |this| {
+                this.text_color(rgb(0x000000))
+                    .font_weight(FontWeight::MEDIUM)
+            })
+            .when(!current, |this| {
+                this.text_color(rgb(0x4D4D4D))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0xE4EEFB)).text_color(rgb(0x007AFF)))
+                    .on_click(cx.listener(move |this, _, _, cx| this.navigate(index, cx)))
+            })
+            .child(self.segments[index].clone())
+    }
+
+    fn toggle_menu(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.menu.take().is_none() {
+            // Items are named by the index of their segment
+            let items = self
+                .collapsed()
+                .map(|index| MenuItem::action(index.to_string(), self.segments[index].clone()))
+                .collect();
+            let menu = cx.new(|cx| Menu::new(items, cx));
+            let subscription = cx.subscribe(&menu, |this, _, event, cx| match event {
+                MenuEvent::Picked(id) => {
+                    if let Ok(index) = id.parse() {
+                        this.navigate(index, cx);
+                    }
+                }
+                MenuEvent::Dismissed => {
+                    this.menu = None;
+                    cx.notify();
+                }
+            });
+            window.focus(&menu.focus_handle(cx));
+            self.menu = Some((menu, subscription));
+        }
+        cx.notify();
+    }
+
+    fn render_overflow(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let menu = self.menu.as_ref().map(|(menu, _)| menu.clone());
+        div()
+            .relative()
+            .child(
+                div()
+                    .id("overflow")
+                    .px_1()
+                    .rounded(px(3.0))
+                    .text_color(rgb(0x4D4D4D))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0xE4EEFB)))
+                    .when(menu.is_some(), |this| this.bg(rgb(0xE4EEFB)))
+                    .on_click(cx.listener(|this, _, window, cx| this.toggle_menu(window, cx)))
+                    .child("…"),
+            )
+            // The menu floats over whatever is below the bar
+            .when_some(menu, |this, menu| {
+                this.child(popup(point(px(0.0), px(20.0)), menu))
+            })
+    }
+}
+
+impl Render for Breadcrumb {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let collapsed = self.collapsed();
+        let separator = || {
+            div()
+                .flex_none()
+                .text_size(px(10.0))
+                .text_color(rgb(0xB8B8B8))
+                .child("▸")
+        };
+
+        let mut bar = div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .h(px(24.0))
+            .overflow_hidden()
+            .text_size(px(12.0));
+        for index in 0..self.segments.len() {
+            if collapsed.contains(&index) {
+                if index == collapsed.start {
+                    bar = bar.child(separator()).child(self.render_overflow(cx));
+                }
+                continue;
+            }
+            if index > 0 {
+                bar = bar.child(separator());
+            }
+            bar = bar.child(self.render_segment(index, cx));
+        }
+        bar
+    }
+}
@@ -0,0 +1,181 @@
+// ======================================================================
+// BUTTON COMPONENT
+// ======================================================================
+// The push button used in dialog button rows.
+//
+// Unlike DialogBox, a Button has no state of its own, so it implements
+// RenderOnce instead of Render: it is created fresh each frame from the
+// parent's render() and consumed when drawn.
+
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::{Badge, Kbd};
+use crate::layout::rem;
+use crate::{rendering, restyle, theme, touch};
+
+// Visual weight of a button. macOS dialogs have exactly one blue
+// (primary) button; everything else takes the theme's well color
+// (secondary), white in the light theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ButtonStyle {
+    Primary,
+    Secondary,
+}
+
+type ClickHandler = Box<dyn Fn(&MouseUpEvent, &mut Window, &mut App) + 'static>;
+type HoverHandler = Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>;
+type PressHandler = Box<dyn Fn(&MouseDownEvent, &mut Window, &mut App) + 'static>;
+
+#[derive(IntoElement)]
+pub struct Button {
+    // The label unless set, so two buttons in a row need different ones
+    id: Option<ElementId>,
+    label: SharedString,
+    style: ButtonStyle,
+    badge: Option<Badge>,
+    shortcut: Option<SharedString>,
+    on_click: Option<ClickHandler>,
+    on_hover: Option<HoverHandler>,
+    on_press: Option<PressHandler>,
+    on_release: Option<ClickHandler>,
+}
+
+impl Button {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            id: None,
+            label: label.into(),
+            style: ButtonStyle::Secondary,
+            badge: None,
+            shortcut: None,
+            on_click: None,
+            on_hover: None,
+            on_press: None,
+            on_release: None,
+        }
+    }
+
+    // Tells apart buttons whose labels may be the same
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn style(mut self, style: ButtonStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    // A badge after the label, such as a count of pending items
+    pub fn badge(mut self, badge: Badge) -> Self {
+        self.badge = Some(badge);
+        self
+    }
+
+    // A key cap after the label naming the key that presses the button
+    pub fn shortcut(mut self, key: impl Into<SharedString>) -> Self {
+        self.shortcut = Some(key.into());
+        self
+    }
+
+    // The handler runs when the left mouse button is released over the button
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&MouseUpEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+
+    // For press-and-hold: the handler runs when the left mouse button goes
+    // down on the button...
+    pub fn on_press(
+        mut self,
+        handler: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_press = Some(Box::new(handler));
+        self
+    }
+
+    // ...and this one when it's released, over the button or not
+    pub fn on_release(
+        mut self,
+        handler: impl Fn(&MouseUpEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_release = Some(Box::new(handler));
+        self
+    }
+
+    // The handler runs with true when the pointer moves onto the button,
+    // and false when it leaves
+    pub fn on_hover(mut self, handler: impl Fn(&bool, &mut Window, &mut App) + 'static) -> Self {
+        self.on_hover = Some(Box::new(handler));
+        self
+    }
+}
+
+impl RenderOnce for Button {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let palette = theme::window_palette(window, cx);
+        // (background, hover background, text color)
+        let (bg, hover_bg, text_color) = match self.style {
+            ButtonStyle::Primary => (rgb(0x007AFF), rgb(0x0068DB), rgb(0xFFFFFF)),
+            ButtonStyle::Secondary => (palette.well, palette.hover(), palette.text),
+        };
+
+        let name = match self.style {
+            ButtonStyle::Primary => "Button::Primary",
+            ButtonStyle::Secondary => "Button::Secondary",
+        };
+
+        // Hover tracking needs an id
+        let id = self
+            .id
+            .unwrap_or_else(|| ElementId::Name(self.label.clone()));
+        let button = div()
+            .id(id)
+            .flex()
+            .items_center() // Center text vertically
+            .justify_center() // Center text horizontally
+            .px_6() // 24px horizontal padding
+            .h(touch::target(px(32.0), cx)) // 32px height, more in touch mode
+            .min_w(px(90.0)) // Minimum width 90px
+            .bg(bg)
+            .text_color(text_color)
+            .text_size(rem(1.0)) // The body text size, 13px by default
+            .font_weight(FontWeight::NORMAL)
+            .when(!rendering::lite(cx), |this| this.rounded(px(6.0))) // 6px rounded corners
+            // Secondary buttons get an outline to stand out from the
+            // dialog background
+            .when(self.style == ButtonStyle::Secondary, |this| {
+                this.border_1().border_color(palette.border)
+            })
+            .cursor_pointer() // Pointer cursor on hover
+            .when(!rendering::lite(cx), |this| this.shadow_sm()) // Small shadow
+            .hover(move |style| style.bg(hover_bg))
+            .when_some(self.on_click, |this, on_click| {
+                this.on_mouse_up(MouseButton::Left, on_click)
+            })
+            .when_some(self.on_hover, |this, on_hover| this.on_hover(on_hover))
+            .when_some(self.on_press, |this, on_press| {
+                this.on_mouse_down(MouseButton::Left, on_press)
+            })
+            .when_some(self.on_release, |this, on_release| {
+                let on_release = Rc::new(on_release);
+                let on_release_out = on_release.clone();
+                this.on_mouse_up(MouseButton::Left, move |event, window, cx| {
+                    on_release(event, window, cx)
+                })
+                .on_mouse_up_out(MouseButton::Left, move |event, window, cx| {
+                    on_release_out(event, window, cx)
+                })
+            })
+            .child(self.label)
+            .when_some(self.badge, |this, badge| this.gap_2().child(badge))
+            .when_some(self.shortcut, |this, key| this.gap_2().child(Kbd::new(key)));
+        restyle::apply(name, button, cx)
+    }
+}
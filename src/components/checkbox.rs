@@ -0,0 +1,57 @@
+// ======================================================================
+// CHECKBOX COMPONENT
+// ======================================================================
+// A macOS-style checkbox: a small rounded square, blue with a check mark
+// when checked. The third, "mixed" state (a dash) is for a checkbox that
+// stands for a group whose members are only partly checked.
+//
+// Like Button, it holds no state: the parent decides what it shows and
+// what a click does.
+
+use gpui::*;
+
+use crate::restyle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    Indeterminate,
+}
+
+#[derive(IntoElement)]
+pub struct Checkbox {
+    state: CheckState,
+}
+
+impl Checkbox {
+    pub fn new(state: CheckState) -> Self {
+        Self { state }
+    }
+}
+
+impl RenderOnce for Checkbox {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let (bg, border, mark) = match self.state {
+            CheckState::Unchecked => (rgb(0xFFFFFF), rgb(0xB8B8B8), ""),
+            CheckState::Checked => (rgb(0x007AFF), rgb(0x0068DB), "✓"),
+            CheckState::Indeterminate => (rgb(0x007AFF), rgb(0x0068DB), "–"),
+        };
+
+        let checkbox = div()
+            .flex()
+            .flex_none()
+            .items_center()
+            .justify_center()
+            .size(px(14.0))
+            .rounded(px(3.0))
+            .bg(bg)
+            .border_1()
+            .border_color(border)
+            .text_size(px(10.0))
+            .font_weight(FontWeight::BOLD)
+            .text_color(rgb(0xFFFFFF))
+            .child(mark);
+        restyle::apply("Checkbox", checkbox, cx)
+    }
+}
@@ -0,0 +1,298 @@
+// ======================================================================
+// COLOR SWATCHES COMPONENT
+// ======================================================================
+// A compact grid of color chips for quick color choices in forms: the
+// theme colors, then the colors picked recently, then "Other…", which
+// opens the full color picker for anything else.
+//
+// Like TextInput it's an entity with state (the chosen color) and its
+// own focus: the arrow keys move between chips and Space or Enter picks
+// one. The value is a "#RRGGBB" string.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use serde_json::json;
+
+use crate::presets::ColorPicker;
+use crate::storage;
+
+const COLUMNS: usize = 10;
+const MAX_RECENT: usize = COLUMNS - 1; // One row, with "Other…" at the end
+const STORAGE_FILE: &str = "colors.json";
+
+// macOS system colors, then a gray ramp
+const THEME_COLORS: [u32; 20] = [
+    0xFF3B30, 0xFF9500, 0xFFCC00, 0x34C759, 0x00C7BE, 0x30B0C7, 0x32ADE6, 0x007AFF, 0x5856D6,
+    0xAF52DE, 0xFF2D55, 0xA2845E, 0xFFFFFF, 0xE5E5EA, 0xC7C7CC, 0xAEAEB2, 0x8E8E93, 0x636366,
+    0x3A3A3C, 0x000000,
+];
+
+// Parse "#RRGGBB" (or "RRGGBB", or the "#RGB" shorthand)
+pub fn parse_hex(text: &str) -> Option<Rgba> {
+    let hex = text.trim().trim_start_matches('#');
+    // from_str_radix would take a sign too
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    u32::from_str_radix(&hex, 16).ok().map(rgb)
+}
+
+pub fn to_hex(color: Rgba) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Chip {
+    Color(Rgba),
+    Other,
+}
+
+pub struct ColorSwatches {
+    focus_handle: FocusHandle,
+    color: Rgba,
+    recent: Vec<Rgba>,
+    // Index of the chip the keyboard is on
+    cursor: usize,
+}
+
+impl ColorSwatches {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let recent = storage::load(STORAGE_FILE)
+            .and_then(|stored| stored["recent"].as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|color| parse_hex(color.as_str()?))
+            .collect();
+
+        Self {
+            focus_handle: cx.focus_handle().tab_stop(true),
+            color: rgb(THEME_COLORS[7]),
+            recent,
+            cursor: 7,
+        }
+    }
+
+    pub fn color(&self) -> Rgba {
+        self.color
+    }
+
+    pub fn hex(&self) -> String {
+        to_hex(self.color)
+    }
+
+    pub fn set_color(&mut self, color: Rgba, cx: &mut Context<Self>) {
+        self.color = color;
+        if let Some(index) = self
+            .chips()
+            .iter()
+            .position(|chip| *chip == Chip::Color(color))
+        {
+            self.cursor = index;
+        }
+        cx.notify();
+    }
+
+    fn chips(&self) -> Vec<Chip> {
+        THEME_COLORS
+            .into_iter()
+            .map(|color| Chip::Color(rgb(color)))
+            .chain(self.recent.iter().copied().map(Chip::Color))
+            .chain([Chip::Other])
+            .collect()
+    }
+
+    fn pick(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.cursor = index;
+        match self.chips().get(index) {
+            Some(Chip::Color(color)) => {
+                self.set_color(*color, cx);
+                self.remember(*color);
+            }
+            Some(Chip::Other) => self.open_picker(cx),
+            None => {}
+        }
+    }
+
+    // The full color picker, starting from the current color. It opens as
+    // a dialog of its own on top of this one.
+    fn open_picker(&mut self, cx: &mut Context<Self>) {
+        let this = cx.entity().downgrade();
+        let shown = ColorPicker::new()
+            .color(self.color)
+            .show(cx, move |result, cx| {
+                // Cancelled, the swatches keep the color they had
+                if !result.is_confirmed() {
+                    return;
+                }
+                let Some(color) = result.value("color").and_then(parse_hex) else {
+                    return;
+                };
+                this.update(cx, |this, cx| {
+                    this.remember(color);
+                    this.set_color(color, cx);
+                })
+                .ok();
+            });
+        if let Err(err) = shown {
+            log::error!("couldn't open the color picker: {err}");
+        }
+    }
+
+    // Put a color at the front of the recent row, unless it's a theme color
+    fn remember(&mut self, color: Rgba) {
+        if THEME_COLORS.into_iter().any(|theme| rgb(theme) == color) {
+            return;
+        }
+        self.recent.retain(|other| *other != color);
+        self.recent.insert(0, color);
+        self.recent.truncate(MAX_RECENT);
+
+        let recent: Vec<_> = self.recent.iter().map(|color| to_hex(*color)).collect();
+        storage::save(STORAGE_FILE, &json!({ "recent": recent }));
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let last = self.chips().len() - 1;
+        let cursor = match event.keystroke.key.as_str() {
+            "left" => self.cursor.saturating_sub(1),
+            "right" => (self.cursor + 1).min(last),
+            "up" => self.cursor.saturating_sub(COLUMNS),
+            "down" => (self.cursor + COLUMNS).min(last),
+            "space" | "enter" => {
+                self.pick(self.cursor, cx);
+                cx.stop_propagation();
+                return;
+            }
+            _ => return,
+        };
+        self.cursor = cursor;
+        cx.stop_propagation();
+        cx.notify();
+    }
+}
+
+impl Focusable for ColorSwatches {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ColorSwatches {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.focus_handle.is_focused(window);
+        let chips = self.chips();
+
+        let rows = chips.chunks(COLUMNS).enumerate().map(|(row, chips)| {
+            div()
+                .flex()
+                .gap_1()
+                .children(chips.iter().enumerate().map(|(column, chip)| {
+                    let index = row * COLUMNS + column;
+                    let under_cursor = focused && index == self.cursor;
+                    let chosen = *chip == Chip::Color(self.color);
+
+                    div()
+                        .id(index)
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .size(px(20.0))
+                        .rounded(px(4.0))
+                        .border_1()
+                        .border_color(rgb(0xB8B8B8))
+                        .cursor_pointer()
+                        .map(|chip_div| match chip {
+                            Chip::Color(color) => chip_div.bg(*color),
+                            Chip::Other => chip_div
+                                .bg(rgb(0xFFFFFF))
+                                .text_size(px(11.0))
+                                .text_color(rgb(0x3C3C3C))
+                                .child("…"),
+                        })
+                        // A blue ring shows the chosen color, a dashed one
+                        // the keyboard cursor
+                        .when(chosen, |chip| chip.border_2().border_color(rgb(0x007AFF)))
+                        .when(under_cursor && !chosen, |chip| {
+                            chip.border_2().border_dashed().border_color(rgb(0x007AFF))
+                        })
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            window.focus(&this.focus_handle);
+                            this.pick(index, cx);
+                        }))
+                }))
+        });
+
+        div()
+            .flex()
+            .gap_3()
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .child(div().flex().flex_col().gap_1().children(rows))
+            .child(
+                // The current value, large, with its hex code
+                div()
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .gap_1()
+                    .child(
+                        div()
+                            .size(px(36.0))
+                            .rounded(px(6.0))
+                            .border_1()
+                            .border_color(rgb(0xB8B8B8))
+                            .bg(self.color),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(11.0))
+                            .text_color(rgb(0x4D4D4D))
+                            .child(self.hex()),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::rgb;
+
+    use super::{parse_hex, to_hex};
+
+    #[test]
+    fn parses_with_or_without_the_hash() {
+        assert_eq!(parse_hex("#FF8000"), Some(rgb(0xFF8000)));
+        assert_eq!(parse_hex(" ff8000 "), Some(rgb(0xFF8000)));
+    }
+
+    #[test]
+    fn expands_the_shorthand() {
+        assert_eq!(parse_hex("#F80"), Some(rgb(0xFF8800)));
+    }
+
+    #[test]
+    fn rejects_what_isnt_six_or_three_hex_digits() {
+        assert_eq!(parse_hex("#FF800"), None);
+        assert_eq!(parse_hex("#GG8000"), None);
+        assert_eq!(parse_hex("+FF800"), None);
+        assert_eq!(parse_hex("+F8"), None);
+        assert_eq!(parse_hex(""), None);
+    }
+
+    #[test]
+    fn round_trips_through_to_hex() {
+        assert_eq!(to_hex(rgb(0x1A2B3C)), "#1A2B3C");
+        assert_eq!(parse_hex(&to_hex(rgb(0x1A2B3C))), Some(rgb(0x1A2B3C)));
+    }
+}
@@ -0,0 +1,95 @@
+// ======================================================================
+// EMPTY STATE COMPONENT
+// ======================================================================
+// What a list shows when there's nothing in it: an icon, a short title,
+// a hint about what to do, and optionally a button that does it.
+//
+//     EmptyState::new("No matches")
+//         .icon("🔍")
+//         .hint("Try a shorter search")
+//         .action("Clear Search", |_, window, cx| { ... })
+//
+// It centers itself in whatever space it's given.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::Button;
+use crate::restyle;
+
+#[derive(IntoElement)]
+pub struct EmptyState {
+    icon: Option<SharedString>,
+    title: SharedString,
+    hint: Option<SharedString>,
+    action: Option<Button>,
+}
+
+impl EmptyState {
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            icon: None,
+            title: title.into(),
+            hint: None,
+            action: None,
+        }
+    }
+
+    // An emoji or symbol shown large above the title
+    pub fn icon(mut self, icon: impl Into<SharedString>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    pub fn hint(mut self, hint: impl Into<SharedString>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&MouseUpEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.action = Some(Button::new(label).on_click(handler));
+        self
+    }
+}
+
+impl RenderOnce for EmptyState {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let empty_state = div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .items_center()
+            .justify_center()
+            .gap_1()
+            .w_full()
+            .py_6()
+            .px_4()
+            .text_center()
+            .when_some(self.icon, |this, icon| {
+                this.child(div().mb_1().text_size(px(28.0)).child(icon))
+            })
+            .child(
+                div()
+                    .text_size(px(13.0))
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0x4D4D4D))
+                    .child(self.title),
+            )
+            .when_some(self.hint, |this, hint| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(rgb(0x8E8E8E))
+                        .child(hint),
+                )
+            })
+            .when_some(self.action, |this, action| {
+                this.child(div().mt_2().child(action))
+            });
+        restyle::apply("EmptyState", empty_state, cx)
+    }
+}
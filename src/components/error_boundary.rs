@@ -0,0 +1,154 @@
+// ======================================================================
+// ERROR BOUNDARY COMPONENT
+// ======================================================================
+// Keeps one broken piece from taking the whole window down. The boundary
+// builds its child with a closure; if the closure returns an error or
+// panics, the child is replaced by a small card saying what went wrong,
+// with "Try Again" and "Report" buttons, and the error goes to the log.
+//
+//     let preview = cx.new(|_| {
+//         ErrorBoundary::new("Preview", move |_window, _cx| {
+//             let text = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+//             Ok(div().child(text).into_any_element())
+//         })
+//     });
+//     cx.subscribe(&preview, |_, _, Report(details), _| { ... }).detach();
+//
+// "Report" copies the details to the clipboard and emits Report, so the
+// owner can also send them somewhere.
+//
+// Only the closure itself is guarded. Elements it returns are laid out
+// and painted later, outside the boundary, and a panic inside another
+// entity's update leaves that entity unusable, so entities should hand
+// their problems back as errors rather than panic.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use gpui::*;
+
+use crate::components::{Button, ButtonStyle};
+use crate::crash;
+
+// Emitted when "Report" is clicked, with the name and error
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report(pub SharedString);
+
+type RenderChild = Box<dyn Fn(&mut Window, &mut App) -> Result<AnyElement, String>>;
+
+pub struct ErrorBoundary {
+    // What the child is, for the card and the log ("Preview")
+    name: SharedString,
+    render: RenderChild,
+    error: Option<SharedString>,
+}
+
+impl EventEmitter<Report> for ErrorBoundary {}
+
+impl ErrorBoundary {
+    pub fn new(
+        name: impl Into<SharedString>,
+        render: impl Fn(&mut Window, &mut App) -> Result<AnyElement, String> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            render: Box::new(render),
+            error: None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&SharedString> {
+        self.error.as_ref()
+    }
+
+    // Build the child again on the next frame
+    pub fn retry(&mut self, cx: &mut Context<Self>) {
+        self.error = None;
+        cx.notify();
+    }
+
+    fn fail(&mut self, error: String) {
+        log::error!("{} failed to render: {error}", self.name);
+        self.error = Some(error.into());
+    }
+
+    fn report(&mut self, cx: &mut Context<Self>) {
+        let Some(error) = &self.error else { return };
+        let details = format!("{} failed to render: {error}", self.name);
+        cx.write_to_clipboard(ClipboardItem::new_string(details.clone()));
+        cx.emit(Report(details.into()));
+    }
+
+    fn render_error(&self, error: SharedString, cx: &mut Context<Self>) -> impl IntoElement {
+        let entity = cx.entity();
+        let report = entity.clone();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .w_full()
+            .p_3()
+            .rounded(px(6.0))
+            .border_1()
+            .border_color(rgb(0xFFB3AE))
+            .bg(rgb(0xFFF2F1))
+            .text_size(px(12.0))
+            .child(
+                div()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0xD70015))
+                    .child(format!("⚠ {} couldn't be shown", self.name)),
+            )
+            .child(
+                div()
+                    .font_family("Menlo")
+                    .text_size(px(11.0))
+                    .text_color(rgb(0x4D4D4D))
+                    .child(error),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .mt_1()
+                    .child(Button::new("Try Again").on_click(move |_, _, cx| {
+                        entity.update(cx, |this, cx| this.retry(cx));
+                    }))
+                    .child(Button::new("Report").style(ButtonStyle::Primary).on_click(
+                        move |_, _, cx| {
+                            report.update(cx, |this, cx| this.report(cx));
+                        },
+                    )),
+            )
+    }
+}
+
+// The message a panic was started with, when it's text
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+impl Render for ErrorBoundary {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.error.is_none() {
+            let render = &self.render;
+            // Caught here, so not a crash to report
+            let rendered =
+                crash::caught(|| panic::catch_unwind(AssertUnwindSafe(|| render(window, cx))));
+            match rendered {
+                Ok(Ok(child)) => return child,
+                Ok(Err(error)) => self.fail(error),
+                Err(payload) => self.fail(format!("panicked: {}", panic_message(&*payload))),
+            }
+        }
+
+        let error = self.error.clone().unwrap_or_default();
+        self.render_error(error, cx).into_any_element()
+    }
+}
@@ -0,0 +1,481 @@
+// ======================================================================
+// IMAGE LOADING
+// ======================================================================
+// Pictures from a file, from bytes already in memory, or from a URL, all
+// loaded the same way: read and decoded in the background, kept in an
+// in-memory cache so the next dialog that shows the same picture gets it
+// straight away, and drawn with a skeleton while loading and a fallback
+// if it can't be loaded.
+//
+//     ImageView::new("assets/logo.png").size(px(64.0), px(64.0))
+//     ImageView::new(ImageSource::url("https://example.com/a.png"))
+//         .fallback(|error| div().child(error.clone()).into_any_element())
+//
+// Components that draw pictures their own way ask the source for its
+// state directly, which starts the load the first time:
+//
+//     match source.load(cx) {
+//         ImageState::Loading => ...,
+//         ImageState::Ready(image) => img(image),
+//         ImageState::Failed(error) => ...,
+//     }
+//
+// URLs go through the app's HTTP client (cx.set_http_client()). gpui's
+// default client can't fetch anything, so an app without one of its own
+// can have them fetched with the system's curl instead, which ships with
+// macOS, Windows 10 and most Linux installs; only http and https URLs:
+//
+//     ImageSource::use_system_curl(cx);
+//
+// The cache lives as long as the app and keeps the pictures used most
+// recently; forget() drops one from it (after the file changed, say). A
+// picture that failed to load is tried again when asked for a while later.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::AsyncReadExt;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use image::codecs::png::PngDecoder;
+use image::AnimationDecoder;
+
+use crate::components::Skeleton;
+
+// Pictures the cache keeps; the ones used longest ago make room
+const MAX_CACHED: usize = 64;
+// How long a failed load stands before the next load() tries again
+const RETRY_FAILED: Duration = Duration::from_secs(30);
+// For curl: the longest a download may take, and its largest size
+const FETCH_TIMEOUT: &str = "30";
+const MAX_DOWNLOAD: &str = "50000000";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageSource {
+    Path(PathBuf),
+    Bytes(Arc<[u8]>),
+    Url(SharedString),
+}
+
+// Set by ImageSource::use_system_curl()
+struct SystemCurl;
+
+impl Global for SystemCurl {}
+
+impl ImageSource {
+    pub fn url(url: impl Into<SharedString>) -> Self {
+        ImageSource::Url(url.into())
+    }
+
+    // Fetch URLs with curl from now on, rather than the HTTP client
+    pub fn use_system_curl(cx: &mut App) {
+        cx.set_global(SystemCurl);
+    }
+
+    // Where the picture is in the cache. Bytes are keyed by their hash, so
+    // the same picture passed twice is decoded once.
+    fn key(&self) -> CacheKey {
+        match self {
+            ImageSource::Path(path) => CacheKey::Path(path.clone()),
+            ImageSource::Bytes(bytes) => {
+                let mut hasher = DefaultHasher::new();
+                bytes.hash(&mut hasher);
+                CacheKey::Bytes(hasher.finish())
+            }
+            ImageSource::Url(url) => CacheKey::Url(url.clone()),
+        }
+    }
+
+    // The picture's state, starting to load it if nobody has asked yet
+    pub fn load(&self, cx: &mut App) -> ImageState {
+        let key = self.key();
+        let cache = cx.default_global::<ImageCache>();
+        if let Some(state) = cache.get(&key) {
+            return state;
+        }
+        cache.insert(key.clone(), ImageState::Loading);
+
+        let read = self.read(cx);
+        let svg_renderer = cx.svg_renderer();
+        let decoded = cx.background_spawn(async move {
+            let bytes = read.await?;
+            let format = sniff_format(&bytes).ok_or("not a picture in a known format")?;
+            match decode_apng(format, &bytes) {
+                Ok(Some(image)) => Ok(image),
+                Ok(None) => gpui::Image::from_bytes(format, bytes).to_image_data(svg_renderer),
+                Err(err) => Err(err),
+            }
+            .map_err(|err| err.to_string())
+        });
+        cx.spawn(async move |cx| {
+            let state = match decoded.await {
+                Ok(image) => ImageState::Ready(image),
+                Err(error) => {
+                    log::warn!("failed to load image: {error}");
+                    ImageState::Failed(error.into())
+                }
+            };
+            cx.update(|cx| {
+                cx.default_global::<ImageCache>().insert(key, state);
+                cx.refresh_windows();
+            })
+            .ok();
+        })
+        .detach();
+
+        ImageState::Loading
+    }
+
+    // Drop the picture from the cache; the next load() reads it again
+    pub fn forget(&self, cx: &mut App) {
+        let key = self.key();
+        cx.default_global::<ImageCache>().entries.remove(&key);
+    }
+
+    // The undecoded bytes, fetched off the main thread
+    fn read(&self, cx: &App) -> Task<Result<Vec<u8>, String>> {
+        match self.clone() {
+            ImageSource::Path(path) => cx.background_spawn(async move {
+                std::fs::read(&path).map_err(|err| format!("{}: {err}", path.display()))
+            }),
+            ImageSource::Bytes(bytes) => Task::ready(Ok(bytes.to_vec())),
+            ImageSource::Url(url) => {
+                if cx.has_global::<SystemCurl>() {
+                    return cx.background_spawn(async move { curl(&url) });
+                }
+                let response = cx.http_client().get(&url, ().into(), true);
+                cx.background_spawn(async move {
+                    let mut response = response.await.map_err(|err| err.to_string())?;
+                    let mut body = Vec::new();
+                    response
+                        .body_mut()
+                        .read_to_end(&mut body)
+                        .await
+                        .map_err(|err| err.to_string())?;
+                    if !response.status().is_success() {
+                        return Err(format!("{url}: {}", response.status()));
+                    }
+                    Ok(body)
+                })
+            }
+        }
+    }
+}
+
+// Fetch a URL with the system's curl
+fn curl(url: &str) -> Result<Vec<u8>, String> {
+    // Anything else could be one of curl's options, or a local file
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(format!("{url}: only http and https URLs can be loaded"));
+    }
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--max-time", FETCH_TIMEOUT, "--max-filesize", MAX_DOWNLOAD])
+        .arg(url)
+        .output()
+        .map_err(|err| format!("{url}: couldn't run curl: {err}"))?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{url}: {}", error.trim()));
+    }
+    Ok(output.stdout)
+}
+
+// Text containing "://" is a URL, anything else a file path
+impl From<&str> for ImageSource {
+    fn from(source: &str) -> Self {
+        if source.contains("://") {
+            ImageSource::Url(source.to_string().into())
+        } else {
+            ImageSource::Path(source.into())
+        }
+    }
+}
+
+impl From<&Path> for ImageSource {
+    fn from(path: &Path) -> Self {
+        ImageSource::Path(path.to_path_buf())
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(path: PathBuf) -> Self {
+        ImageSource::Path(path)
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        ImageSource::Bytes(bytes.into())
+    }
+}
+
+impl From<&'static [u8]> for ImageSource {
+    fn from(bytes: &'static [u8]) -> Self {
+        ImageSource::Bytes(bytes.into())
+    }
+}
+
+#[derive(Clone)]
+pub enum ImageState {
+    Loading,
+    Ready(Arc<RenderImage>),
+    Failed(SharedString),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Path(PathBuf),
+    Bytes(u64),
+    Url(SharedString),
+}
+
+struct Cached {
+    state: ImageState,
+    // When it was stored, and the cache's clock when it was last asked for
+    stored: Instant,
+    used: u64,
+}
+
+#[derive(Default)]
+struct ImageCache {
+    entries: HashMap<CacheKey, Cached>,
+    // Counts lookups, to tell which picture was used longest ago
+    clock: u64,
+}
+
+impl Global for ImageCache {}
+
+impl ImageCache {
+    // The picture's state, unless it isn't here or failed a while ago
+    fn get(&mut self, key: &CacheKey) -> Option<ImageState> {
+        self.clock += 1;
+        let cached = self.entries.get_mut(key)?;
+        if matches!(cached.state, ImageState::Failed(_)) && cached.stored.elapsed() > RETRY_FAILED {
+            return None;
+        }
+        cached.used = self.clock;
+        Some(cached.state.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, state: ImageState) {
+        self.entries.insert(
+            key,
+            Cached {
+                state,
+                stored: Instant::now(),
+                used: self.clock,
+            },
+        );
+        while self.entries.len() > MAX_CACHED {
+            // Pictures still loading stay, to be stored when they're done
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(_, cached)| !matches!(cached.state, ImageState::Loading))
+                .min_by_key(|(_, cached)| cached.used)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+// The format from the first bytes of the file, since bytes and URLs
+// don't come with a trustworthy extension
+fn sniff_format(bytes: &[u8]) -> Option<ImageFormat> {
+    let format = match bytes {
+        [0x89, b'P', b'N', b'G', ..] => ImageFormat::Png,
+        [0xFF, 0xD8, 0xFF, ..] => ImageFormat::Jpeg,
+        [b'G', b'I', b'F', b'8', ..] => ImageFormat::Gif,
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => ImageFormat::Webp,
+        [b'B', b'M', ..] => ImageFormat::Bmp,
+        [b'I', b'I', 42, 0, ..] | [b'M', b'M', 0, 42, ..] => ImageFormat::Tiff,
+        _ if String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]).contains("<svg") => {
+            ImageFormat::Svg
+        }
+        _ => return None,
+    };
+    Some(format)
+}
+
+// All the frames of an animated PNG, which gpui would read only the
+// first of. None for every other picture, which gpui decodes itself.
+fn decode_apng(format: ImageFormat, bytes: &[u8]) -> Result<Option<Arc<RenderImage>>> {
+    if format != ImageFormat::Png {
+        return Ok(None);
+    }
+    let decoder = PngDecoder::new(Cursor::new(bytes))?;
+    if !decoder.is_apng()? {
+        return Ok(None);
+    }
+    let mut frames = decoder.apng()?.into_frames().collect_frames()?;
+    // gpui draws BGRA
+    for frame in &mut frames {
+        for pixel in frame.buffer_mut().chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+    Ok(Some(Arc::new(RenderImage::new(frames))))
+}
+
+// ======================================================================
+// IMAGE VIEW
+// ======================================================================
+
+type Fallback = Box<dyn Fn(&SharedString) -> AnyElement>;
+
+#[derive(IntoElement)]
+pub struct ImageView {
+    source: ImageSource,
+    width: Pixels,
+    height: Pixels,
+    object_fit: ObjectFit,
+    corner_radius: Pixels,
+    fallback: Option<Fallback>,
+}
+
+impl ImageView {
+    pub fn new(source: impl Into<ImageSource>) -> Self {
+        Self {
+            source: source.into(),
+            width: px(64.0),
+            height: px(64.0),
+            object_fit: ObjectFit::Contain,
+            corner_radius: px(0.0),
+            fallback: None,
+        }
+    }
+
+    pub fn size(mut self, width: Pixels, height: Pixels) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn object_fit(mut self, object_fit: ObjectFit) -> Self {
+        self.object_fit = object_fit;
+        self
+    }
+
+    pub fn rounded(mut self, corner_radius: Pixels) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
+
+    // Shown instead when the picture can't be loaded, given the error.
+    // Without one it's a gray box with a warning sign.
+    pub fn fallback(mut self, fallback: impl Fn(&SharedString) -> AnyElement + 'static) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+}
+
+impl RenderOnce for ImageView {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        match self.source.load(cx) {
+            ImageState::Loading => Skeleton::rect(self.width, self.height).into_any_element(),
+            ImageState::Ready(image) => img(image)
+                .flex_none()
+                .w(self.width)
+                .h(self.height)
+                .rounded(self.corner_radius)
+                .object_fit(self.object_fit)
+                .into_any_element(),
+            ImageState::Failed(error) => match &self.fallback {
+                Some(fallback) => fallback(&error),
+                None => div()
+                    .flex()
+                    .flex_none()
+                    .items_center()
+                    .justify_center()
+                    .w(self.width)
+                    .h(self.height)
+                    .rounded(self.corner_radius)
+                    .bg(rgb(0xE5E5EA))
+                    .text_color(rgb(0x8E8E8E))
+                    .when(self.height >= px(24.0), |this| this.child("⚠"))
+                    .into_any_element(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use gpui::ImageFormat;
+
+    use super::{sniff_format, CacheKey, ImageCache, ImageState, MAX_CACHED};
+
+    fn key(n: usize) -> CacheKey {
+        CacheKey::Path(PathBuf::from(n.to_string()))
+    }
+
+    fn failed() -> ImageState {
+        ImageState::Failed("no".into())
+    }
+
+    #[test]
+    fn formats_come_from_the_first_bytes() {
+        let cases: [(&[u8], ImageFormat); 8] = [
+            (b"\x89PNG\r\n\x1a\n", ImageFormat::Png),
+            (b"\xFF\xD8\xFF\xE0", ImageFormat::Jpeg),
+            (b"GIF89a", ImageFormat::Gif),
+            (b"RIFF\0\0\0\0WEBPVP8 ", ImageFormat::Webp),
+            (b"BM\0\0", ImageFormat::Bmp),
+            (b"II*\0", ImageFormat::Tiff),
+            (b"MM\0*", ImageFormat::Tiff),
+            (b"<?xml version=\"1.0\"?>\n<svg xmlns=", ImageFormat::Svg),
+        ];
+        for (bytes, format) in cases {
+            assert_eq!(sniff_format(bytes), Some(format), "{bytes:?}");
+        }
+        assert_eq!(sniff_format(b""), None);
+        assert_eq!(sniff_format(b"hello"), None);
+        // A RIFF that isn't a WebP
+        assert_eq!(sniff_format(b"RIFF\0\0\0\0WAVE"), None);
+    }
+
+    #[test]
+    fn the_picture_used_longest_ago_makes_room() {
+        let mut cache = ImageCache::default();
+        for n in 0..MAX_CACHED {
+            cache.insert(key(n), failed());
+            cache.get(&key(n));
+        }
+        // 0 is now the most recently used, so 1 goes
+        cache.get(&key(0));
+        cache.insert(key(MAX_CACHED), failed());
+        assert_eq!(cache.entries.len(), MAX_CACHED);
+        assert!(cache.entries.contains_key(&key(0)));
+        assert!(!cache.entries.contains_key(&key(1)));
+        assert!(cache.entries.contains_key(&key(MAX_CACHED)));
+    }
+
+    #[test]
+    fn pictures_still_loading_stay() {
+        let mut cache = ImageCache::default();
+        cache.insert(key(0), failed());
+        cache.get(&key(0));
+        for n in 1..=MAX_CACHED {
+            cache.insert(key(n), ImageState::Loading);
+        }
+        // Only the one that isn't loading can go
+        assert_eq!(cache.entries.len(), MAX_CACHED);
+        assert!(!cache.entries.contains_key(&key(0)));
+
+        // And with nothing else, the cache grows rather than drop one
+        cache.insert(key(MAX_CACHED + 1), ImageState::Loading);
+        assert_eq!(cache.entries.len(), MAX_CACHED + 1);
+    }
+}
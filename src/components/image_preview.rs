@@ -0,0 +1,136 @@
+// ======================================================================
+// IMAGE PREVIEW COMPONENT
+// ======================================================================
+// A picture shown as large as its box allows, that can be zoomed to look
+// at the details:
+//
+//   - pinch, or Ctrl+scroll (touch screens and precision touchpads send
+//     a pinch as that)
+//   - double click or double tap to switch between fitting the box and
+//     twice that
+//   - drag (finger, pen or mouse) to move around a zoomed picture
+//
+//     let preview = cx.new(|_| ImagePreview::new("photo.jpg"));
+//     div().h(px(240.0)).child(preview.clone())
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::image::{ImageSource, ImageState};
+use crate::gesture::{Click, ClickCounter};
+
+const MAX_ZOOM: f32 = 8.0;
+// Zoom factor per pixel scrolled with Ctrl held
+const ZOOM_PER_PIXEL: f32 = 0.01;
+
+pub struct ImagePreview {
+    source: ImageSource,
+    // 1.0 fits the box
+    zoom: f32,
+    // How far a zoomed picture has been moved from the center
+    pan: Point<Pixels>,
+    // Where the current drag started, and the pan then
+    drag: Option<(Point<Pixels>, Point<Pixels>)>,
+    clicks: ClickCounter,
+}
+
+impl ImagePreview {
+    pub fn new(source: impl Into<ImageSource>) -> Self {
+        Self {
+            source: source.into(),
+            zoom: 1.0,
+            pan: Point::default(),
+            drag: None,
+            clicks: ClickCounter::default(),
+        }
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32, cx: &mut Context<Self>) {
+        self.zoom = zoom.clamp(1.0, MAX_ZOOM);
+        // Nothing to move around when the whole picture shows
+        if self.zoom == 1.0 {
+            self.pan = Point::default();
+        }
+        cx.notify();
+    }
+
+    fn on_scroll(&mut self, event: &ScrollWheelEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if !event.modifiers.control && !event.modifiers.platform {
+            return;
+        }
+        let delta = match event.delta {
+            ScrollDelta::Pixels(delta) => f32::from(delta.y),
+            ScrollDelta::Lines(delta) => delta.y * 20.0,
+        };
+        self.set_zoom(self.zoom * (1.0 + delta * ZOOM_PER_PIXEL), cx);
+        cx.stop_propagation();
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.clicks.down(event.position) == Click::Double {
+            let zoom = if self.zoom > 1.0 { 1.0 } else { 2.0 };
+            self.set_zoom(zoom, cx);
+            return;
+        }
+        self.drag = Some((event.position, self.pan));
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((start, pan)) = self.drag else {
+            return;
+        };
+        if !event.dragging() {
+            self.drag = None;
+            return;
+        }
+        if self.zoom > 1.0 {
+            self.pan = pan + (event.position - start);
+            cx.notify();
+        }
+    }
+}
+
+impl Render for ImagePreview {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // The picture is `zoom` times the box, centered and then moved by
+        // the pan
+        let offset = (1.0 - self.zoom) / 2.0;
+        div()
+            .id("image-preview")
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .bg(rgb(0xF5F5F5))
+            .rounded(px(6.0))
+            .when(self.zoom > 1.0, |this| this.cursor_grab())
+            .on_scroll_wheel(cx.listener(Self::on_scroll))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .child(match self.source.load(cx) {
+                ImageState::Ready(image) => img(image)
+                    .absolute()
+                    .left(relative(offset))
+                    .top(relative(offset))
+                    .w(relative(self.zoom))
+                    .h(relative(self.zoom))
+                    .ml(self.pan.x)
+                    .mt(self.pan.y)
+                    .object_fit(ObjectFit::Contain)
+                    .into_any_element(),
+                ImageState::Loading => div().into_any_element(),
+                ImageState::Failed(error) => div()
+                    .size_full()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_size(px(12.0))
+                    .text_color(rgb(0x8E8E8E))
+                    .child(format!("⚠ {error}"))
+                    .into_any_element(),
+            })
+    }
+}
@@ -0,0 +1,104 @@
+// ======================================================================
+// KBD COMPONENT
+// ======================================================================
+// A key cap, like <kbd> in HTML: a small outlined chip showing one key.
+// A shortcut such as ctrl-shift-s is drawn as one chip per key:
+//
+//     div().flex().gap_1().children(Kbd::keystroke(&keystroke))
+
+use gpui::*;
+
+use crate::{restyle, theme};
+
+#[derive(IntoElement)]
+pub struct Kbd {
+    label: SharedString,
+}
+
+impl Kbd {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+        }
+    }
+
+    // One chip per modifier, then one for the key
+    pub fn keystroke(keystroke: &KeybindingKeystroke) -> Vec<Self> {
+        keystroke_labels(keystroke)
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+}
+
+// How each part of a keystroke is spelled on the chips, using the
+// platform's names for the modifiers
+pub fn keystroke_labels(keystroke: &KeybindingKeystroke) -> Vec<SharedString> {
+    let mac = cfg!(target_os = "macos");
+    let modifiers = keystroke.modifiers();
+
+    let mut labels: Vec<SharedString> = [
+        (modifiers.control, if mac { "⌃" } else { "Ctrl" }),
+        (modifiers.alt, if mac { "⌥" } else { "Alt" }),
+        (modifiers.shift, if mac { "⇧" } else { "Shift" }),
+        (modifiers.platform, if mac { "⌘" } else { "Super" }),
+        (modifiers.function, "Fn"),
+    ]
+    .into_iter()
+    .filter(|(held, _)| *held)
+    .map(|(_, label)| label.into())
+    .collect();
+
+    labels.push(key_label(keystroke.key()).into());
+    labels
+}
+
+fn key_label(key: &str) -> String {
+    let named = match key {
+        "enter" => "Enter",
+        "escape" => "Esc",
+        "backspace" => "⌫",
+        "delete" => "Del",
+        "tab" => "Tab",
+        "space" => "Space",
+        "left" => "←",
+        "right" => "→",
+        "up" => "↑",
+        "down" => "↓",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" => "PgUp",
+        "pagedown" => "PgDn",
+        _ => {
+            // Letters and function keys in upper case ("a" → "A", "f5" → "F5")
+            let mut chars = key.chars();
+            return match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            };
+        }
+    };
+    named.to_string()
+}
+
+impl RenderOnce for Kbd {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let palette = theme::window_palette(window, cx);
+        let kbd = div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .min_w(px(20.0))
+            .h(px(20.0))
+            .px_1()
+            .bg(palette.well)
+            .border_1()
+            .border_b_2() // A thicker bottom edge, like a physical key
+            .border_color(palette.border)
+            .rounded(px(4.0))
+            .text_size(px(11.0))
+            .text_color(palette.secondary_text)
+            .child(self.label);
+        restyle::apply("Kbd", kbd, cx)
+    }
+}
@@ -0,0 +1,1013 @@
+// ======================================================================
+// LIST VIEW COMPONENT
+// ======================================================================
+// A scrolling list of text rows to pick from. Only the rows in view are
+// built each frame (gpui's uniform_list), so long lists stay fast.
+//
+// Single selection: click or Up/Down picks a row.
+//
+// Multiple selection: every row gets a checkbox. Click toggles a row,
+// Shift-click selects the range from the last clicked row, Space toggles
+// the row under the keyboard cursor and Ctrl/Cmd-A selects everything. A
+// header row has a select-all checkbox (mixed when only some rows are
+// selected) and bulk actions.
+//
+// Reordering: with reorderable() each row gets a drag handle. While a row
+// is dragged a line opens where it will land, and holding it near the top
+// or bottom edge scrolls the list. Alt-Up/Alt-Down move the row under the
+// keyboard cursor. order() gives the rows' original indices in their new
+// order.
+//
+// An empty list (including a lazy one whose provider has no rows) shows
+// an EmptyState, with a title and hint the owner can set.
+//
+// Rows are always identified by their original index (selected(),
+// Activated), so reordering never changes what a selection means.
+//
+// Touchpad scrolling glides and rubber-bands at the ends (see scroll.rs).
+//
+// In touch mode (see touch.rs) rows are taller, and dragging the list
+// scrolls it.
+//
+// Lazy rows: ListView::lazy() takes a RowProvider instead of the rows
+// themselves, for lists too big (or too slow) to load up front. Rows are
+// fetched a page at a time as they scroll into view, with placeholder
+// bars while a page loads and a Retry bar if fetching fails.
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::checkbox::{CheckState, Checkbox};
+use crate::components::{EmptyState, Skeleton};
+use crate::rendering;
+use crate::scroll::Scroller;
+use crate::theme::{self, Palette};
+use crate::touch::{self, DragScroll};
+
+const ROW_HEIGHT: f32 = 28.0;
+// How close to an edge a dragged row starts scrolling the list, and the
+// fastest it scrolls (pixels per frame, reached right at the edge)
+const SCROLL_ZONE: f32 = 32.0;
+const MAX_SCROLL_SPEED: f32 = 12.0;
+// Rows fetched from a RowProvider at once
+const PAGE_SIZE: usize = 50;
+
+// Where a lazy list gets its rows. fetch() is asked for one page of rows
+// at a time, and the future it returns runs on a background thread, so it
+// can read files or query a database directly:
+//
+//     impl RowProvider for LogFile {
+//         fn row_count(&self) -> usize {
+//             self.line_count
+//         }
+//
+//         fn fetch(&self, rows: Range<usize>) -> BoxFuture<'static, Result<Vec<SharedString>, String>> {
+//             let path = self.path.clone();
+//             async move { read_lines(&path, rows).map_err(|e| e.to_string()) }.boxed()
+//         }
+//     }
+//
+// A page that fails shows its error with a Retry button, which fetches it
+// again.
+pub trait RowProvider: 'static {
+    fn row_count(&self) -> usize;
+    fn fetch(&self, rows: Range<usize>) -> BoxFuture<'static, Result<Vec<SharedString>, String>>;
+}
+
+enum Rows {
+    Loaded(Vec<SharedString>),
+    Lazy {
+        provider: Rc<dyn RowProvider>,
+        // Keyed by page number (row / PAGE_SIZE)
+        pages: HashMap<usize, Page>,
+    },
+}
+
+enum Page {
+    Loading,
+    Loaded(Vec<SharedString>),
+    Failed(SharedString),
+}
+
+// Emitted when a row is double-clicked, which a dialog takes as "OK"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Activated(pub usize);
+
+// What's carried while a row is dragged by its handle. It also draws
+// itself under the pointer.
+#[derive(Clone)]
+struct DraggedRow {
+    position: usize,
+    label: SharedString,
+}
+
+impl Render for DraggedRow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let palette = theme::window_palette(window, cx);
+        div()
+            .flex()
+            .items_center()
+            .h(row_height(cx))
+            .px_2()
+            .bg(palette.well)
+            .border_1()
+            .border_color(palette.border)
+            .text_color(palette.well_text)
+            .when(!rendering::lite(cx), |this| {
+                this.rounded(px(4.0)).shadow_md()
+            })
+            .text_size(px(13.0))
+            .opacity(0.9)
+            .child(self.label.clone())
+    }
+}
+
+pub struct ListView {
+    focus_handle: FocusHandle,
+    rows: Rows,
+    row_count: usize,
+    multi_select: bool,
+    reorderable: bool,
+    // Original row indices in display order
+    order: Vec<usize>,
+    // Original indices of the selected rows
+    selected: BTreeSet<usize>,
+    // Display positions of the row under the keyboard cursor, and of
+    // where a Shift-click range starts
+    cursor: usize,
+    anchor: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    // While dragging: the gap the row would drop into (0 is above the
+    // first row, rows.len() below the last) and the edge scrolling
+    drop_target: Option<usize>,
+    scroll_speed: f32,
+    _autoscroll: Option<Task<()>>,
+    empty_title: SharedString,
+    empty_hint: Option<SharedString>,
+    drag_scroll: DragScroll,
+    scroller: Scroller,
+}
+
+impl EventEmitter<Activated> for ListView {}
+
+impl ListView {
+    pub fn new(rows: Vec<SharedString>, multi_select: bool, cx: &mut Context<Self>) -> Self {
+        Self::with_rows(rows.len(), Rows::Loaded(rows), multi_select, cx)
+    }
+
+    // Rows fetched from `provider` as they come into view
+    pub fn lazy(provider: Rc<dyn RowProvider>, multi_select: bool, cx: &mut Context<Self>) -> Self {
+        let row_count = provider.row_count();
+        let rows = Rows::Lazy {
+            provider,
+            pages: HashMap::new(),
+        };
+        Self::with_rows(row_count, rows, multi_select, cx)
+    }
+
+    fn with_rows(row_count: usize, rows: Rows, multi_select: bool, cx: &mut Context<Self>) -> Self {
+        let scroll_handle = UniformListScrollHandle::new();
+        let scroller = Scroller::new(scroll_handle.0.borrow().base_handle.clone());
+        Self {
+            focus_handle: cx.focus_handle().tab_stop(true),
+            rows,
+            row_count,
+            order: (0..row_count).collect(),
+            multi_select,
+            reorderable: false,
+            selected: BTreeSet::new(),
+            cursor: 0,
+            anchor: None,
+            scroll_handle,
+            drop_target: None,
+            scroll_speed: 0.0,
+            _autoscroll: None,
+            empty_title: "No items".into(),
+            empty_hint: None,
+            drag_scroll: DragScroll::default(),
+            scroller,
+        }
+    }
+
+    // Drag handles to reorder the rows
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    // What the list says when it has no rows
+    pub fn empty_title(mut self, title: impl Into<SharedString>) -> Self {
+        self.empty_title = title.into();
+        self
+    }
+
+    pub fn empty_hint(mut self, hint: impl Into<SharedString>) -> Self {
+        self.empty_hint = Some(hint.into());
+        self
+    }
+
+    pub fn multi_select(&self) -> bool {
+        self.multi_select
+    }
+
+    pub fn is_reorderable(&self) -> bool {
+        self.reorderable
+    }
+
+    // Replace the rows with new ones, clearing the selection
+    pub fn set_rows(&mut self, rows: Vec<SharedString>, cx: &mut Context<Self>) {
+        self.row_count = rows.len();
+        self.order = (0..rows.len()).collect();
+        self.rows = Rows::Loaded(rows);
+        self.selected.clear();
+        self.cursor = 0;
+        self.anchor = None;
+        self.drop_target = None;
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    // The text of a row by its original index, or None while it's still
+    // being fetched
+    pub fn row(&self, row: usize) -> Option<SharedString> {
+        match &self.rows {
+            Rows::Loaded(rows) => rows.get(row).cloned(),
+            Rows::Lazy { pages, .. } => match pages.get(&(row / PAGE_SIZE)) {
+                Some(Page::Loaded(rows)) => rows.get(row % PAGE_SIZE).cloned(),
+                _ => None,
+            },
+        }
+    }
+
+    // Original indices of the rows, in the order they're shown
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    // Original indices of the selected rows, in display order
+    pub fn selected(&self) -> impl Iterator<Item = usize> + '_ {
+        self.order
+            .iter()
+            .copied()
+            .filter(|row| self.selected.contains(row))
+    }
+
+    pub fn set_selected(&mut self, rows: impl IntoIterator<Item = usize>, cx: &mut Context<Self>) {
+        self.selected = rows
+            .into_iter()
+            .filter(|row| *row < self.row_count)
+            .collect();
+        if !self.multi_select {
+            // Keep only the first
+            self.selected = self.selected.iter().take(1).copied().collect();
+        }
+        cx.notify();
+    }
+
+    fn select_all_state(&self) -> CheckState {
+        match self.selected.len() {
+            0 => CheckState::Unchecked,
+            n if n == self.row_count => CheckState::Checked,
+            _ => CheckState::Indeterminate,
+        }
+    }
+
+    fn move_cursor(&mut self, position: usize, cx: &mut Context<Self>) {
+        self.cursor = position;
+        if !self.multi_select {
+            self.selected = BTreeSet::from([self.order[position]]);
+        }
+        self.scroll_handle
+            .scroll_to_item(position, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn toggle(&mut self, position: usize) {
+        let row = self.order[position];
+        if !self.selected.remove(&row) {
+            self.selected.insert(row);
+        }
+    }
+
+    fn on_row_click(
+        &mut self,
+        position: usize,
+        event: &ClickEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // The end of a drag that scrolled the list
+        if self.drag_scroll.dragged() {
+            return;
+        }
+        window.focus(&self.focus_handle);
+        if event.click_count() > 1 {
+            cx.emit(Activated(self.order[position]));
+            return;
+        }
+        self.click_row(position, event.modifiers().shift, cx);
+    }
+
+    // With checkboxes a click toggles the row, and Shift-click selects the
+    // range from the last one toggled; otherwise the row is the selection
+    fn click_row(&mut self, position: usize, shift: bool, cx: &mut Context<Self>) {
+        match (self.multi_select, self.anchor) {
+            (true, Some(anchor)) if shift => {
+                let range = anchor.min(position)..=anchor.max(position);
+                self.selected
+                    .extend(range.map(|position| self.order[position]));
+            }
+            (true, _) => {
+                self.toggle(position);
+                self.anchor = Some(position);
+            }
+            (false, _) => self.selected = BTreeSet::from([self.order[position]]),
+        }
+        self.cursor = position;
+        cx.notify();
+    }
+
+    // Move the row at one display position so it sits in the gap `to`
+    // (counted before the move), keeping the keyboard cursor on it
+    fn move_row(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        let row = self.order.remove(from);
+        let to = if to > from { to - 1 } else { to };
+        self.order.insert(to, row);
+        self.cursor = to;
+        self.anchor = None;
+        self.scroll_handle
+            .scroll_to_item(to, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    // Work out which gap the dragged row is over, from the row under the
+    // pointer: its top half means the gap above it, its bottom half the gap
+    // below
+    fn on_row_drag_move(
+        &mut self,
+        position: usize,
+        event: &DragMoveEvent<DraggedRow>,
+        cx: &mut Context<Self>,
+    ) {
+        if !event.bounds.contains(&event.event.position) {
+            return;
+        }
+        let target = if event.event.position.y < event.bounds.center().y {
+            position
+        } else {
+            position + 1
+        };
+        if self.drop_target != Some(target) {
+            self.drop_target = Some(target);
+            cx.notify();
+        }
+    }
+
+    fn on_drop(&mut self, dragged: &DraggedRow, cx: &mut Context<Self>) {
+        if let Some(target) = self.drop_target.take() {
+            self.move_row(dragged.position, target, cx);
+        }
+        self.set_scroll_speed(0.0, cx);
+    }
+
+    // Near the top or bottom of the list a dragged row scrolls it, faster
+    // the closer it gets to the edge
+    fn on_list_drag_move(&mut self, event: &DragMoveEvent<DraggedRow>, cx: &mut Context<Self>) {
+        let y = event.event.position.y;
+        let from_top = f32::from(y - event.bounds.top());
+        let from_bottom = f32::from(event.bounds.bottom() - y);
+        let speed = if from_top < SCROLL_ZONE {
+            -(SCROLL_ZONE - from_top.max(0.0))
+        } else if from_bottom < SCROLL_ZONE {
+            SCROLL_ZONE - from_bottom.max(0.0)
+        } else {
+            0.0
+        };
+        self.set_scroll_speed(speed / SCROLL_ZONE * MAX_SCROLL_SPEED, cx);
+    }
+
+    // Pointer events stop while the pointer rests at the edge, so the
+    // scrolling runs on a timer of its own until the drag ends or leaves
+    // the edge
+    fn set_scroll_speed(&mut self, speed: f32, cx: &mut Context<Self>) {
+        let was_scrolling = self.scroll_speed != 0.0;
+        self.scroll_speed = speed;
+        if speed == 0.0 || was_scrolling {
+            return;
+        }
+        self._autoscroll = Some(cx.spawn(async move |this, cx| loop {
+            cx.background_executor()
+                .timer(Duration::from_millis(16))
+                .await;
+            let scrolling = this.update(cx, |this, cx| this.autoscroll_step(cx));
+            if !matches!(scrolling, Ok(true)) {
+                return;
+            }
+        }));
+    }
+
+    fn autoscroll_step(&mut self, cx: &mut Context<Self>) -> bool {
+        if !cx.has_active_drag() {
+            self.scroll_speed = 0.0;
+        }
+        if self.scroll_speed == 0.0 {
+            return false;
+        }
+
+        let state = self.scroll_handle.0.borrow();
+        let Some(size) = state.last_item_size else {
+            return true;
+        };
+        let max_scroll = (size.contents.height - size.item.height).max(px(0.0));
+        let offset = state.base_handle.offset();
+        let y = (offset.y - px(self.scroll_speed)).clamp(-max_scroll, px(0.0));
+        state.base_handle.set_offset(point(offset.x, y));
+        cx.notify();
+        true
+    }
+
+    // Header checkbox: anything selected clears it, otherwise select all
+    fn on_select_all_click(&mut self, cx: &mut Context<Self>) {
+        if self.selected.is_empty() {
+            self.select_all(cx);
+        } else {
+            self.select_none(cx);
+        }
+    }
+
+    fn select_all(&mut self, cx: &mut Context<Self>) {
+        self.selected = (0..self.row_count).collect();
+        cx.notify();
+    }
+
+    fn select_none(&mut self, cx: &mut Context<Self>) {
+        self.selected.clear();
+        cx.notify();
+    }
+
+    fn invert_selection(&mut self, cx: &mut Context<Self>) {
+        self.selected = (0..self.row_count)
+            .filter(|row| !self.selected.contains(row))
+            .collect();
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.row_count == 0 {
+            return;
+        }
+        let last = self.row_count - 1;
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "down" if self.reorderable && keystroke.modifiers.alt => {
+                if self.cursor < last {
+                    self.move_row(self.cursor, self.cursor + 2, cx);
+                }
+            }
+            "up" if self.reorderable && keystroke.modifiers.alt => {
+                if self.cursor > 0 {
+                    self.move_row(self.cursor, self.cursor - 1, cx);
+                }
+            }
+            "down" => self.move_cursor((self.cursor + 1).min(last), cx),
+            "up" => self.move_cursor(self.cursor.saturating_sub(1), cx),
+            "space" if self.multi_select => {
+                self.toggle(self.cursor);
+                self.anchor = Some(self.cursor);
+                cx.notify();
+            }
+            "a" if self.multi_select && keystroke.modifiers.secondary() => self.select_all(cx),
+            _ => return,
+        }
+        cx.stop_propagation();
+    }
+
+    // Start fetching any page with rows in view (or just below it) that
+    // isn't loaded or on its way
+    fn fetch_rows(&mut self, positions: Range<usize>, cx: &mut Context<Self>) {
+        let Rows::Lazy { provider, pages } = &mut self.rows else {
+            return;
+        };
+        let end = (positions.end + PAGE_SIZE / 2).min(self.row_count);
+        let wanted: BTreeSet<usize> = self.order[positions.start..end]
+            .iter()
+            .map(|row| row / PAGE_SIZE)
+            .collect();
+
+        for page in wanted {
+            if pages.contains_key(&page) {
+                continue;
+            }
+            pages.insert(page, Page::Loading);
+            let start = page * PAGE_SIZE;
+            let rows = start..(start + PAGE_SIZE).min(self.row_count);
+            let fetch = cx.background_spawn(provider.fetch(rows));
+            cx.spawn(async move |this, cx| {
+                let result = fetch.await;
+                this.update(cx, |this, cx| this.page_fetched(page, result, cx))
+                    .ok();
+            })
+            .detach();
+        }
+    }
+
+    fn page_fetched(
+        &mut self,
+        page: usize,
+        result: Result<Vec<SharedString>, String>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Rows::Lazy { pages, .. } = &mut self.rows {
+            let page_state = match result {
+                Ok(rows) => Page::Loaded(rows),
+                Err(error) => Page::Failed(error.into()),
+            };
+            pages.insert(page, page_state);
+            cx.notify();
+        }
+    }
+
+    // The first error among the pages that failed
+    fn fetch_error(&self) -> Option<SharedString> {
+        let Rows::Lazy { pages, .. } = &self.rows else {
+            return None;
+        };
+        pages.values().find_map(|page| match page {
+            Page::Failed(error) => Some(error.clone()),
+            _ => None,
+        })
+    }
+
+    // Forget the failed pages, so the next frame fetches them again
+    fn retry(&mut self, cx: &mut Context<Self>) {
+        if let Rows::Lazy { pages, .. } = &mut self.rows {
+            pages.retain(|_, page| !matches!(page, Page::Failed(_)));
+            cx.notify();
+        }
+    }
+
+    fn render_rows(
+        &mut self,
+        range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        self.fetch_rows(range.clone(), cx);
+
+        let focused = self.focus_handle.is_focused(window);
+        let palette = theme::window_palette(window, cx);
+        let dragging = cx.has_active_drag();
+        let last = self.row_count.saturating_sub(1);
+        let row_height = row_height(cx);
+        range
+            .map(|position| {
+                let row = self.order[position];
+                let label = self.row(row);
+                let selected = self.selected.contains(&row);
+                // In single selection the highlight is the selection; with
+                // checkboxes it follows the keyboard cursor instead
+                let highlighted = if self.multi_select {
+                    focused && position == self.cursor
+                } else {
+                    selected
+                };
+                // The gap line sits on the top edge of the row below it, or
+                // the bottom edge of the last row
+                let gap = match self.drop_target {
+                    Some(target) if dragging && target == position => Some(px(-1.0)),
+                    Some(target) if dragging && target == position + 1 && position == last => {
+                        Some(row_height - px(1.0))
+                    }
+                    _ => None,
+                };
+
+                div()
+                    .id(row)
+                    .relative()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .h(row_height)
+                    .px_2()
+                    .when(highlighted && self.multi_select, |this| {
+                        this.bg(rgba(0x007AFF1F))
+                    })
+                    .when(highlighted && !self.multi_select, |this| {
+                        this.bg(rgb(0x007AFF)).text_color(rgb(0xFFFFFF))
+                    })
+                    // Rows still loading can't be picked yet
+                    .when(label.is_some(), |this| {
+                        this.cursor_pointer()
+                            .when(!highlighted, |this| {
+                                this.hover(|style| style.bg(palette.hover()))
+                            })
+                            .on_click(cx.listener(move |this, event, window, cx| {
+                                this.on_row_click(position, event, window, cx)
+                            }))
+                    })
+                    .when(self.reorderable, |this| {
+                        this.on_drag_move(cx.listener(move |this, event, _, cx| {
+                            this.on_row_drag_move(position, event, cx)
+                        }))
+                        .child(self.render_handle(position, row, &palette))
+                    })
+                    .when(self.multi_select, |this| {
+                        this.child(Checkbox::new(if selected {
+                            CheckState::Checked
+                        } else {
+                            CheckState::Unchecked
+                        }))
+                    })
+                    .map(|this| match label {
+                        Some(label) => this.child(label),
+                        None => this.child(self.render_placeholder(row, &palette)),
+                    })
+                    .when_some(gap, |this, top| this.child(render_gap(position, top)))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_handle(&self, position: usize, row: usize, palette: &Palette) -> impl IntoElement {
+        let dragged = DraggedRow {
+            position,
+            label: self.row(row).unwrap_or_else(|| "…".into()),
+        };
+        div()
+            .id(("handle", row))
+            .flex_none()
+            .px_1()
+            .text_color(palette.border)
+            .cursor_grab()
+            .hover(|style| style.text_color(palette.secondary_text))
+            .child("⠿")
+            .on_drag(dragged, |dragged, _, _, cx| cx.new(|_| dragged.clone()))
+    }
+
+    fn render_header(&self, palette: &Palette, cx: &mut Context<Self>) -> impl IntoElement {
+        let action = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_1()
+                .rounded(px(3.0))
+                .text_color(rgb(0x007AFF))
+                .cursor_pointer()
+                .hover(|style| style.bg(rgba(0x007AFF1F)))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .h(row_height(cx))
+            .px_2()
+            .border_b_1()
+            .border_color(palette.border)
+            .text_size(px(12.0))
+            .child(
+                div()
+                    .id("select-all")
+                    .cursor_pointer()
+                    .on_click(cx.listener(|this, _, _, cx| this.on_select_all_click(cx)))
+                    .child(Checkbox::new(self.select_all_state())),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_color(palette.secondary_text)
+                    .child(format!(
+                        "{} of {} selected",
+                        self.selected.len(),
+                        self.row_count
+                    )),
+            )
+            // Bulk actions
+            .child(action("all", "All").on_click(cx.listener(|this, _, _, cx| this.select_all(cx))))
+            .child(
+                action("none", "None").on_click(cx.listener(|this, _, _, cx| this.select_none(cx))),
+            )
+            .child(
+                action("invert", "Invert")
+                    .on_click(cx.listener(|this, _, _, cx| this.invert_selection(cx))),
+            )
+    }
+
+    // A row that isn't loaded: a skeleton line while its page is fetched, or
+    // a short note if fetching failed (the Retry bar is below the list).
+    // Bar widths vary a little so the rows look like text.
+    fn render_placeholder(&self, row: usize, palette: &Palette) -> AnyElement {
+        if let Rows::Lazy { pages, .. } = &self.rows {
+            if let Some(Page::Failed(_)) = pages.get(&(row / PAGE_SIZE)) {
+                return div()
+                    .text_size(px(12.0))
+                    .text_color(palette.muted_text)
+                    .child("Not loaded")
+                    .into_any_element();
+            }
+        }
+
+        let width = 0.35 + (row * 37 % 40) as f32 / 100.0;
+        Skeleton::line()
+            .id(("loading", row))
+            .width(relative(width))
+            .into_any_element()
+    }
+
+    fn render_fetch_error(
+        &self,
+        error: SharedString,
+        palette: &Palette,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .h(row_height(cx))
+            .px_2()
+            .border_t_1()
+            .border_color(palette.border)
+            .bg(rgba(0xFF3B3018))
+            .text_size(px(12.0))
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .text_color(rgb(0xD70015))
+                    .child(format!("Couldn't load rows: {error}")),
+            )
+            .child(
+                div()
+                    .id("retry")
+                    .px_1()
+                    .rounded(px(3.0))
+                    .text_color(rgb(0x007AFF))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgba(0x007AFF1F)))
+                    .on_click(cx.listener(|this, _, _, cx| this.retry(cx)))
+                    .child("Retry"),
+            )
+    }
+}
+
+// Taller in touch mode, to be easier to tap
+fn row_height(cx: &App) -> Pixels {
+    touch::target(px(ROW_HEIGHT), cx)
+}
+
+// The line showing where a dragged row will land. It grows out from the
+// left each time it moves, so the eye follows it from gap to gap.
+fn render_gap(position: usize, top: Pixels) -> impl IntoElement {
+    div()
+        .absolute()
+        .left_0()
+        .top(top)
+        .h(px(2.0))
+        .bg(rgb(0x007AFF))
+        .with_animation(
+            ("gap", position),
+            Animation::new(Duration::from_millis(150)).with_easing(ease_out_quint()),
+            |line, delta| line.w(relative(delta)),
+        )
+}
+
+impl Focusable for ListView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ListView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let palette = theme::window_palette(window, cx);
+        // A drag let go outside the list leaves nothing to drop into
+        if !cx.has_active_drag() {
+            self.drop_target = None;
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(palette.well)
+            .border_1()
+            .border_color(palette.border)
+            .rounded(px(5.0))
+            .overflow_hidden()
+            .text_size(px(13.0))
+            .text_color(palette.well_text)
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .when(self.multi_select, |this| {
+                this.child(self.render_header(&palette, cx))
+            })
+            .when(self.row_count == 0, |this| {
+                this.child(
+                    EmptyState::new(self.empty_title.clone())
+                        .when_some(self.empty_hint.clone(), EmptyState::hint),
+                )
+            })
+            // Dragging the list scrolls it in touch mode; a row dragged by its
+            // handle is moved instead
+            .when(touch::touch_mode(cx), |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, event: &MouseDownEvent, _, _| {
+                        let handle = this.scroll_handle.0.borrow().base_handle.clone();
+                        this.drag_scroll.down(event.position, &handle);
+                    }),
+                )
+                .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _, cx| {
+                    if !event.dragging() || cx.has_active_drag() {
+                        return;
+                    }
+                    let handle = this.scroll_handle.0.borrow().base_handle.clone();
+                    if this.drag_scroll.moved(event.position, &handle) {
+                        cx.notify();
+                    }
+                }))
+                .on_mouse_up(
+                    MouseButton::Left,
+                    cx.listener(|this, _, _, _| this.drag_scroll.up()),
+                )
+            })
+            .on_scroll_wheel(cx.listener(|this, event, _, cx| this.scroller.on_scroll(event, cx)))
+            .when(self.row_count > 0, |this| {
+                this.child(
+                    uniform_list("rows", self.row_count, cx.processor(Self::render_rows))
+                        .track_scroll(self.scroll_handle.clone())
+                        .flex_1()
+                        // Pulled past an end
+                        .relative()
+                        .top(self.scroller.overscroll())
+                        .when(self.reorderable, |this| {
+                            this.on_drag_move(
+                                cx.listener(|this, event, _, cx| this.on_list_drag_move(event, cx)),
+                            )
+                            .on_drop(cx.listener(|this, dragged, _, cx| this.on_drop(dragged, cx)))
+                        }),
+                )
+            })
+            .when_some(self.fetch_error(), |this, error| {
+                this.child(self.render_fetch_error(error, &palette, cx))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{AppContext, Entity, SharedString, TestAppContext};
+
+    use super::ListView;
+    use crate::components::checkbox::CheckState;
+
+    fn list(rows: usize, multi_select: bool, cx: &mut TestAppContext) -> Entity<ListView> {
+        let rows: Vec<SharedString> = (0..rows).map(|row| format!("Row {row}").into()).collect();
+        cx.new(|cx| ListView::new(rows, multi_select, cx))
+    }
+
+    fn selected(list: &Entity<ListView>, cx: &mut TestAppContext) -> Vec<usize> {
+        list.read_with(cx, |list, _| list.selected().collect())
+    }
+
+    #[gpui::test]
+    fn shift_click_selects_the_range_from_the_last_click(cx: &mut TestAppContext) {
+        let list = list(6, true, cx);
+        list.update(cx, |list, cx| {
+            list.click_row(1, false, cx);
+            list.click_row(3, true, cx);
+        });
+        assert_eq!(selected(&list, cx), [1, 2, 3]);
+
+        // Upwards too, still from the row clicked without Shift
+        list.update(cx, |list, cx| list.click_row(0, true, cx));
+        assert_eq!(selected(&list, cx), [0, 1, 2, 3]);
+
+        // A plain click toggles, and starts the next range
+        list.update(cx, |list, cx| {
+            list.click_row(2, false, cx);
+            list.click_row(5, true, cx);
+        });
+        assert_eq!(selected(&list, cx), [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[gpui::test]
+    fn shift_click_without_an_earlier_click_toggles(cx: &mut TestAppContext) {
+        let list = list(4, true, cx);
+        list.update(cx, |list, cx| list.click_row(2, true, cx));
+        assert_eq!(selected(&list, cx), [2]);
+    }
+
+    #[gpui::test]
+    fn single_selection_ignores_shift(cx: &mut TestAppContext) {
+        let list = list(4, false, cx);
+        list.update(cx, |list, cx| {
+            list.click_row(0, false, cx);
+            list.click_row(3, true, cx);
+        });
+        assert_eq!(selected(&list, cx), [3]);
+    }
+
+    #[gpui::test]
+    fn bulk_actions(cx: &mut TestAppContext) {
+        let list = list(5, true, cx);
+        let state = |cx: &mut TestAppContext| list.read_with(cx, |list, _| list.select_all_state());
+
+        list.update(cx, |list, cx| list.select_all(cx));
+        assert_eq!(selected(&list, cx), [0, 1, 2, 3, 4]);
+        assert_eq!(state(cx), CheckState::Checked);
+
+        list.update(cx, |list, cx| list.select_none(cx));
+        assert!(selected(&list, cx).is_empty());
+        assert_eq!(state(cx), CheckState::Unchecked);
+
+        list.update(cx, |list, cx| {
+            list.set_selected([1, 3], cx);
+            list.invert_selection(cx);
+        });
+        assert_eq!(selected(&list, cx), [0, 2, 4]);
+        assert_eq!(state(cx), CheckState::Indeterminate);
+
+        // The header checkbox clears a partial selection, then selects all
+        list.update(cx, |list, cx| list.on_select_all_click(cx));
+        assert!(selected(&list, cx).is_empty());
+        list.update(cx, |list, cx| list.on_select_all_click(cx));
+        assert_eq!(state(cx), CheckState::Checked);
+    }
+
+    #[gpui::test]
+    fn moving_a_row_up(cx: &mut TestAppContext) {
+        let list = list(4, true, cx);
+        list.update(cx, |list, cx| list.move_row(2, 0, cx));
+        list.read_with(cx, |list, _| {
+            assert_eq!(list.order(), [2, 0, 1, 3]);
+            assert_eq!(list.cursor, 0);
+        });
+    }
+
+    #[gpui::test]
+    fn moving_a_row_down(cx: &mut TestAppContext) {
+        let list = list(4, true, cx);
+        // The gap below row 2, counted before the move
+        list.update(cx, |list, cx| list.move_row(0, 3, cx));
+        list.read_with(cx, |list, _| {
+            assert_eq!(list.order(), [1, 2, 0, 3]);
+            assert_eq!(list.cursor, 2);
+        });
+
+        // Into its own gaps, it stays put
+        list.update(cx, |list, cx| {
+            list.move_row(1, 1, cx);
+            list.move_row(1, 2, cx);
+        });
+        list.read_with(cx, |list, _| assert_eq!(list.order(), [1, 2, 0, 3]));
+    }
+
+    #[gpui::test]
+    fn dropping_a_row_at_the_end(cx: &mut TestAppContext) {
+        let list = list(4, true, cx);
+        list.update(cx, |list, cx| list.move_row(1, 4, cx));
+        list.read_with(cx, |list, _| {
+            assert_eq!(list.order(), [0, 2, 3, 1]);
+            assert_eq!(list.cursor, 3);
+        });
+    }
+
+    #[gpui::test]
+    fn the_selection_moves_with_its_row(cx: &mut TestAppContext) {
+        let multi = list(4, true, cx);
+        multi.update(cx, |list, cx| {
+            list.click_row(3, false, cx);
+            list.move_row(3, 0, cx);
+        });
+        multi.read_with(cx, |list, _| {
+            assert_eq!(list.order(), [3, 0, 1, 2]);
+            assert_eq!(list.selected().collect::<Vec<_>>(), [3]);
+            // A Shift-click range no longer starts from where it was
+            assert_eq!(list.anchor, None);
+        });
+
+        // And in single selection
+        let single = list(4, false, cx);
+        single.update(cx, |list, cx| {
+            list.click_row(1, false, cx);
+            list.move_row(1, 4, cx);
+        });
+        single.read_with(cx, |list, _| {
+            assert_eq!(list.order(), [0, 2, 3, 1]);
+            assert_eq!(list.selected().collect::<Vec<_>>(), [1]);
+        });
+    }
+}
@@ -0,0 +1,386 @@
+// ======================================================================
+// MENU COMPONENT
+// ======================================================================
+// A pop-up list of commands, where an item can open a submenu beside it:
+//
+//     cx.new(|cx| {
+//         Menu::new(
+//             vec![
+//                 MenuItem::action("open", "Open"),
+//                 MenuItem::submenu("Recent", vec![
+//                     MenuItem::action("recent-1", "notes.txt"),
+//                     MenuItem::action("recent-2", "todo.md"),
+//                 ]),
+//                 MenuItem::separator(),
+//                 MenuItem::action("quit", "Quit"),
+//             ],
+//             cx,
+//         )
+//     })
+//
+// Picking an item emits Picked with its id; Escape or a click outside
+// emits Dismissed. Either way the owner takes the menu away.
+//
+// The pointer can cut across other items on its way into an open
+// submenu without the submenu closing: hovering a sibling only switches
+// submenus once it has rested there for HOVER_INTENT.
+//
+// Keyboard: Up/Down move through the items, Home/End jump to the first
+// and last, Right or Enter opens a submenu and Left closes it, Enter or
+// Space picks, and typing a letter moves to the next item starting with
+// it.
+
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::{rendering, restyle};
+
+// How long the pointer has to rest on a sibling before the open submenu
+// gives way to it
+pub const HOVER_INTENT: Duration = Duration::from_millis(300);
+
+const ROW_HEIGHT: f32 = 24.0;
+const SEPARATOR_HEIGHT: f32 = 9.0;
+const PADDING: f32 = 4.0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    Action {
+        id: SharedString,
+        label: SharedString,
+    },
+    Submenu {
+        label: SharedString,
+        items: Vec<MenuItem>,
+    },
+    Separator,
+}
+
+impl MenuItem {
+    pub fn action(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self::Action {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+
+    pub fn submenu(label: impl Into<SharedString>, items: Vec<MenuItem>) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            items,
+        }
+    }
+
+    pub fn separator() -> Self {
+        Self::Separator
+    }
+
+    fn label(&self) -> Option<&SharedString> {
+        match self {
+            Self::Action { label, .. } | Self::Submenu { label, .. } => Some(label),
+            Self::Separator => None,
+        }
+    }
+
+    fn height(&self) -> f32 {
+        match self {
+            Self::Separator => SEPARATOR_HEIGHT,
+            _ => ROW_HEIGHT,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MenuEvent {
+    Picked(SharedString),
+    Dismissed,
+}
+
+pub struct Menu {
+    focus_handle: FocusHandle,
+    items: Vec<MenuItem>,
+    // One entry per open level, outermost first: the item highlighted in
+    // it, if any. Each level after the first is the submenu of the item
+    // highlighted in the one before. The keyboard works on the last.
+    highlight: Vec<Option<usize>>,
+    // A hovered sibling waiting out HOVER_INTENT, and its level
+    pending: Option<(usize, Task<()>)>,
+}
+
+impl EventEmitter<MenuEvent> for Menu {}
+
+impl Menu {
+    pub fn new(items: Vec<MenuItem>, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            items,
+            highlight: vec![None],
+            pending: None,
+        }
+    }
+
+    fn items_at(&self, level: usize) -> &[MenuItem] {
+        let mut items = self.items.as_slice();
+        for index in self.highlight[..level].iter().flatten() {
+            if let Some(MenuItem::Submenu { items: inner, .. }) = items.get(*index) {
+                items = inner;
+            }
+        }
+        items
+    }
+
+    fn deepest(&self) -> usize {
+        self.highlight.len() - 1
+    }
+
+    // Highlight an item, closing any submenus below its level
+    fn highlight_at(&mut self, level: usize, index: usize) {
+        self.highlight.truncate(level + 1);
+        self.highlight[level] = Some(index);
+    }
+
+    // Open the submenu of the item highlighted at `level`, if it is one,
+    // highlighting its first item when the keyboard opened it
+    fn open_submenu(&mut self, level: usize, highlight_first: bool) -> bool {
+        let Some(index) = self.highlight[level] else {
+            return false;
+        };
+        let Some(MenuItem::Submenu { items, .. }) = self.items_at(level).get(index) else {
+            return false;
+        };
+        let first = if highlight_first {
+            items.iter().position(|item| item.label().is_some())
+        } else {
+            None
+        };
+        self.highlight.truncate(level + 1);
+        self.highlight.push(first);
+        true
+    }
+
+    fn pick(&mut self, level: usize, index: usize, cx: &mut Context<Self>) {
+        self.pending = None;
+        self.highlight_at(level, index);
+        match &self.items_at(level)[index] {
+            MenuItem::Action { id, .. } => {
+                let id = id.clone();
+                self.highlight = vec![None];
+                cx.emit(MenuEvent::Picked(id));
+            }
+            MenuItem::Submenu { .. } => {
+                self.open_submenu(level, true);
+            }
+            MenuItem::Separator => {}
+        }
+        cx.notify();
+    }
+
+    fn on_hover(&mut self, level: usize, index: usize, cx: &mut Context<Self>) {
+        // The pointer made it into a submenu, so it was only crossing its
+        // parent's siblings
+        if self.pending.as_ref().is_some_and(|(at, _)| *at < level) {
+            self.pending = None;
+        }
+        if self.highlight.get(level) == Some(&Some(index)) {
+            self.pending = None;
+            return;
+        }
+
+        let submenu_open = self.highlight.len() > level + 1;
+        if !submenu_open {
+            self.highlight_at(level, index);
+            self.open_submenu(level, false);
+            cx.notify();
+            return;
+        }
+        let task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(HOVER_INTENT).await;
+            this.update(cx, |this, cx| {
+                this.pending = None;
+                if level < this.highlight.len() {
+                    this.highlight_at(level, index);
+                    this.open_submenu(level, false);
+                    cx.notify();
+                }
+            })
+            .ok();
+        });
+        self.pending = Some((level, task));
+    }
+
+    // The next item to highlight going `forward` from `from` (or from an
+    // end), skipping separators and wrapping around
+    fn step(items: &[MenuItem], from: Option<usize>, forward: bool) -> Option<usize> {
+        let count = items.len();
+        (1..=count)
+            .map(|offset| match (from, forward) {
+                (Some(from), true) => (from + offset) % count,
+                (Some(from), false) => (from + count - offset) % count,
+                (None, true) => offset - 1,
+                (None, false) => count - offset,
+            })
+            .find(|&index| items[index].label().is_some())
+    }
+
+    // The next item after the highlighted one starting with `letter`
+    fn typeahead(&self, letter: char) -> Option<usize> {
+        let items = self.items_at(self.deepest());
+        let from = self.highlight[self.deepest()];
+        let count = items.len();
+        let start = from.map_or(0, |from| from + 1);
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&index| {
+                items[index].label().is_some_and(|label| {
+                    label
+                        .chars()
+                        .next()
+                        .is_some_and(|first| first.to_lowercase().eq(letter.to_lowercase()))
+                })
+            })
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.pending = None;
+        let level = self.deepest();
+        let current = self.highlight[level];
+        let items = self.items_at(level);
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "down" => self.highlight[level] = Self::step(items, current, true),
+            "up" => self.highlight[level] = Self::step(items, current, false),
+            "home" => self.highlight[level] = Self::step(items, None, true),
+            "end" => self.highlight[level] = Self::step(items, None, false),
+            "right" => {
+                if !self.open_submenu(level, true) {
+                    return;
+                }
+            }
+            "left" if level > 0 => {
+                self.highlight.pop();
+            }
+            "enter" | "space" => match current {
+                Some(index) => self.pick(level, index, cx),
+                None => return,
+            },
+            "escape" if level > 0 => {
+                self.highlight.pop();
+            }
+            "escape" => cx.emit(MenuEvent::Dismissed),
+            _ => {
+                let letter = keystroke
+                    .key_char
+                    .as_deref()
+                    .filter(|_| !keystroke.modifiers.modified())
+                    .and_then(|text| text.chars().next())
+                    .filter(|letter| letter.is_alphanumeric());
+                match letter.and_then(|letter| self.typeahead(letter)) {
+                    Some(index) => self.highlight[level] = Some(index),
+                    None => return,
+                }
+            }
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn render_level(&self, level: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let highlighted = self.highlight[level];
+        // The keyboard's level shows its highlight in full; the ones
+        // above just mark the way to it
+        let active = level == self.deepest();
+        let rows = self
+            .items_at(level)
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let Some(label) = item.label() else {
+                    return div()
+                        .my(px(4.0))
+                        .h(px(1.0))
+                        .bg(rgb(0xE0E0E0))
+                        .into_any_element();
+                };
+                let is_highlighted = highlighted == Some(index);
+                div()
+                    .id(("item", index))
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .h(px(ROW_HEIGHT))
+                    .px_2()
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0xE4EEFB)))
+                    .when(is_highlighted && !active, |this| this.bg(rgb(0xE4EEFB)))
+                    .when(is_highlighted && active, |this| {
+                        this.bg(rgb(0x007AFF)).text_color(rgb(0xFFFFFF))
+                    })
+                    .on_hover(cx.listener(move |this, hovered: &bool, _, cx| {
+                        if *hovered {
+                            this.on_hover(level, index, cx);
+                        }
+                    }))
+                    .on_click(cx.listener(move |this, _, _, cx| this.pick(level, index, cx)))
+                    .child(label.clone())
+                    .when(matches!(item, MenuItem::Submenu { .. }), |this| {
+                        this.child(div().text_size(px(10.0)).child("▸"))
+                    })
+                    .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        let level = div()
+            .id(("level", level))
+            .mt(px(self.level_offset(level)))
+            .min_w(px(160.0))
+            .py(px(PADDING))
+            .bg(rgb(0xFFFFFF))
+            .border_1()
+            .border_color(rgb(0xB8B8B8))
+            .when(!rendering::lite(cx), |this| {
+                this.rounded(px(5.0)).shadow_md()
+            })
+            .children(rows);
+        restyle::apply("Menu", level, cx)
+    }
+
+    // How far down a level sits, so a submenu's first item lines up with
+    // the item that opened it
+    fn level_offset(&self, level: usize) -> f32 {
+        (0..level)
+            .map(|parent| {
+                let index = self.highlight[parent].unwrap_or(0);
+                self.items_at(parent)[..index]
+                    .iter()
+                    .map(MenuItem::height)
+                    .sum::<f32>()
+            })
+            .sum()
+    }
+}
+
+impl Focusable for Menu {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for Menu {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let levels: Vec<_> = (0..self.highlight.len())
+            .map(|level| self.render_level(level, cx))
+            .collect();
+
+        div()
+            .flex()
+            .items_start()
+            .text_size(px(13.0))
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_mouse_down_out(cx.listener(|_, _, _, cx| cx.emit(MenuEvent::Dismissed)))
+            .children(levels)
+    }
+}
@@ -0,0 +1,263 @@
+// ======================================================================
+// METER AND GAUGE COMPONENTS
+// ======================================================================
+// Show how full something is, for dialogs like "Your disk is almost
+// full": Meter is a horizontal bar, Gauge a 270° dial with the percentage
+// in the middle.
+//
+//     Meter::new(used / total).label("Macintosh HD").thresholds(0.75, 0.9)
+//     Gauge::new(cpu).label("CPU")
+//
+// Both turn from green to orange past the warning threshold and to red
+// past the critical one, blending over a short stretch before each, so
+// the color warns as the value creeps up. Values are fractions, 0.0 to
+// 1.0.
+
+use std::f32::consts::PI;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+const NORMAL: u32 = 0x34C759;
+const WARNING: u32 = 0xFF9500;
+const CRITICAL: u32 = 0xFF3B30;
+// How far below a threshold the color starts changing
+const BLEND: f32 = 0.05;
+
+// The gauge's dial starts at the bottom left and sweeps clockwise over
+// the top to the bottom right
+const DIAL_START: f32 = 0.75 * PI;
+const DIAL_SWEEP: f32 = 1.5 * PI;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Thresholds {
+    warning: f32,
+    critical: f32,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            warning: 0.75,
+            critical: 0.9,
+        }
+    }
+}
+
+// The color for a value: green, orange past `warning`, red past
+// `critical`, mixed near each threshold
+pub fn level_color(value: f32, warning: f32, critical: f32) -> Rgba {
+    let mix = |from: u32, to: u32, threshold: f32| {
+        let t = ((value - (threshold - BLEND)) / BLEND).clamp(0.0, 1.0);
+        let (from, to) = (rgb(from), rgb(to));
+        Rgba {
+            r: from.r + (to.r - from.r) * t,
+            g: from.g + (to.g - from.g) * t,
+            b: from.b + (to.b - from.b) * t,
+            a: 1.0,
+        }
+    };
+    if value > critical - BLEND {
+        mix(WARNING, CRITICAL, critical)
+    } else {
+        mix(NORMAL, WARNING, warning)
+    }
+}
+
+// ======================================================================
+// METER
+// ======================================================================
+
+#[derive(IntoElement)]
+pub struct Meter {
+    value: f32,
+    label: Option<SharedString>,
+    thresholds: Thresholds,
+}
+
+impl Meter {
+    pub fn new(value: f32) -> Self {
+        Self {
+            value: value.clamp(0.0, 1.0),
+            label: None,
+            thresholds: Thresholds::default(),
+        }
+    }
+
+    // Shown above the bar, with the percentage on the right
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn thresholds(mut self, warning: f32, critical: f32) -> Self {
+        self.thresholds = Thresholds { warning, critical };
+        self
+    }
+}
+
+impl RenderOnce for Meter {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let Thresholds { warning, critical } = self.thresholds;
+        let color = level_color(self.value, warning, critical);
+        // Thin marks on the track where the thresholds are
+        let tick = |at: f32| {
+            div()
+                .absolute()
+                .top_0()
+                .left(relative(at))
+                .w(px(1.0))
+                .h_full()
+                .bg(rgb(0xFFFFFF))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .w_full()
+            .when_some(self.label, |this, label| {
+                this.child(
+                    div()
+                        .flex()
+                        .justify_between()
+                        .text_size(px(11.0))
+                        .text_color(rgb(0x4D4D4D))
+                        .child(label)
+                        .child(format!("{:.0}%", self.value * 100.0)),
+                )
+            })
+            .child(
+                div()
+                    .relative()
+                    .w_full()
+                    .h(px(8.0))
+                    .rounded_full()
+                    .bg(rgb(0xD8D8D8))
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .h_full()
+                            .w(relative(self.value))
+                            .rounded_full()
+                            .bg(color),
+                    )
+                    .child(tick(warning))
+                    .child(tick(critical)),
+            )
+    }
+}
+
+// ======================================================================
+// GAUGE
+// ======================================================================
+
+#[derive(IntoElement)]
+pub struct Gauge {
+    value: f32,
+    label: Option<SharedString>,
+    thresholds: Thresholds,
+    size: Pixels,
+}
+
+impl Gauge {
+    pub fn new(value: f32) -> Self {
+        Self {
+            value: value.clamp(0.0, 1.0),
+            label: None,
+            thresholds: Thresholds::default(),
+            size: px(96.0),
+        }
+    }
+
+    // Shown under the percentage, inside the dial
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn thresholds(mut self, warning: f32, critical: f32) -> Self {
+        self.thresholds = Thresholds { warning, critical };
+        self
+    }
+
+    // Width and height of the dial
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+// Stroke part of the dial, from one fraction of the sweep to another, as
+// short straight segments
+fn paint_arc(
+    window: &mut Window,
+    bounds: Bounds<Pixels>,
+    width: Pixels,
+    (from, to): (f32, f32),
+    color: Rgba,
+) {
+    let center = bounds.center();
+    let radius = f32::from(bounds.size.width.min(bounds.size.height) - width) / 2.0;
+    let steps = ((to - from) * 64.0).ceil().max(1.0) as usize;
+
+    let mut path = PathBuilder::stroke(width);
+    for step in 0..=steps {
+        let fraction = from + (to - from) * step as f32 / steps as f32;
+        let angle = DIAL_START + DIAL_SWEEP * fraction;
+        let point = center + point(px(radius * angle.cos()), px(radius * angle.sin()));
+        if step == 0 {
+            path.move_to(point);
+        } else {
+            path.line_to(point);
+        }
+    }
+    if let Ok(path) = path.build() {
+        window.paint_path(path, color);
+    }
+}
+
+impl RenderOnce for Gauge {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let Thresholds { warning, critical } = self.thresholds;
+        let color = level_color(self.value, warning, critical);
+        let value = self.value;
+        let stroke = self.size * 0.1;
+
+        let dial = canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                paint_arc(window, bounds, stroke, (0.0, 1.0), rgb(0xD8D8D8));
+                if value > 0.0 {
+                    paint_arc(window, bounds, stroke, (0.0, value), color);
+                }
+            },
+        )
+        .absolute()
+        .size_full();
+
+        div()
+            .relative()
+            .flex()
+            .flex_col()
+            .flex_none()
+            .items_center()
+            .justify_center()
+            .size(self.size)
+            .child(dial)
+            .child(
+                div()
+                    .text_size(self.size * 0.2)
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(format!("{:.0}%", value * 100.0)),
+            )
+            .when_some(self.label, |this, label| {
+                this.child(
+                    div()
+                        .text_size(self.size * 0.11)
+                        .text_color(rgb(0x4D4D4D))
+                        .child(label),
+                )
+            })
+    }
+}
@@ -0,0 +1,59 @@
+// ======================================================================
+// COMPONENTS
+// ======================================================================
+// Reusable building blocks shared by the dialogs. Small, stateless pieces
+// (Button, Badge, Avatar, Checkbox, Kbd, ProgressBar, Meter, Gauge,
+// Skeleton, EmptyState, ImageView) implement RenderOnce and are rebuilt
+// every frame; pieces that own state between frames (TextInput, TagInput,
+// ColorSwatches, ListView, Breadcrumb, Accordion, StatusBar, Sparkline,
+// ErrorBoundary, AnimatedImage, ImagePreview, Menu) are entities that
+// implement Render. Pictures of any kind go through the image module's
+// cache, and anything floating over the dialog goes through popup().
+
+pub mod accordion;
+pub mod animated_image;
+pub mod avatar;
+pub mod badge;
+pub mod breadcrumb;
+pub mod button;
+pub mod checkbox;
+pub mod color_swatches;
+pub mod empty_state;
+pub mod error_boundary;
+pub mod image;
+pub mod image_preview;
+pub mod kbd;
+pub mod list_view;
+pub mod menu;
+pub mod meter;
+pub mod popup;
+pub mod progress_bar;
+pub mod skeleton;
+pub mod sparkline;
+pub mod status_bar;
+pub mod tag_input;
+pub mod text_input;
+
+pub use accordion::Accordion;
+pub use animated_image::AnimatedImage;
+pub use avatar::{Avatar, AvatarSize};
+pub use badge::{Badge, Tone};
+pub use breadcrumb::Breadcrumb;
+pub use button::{Button, ButtonStyle};
+pub use checkbox::{CheckState, Checkbox};
+pub use color_swatches::ColorSwatches;
+pub use empty_state::EmptyState;
+pub use error_boundary::ErrorBoundary;
+pub use image::{ImageSource, ImageState, ImageView};
+pub use image_preview::ImagePreview;
+pub use kbd::Kbd;
+pub use list_view::{ListView, RowProvider};
+pub use menu::{Menu, MenuEvent, MenuItem};
+pub use meter::{Gauge, Meter};
+pub use popup::popup;
+pub use progress_bar::ProgressBar;
+pub use skeleton::Skeleton;
+pub use sparkline::{Sparkline, SparklineStyle};
+pub use status_bar::{StatusBar, Zone};
+pub use tag_input::TagInput;
+pub use text_input::TextInput;
@@ -0,0 +1,36 @@
+// ======================================================================
+// POPUPS
+// ======================================================================
+// Dropdowns, suggestion lists and menus float over the rest of the
+// dialog:
+//
+//     div()
+//         .relative()
+//         .child(self.input.clone())
+//         .when(open, |this| this.child(popup(point(px(0.0), px(30.0)), list)))
+//
+// A popup is drawn in its dialog's own window, on top of everything else
+// in it, rather than in a window of its own. That keeps the two together
+// for free: when the dialog is moved, minimized or hidden (see
+// manager.rs) its popups go with it, and they close with it. GPUI can't
+// move a window once it's open, so a separate popup window couldn't follow
+// its dialog anyway.
+//
+// The cost is that a popup can't reach past the dialog's edges. Instead
+// of being cut off there, it flips to the other side of its anchor or
+// slides back inside, MARGIN from the edge.
+
+use gpui::*;
+
+const MARGIN: Pixels = px(8.0);
+
+// `content` placed `offset` from the top-left of the element it's a
+// child of
+pub fn popup(offset: Point<Pixels>, content: impl IntoElement) -> impl IntoElement {
+    deferred(
+        anchored()
+            .offset(offset)
+            .snap_to_window_with_margin(MARGIN)
+            .child(content),
+    )
+}
@@ -0,0 +1,44 @@
+// ======================================================================
+// PROGRESS BAR COMPONENT
+// ======================================================================
+// A thin horizontal bar filled from the left, as seen in macOS progress
+// sheets. The fill is a child div whose width is a fraction of the track.
+
+use gpui::*;
+
+use crate::restyle;
+
+#[derive(IntoElement)]
+pub struct ProgressBar {
+    // 0.0 = empty, 1.0 = full
+    fraction: f32,
+}
+
+impl ProgressBar {
+    pub fn new(fraction: f32) -> Self {
+        Self {
+            fraction: fraction.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl RenderOnce for ProgressBar {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        // The track
+        let track = div()
+            .w_full()
+            .h(px(6.0))
+            .rounded_full()
+            .bg(rgb(0xD8D8D8))
+            .overflow_hidden()
+            .child(
+                // The fill: relative() sizes it against the track's width
+                div()
+                    .h_full()
+                    .w(relative(self.fraction))
+                    .rounded_full()
+                    .bg(rgb(0x007AFF)),
+            );
+        restyle::apply("ProgressBar", track, cx)
+    }
+}
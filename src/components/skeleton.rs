@@ -0,0 +1,112 @@
+// ======================================================================
+// SKELETON COMPONENT
+// ======================================================================
+// Gray placeholder shapes that pulse gently while the content they stand
+// in for is still loading: a line for text, a circle for an avatar, a
+// rectangle for an image or a block of notes.
+//
+//     Skeleton::line().width(relative(0.6))
+//     Skeleton::circle(px(32.0))
+//     Skeleton::rect(px(120.0), px(80.0))
+//
+// until() swaps the placeholder for the content as soon as it's there,
+// so a render() can hand over whatever it has:
+//
+//     Skeleton::rect(px(300.0), px(120.0)).until(self.notes.clone())
+
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::power;
+
+// One pulse, dim to bright and back
+const PULSE: Duration = Duration::from_millis(1200);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Shape {
+    Line,
+    Circle,
+    Rect,
+}
+
+#[derive(IntoElement)]
+pub struct Skeleton {
+    id: ElementId,
+    shape: Shape,
+    width: Length,
+    height: Pixels,
+}
+
+impl Skeleton {
+    // A line of text: full width unless narrowed with width()
+    pub fn line() -> Self {
+        Self::new(Shape::Line, relative(1.0).into(), px(10.0))
+    }
+
+    pub fn circle(diameter: Pixels) -> Self {
+        Self::new(Shape::Circle, diameter.into(), diameter)
+    }
+
+    pub fn rect(width: impl Into<Length>, height: Pixels) -> Self {
+        Self::new(Shape::Rect, width.into(), height)
+    }
+
+    fn new(shape: Shape, width: Length, height: Pixels) -> Self {
+        Self {
+            id: "skeleton".into(),
+            shape,
+            width,
+            height,
+        }
+    }
+
+    // Siblings need ids of their own to pulse independently; ones that
+    // share an id pulse in step
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    // The content once it's loaded, the placeholder until then
+    pub fn until(self, content: Option<impl IntoElement>) -> AnyElement {
+        match content {
+            Some(content) => content.into_any_element(),
+            None => self.into_any_element(),
+        }
+    }
+}
+
+impl RenderOnce for Skeleton {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let placeholder = div()
+            .flex_none()
+            .w(self.width)
+            .h(self.height)
+            .bg(rgb(0xE5E5EA))
+            .map(|this| match self.shape {
+                Shape::Line | Shape::Circle => this.rounded_full(),
+                Shape::Rect => this.rounded(px(6.0)),
+            });
+
+        // A pulse redraws every frame, which isn't worth it on battery
+        if power::saving(cx) {
+            return placeholder.opacity(0.7).into_any_element();
+        }
+        placeholder
+            .with_animation(
+                self.id,
+                Animation::new(PULSE)
+                    .repeat()
+                    .with_easing(bounce(ease_in_out)),
+                |this, delta| this.opacity(0.4 + 0.6 * delta),
+            )
+            .into_any_element()
+    }
+}
@@ -0,0 +1,279 @@
+// ======================================================================
+// SPARKLINE COMPONENT
+// ======================================================================
+// A small chart of a series of numbers, as a line or as bars, for
+// monitoring dialogs. It scales itself to the values shown, can draw a
+// dashed baseline (a limit, or an average), and shows the value under
+// the pointer.
+//
+// Values can be set at once, pushed one by one, or streamed from a
+// channel, for instance one a worker thread fills from stdin:
+//
+//     let (sender, receiver) = futures::channel::mpsc::unbounded();
+//     let chart = cx.new(|cx| {
+//         let mut chart = Sparkline::new(Vec::new()).capacity(120);
+//         chart.follow(receiver, cx);
+//         chart
+//     });
+//     thread::spawn(move || {
+//         for line in io::stdin().lines().map_while(Result::ok) {
+//             if let Ok(value) = line.trim().parse() {
+//                 sender.unbounded_send(value).ok();
+//             }
+//         }
+//     });
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures::channel::mpsc;
+use futures::StreamExt;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SparklineStyle {
+    #[default]
+    Line,
+    Bars,
+}
+
+pub struct Sparkline {
+    values: Vec<f32>,
+    style: SparklineStyle,
+    baseline: Option<f32>,
+    // Pushing beyond this many values drops the oldest
+    capacity: Option<usize>,
+    color: Rgba,
+    height: Pixels,
+    // Index of the value under the pointer
+    hovered: Option<usize>,
+    // Where the chart was drawn last frame, to map the pointer to a value
+    bounds: Rc<Cell<Bounds<Pixels>>>,
+    _updates: Option<Task<()>>,
+}
+
+impl Sparkline {
+    pub fn new(values: Vec<f32>) -> Self {
+        Self {
+            values,
+            style: SparklineStyle::default(),
+            baseline: None,
+            capacity: None,
+            color: rgb(0x007AFF),
+            height: px(40.0),
+            hovered: None,
+            bounds: Rc::default(),
+            _updates: None,
+        }
+    }
+
+    pub fn bars(mut self) -> Self {
+        self.style = SparklineStyle::Bars;
+        self
+    }
+
+    // A dashed horizontal line at this value
+    pub fn baseline(mut self, baseline: f32) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    // Keep only the most recent values, for a chart that scrolls
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity.max(1));
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Rgba>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    pub fn height(mut self, height: Pixels) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    pub fn set_values(&mut self, values: Vec<f32>, cx: &mut Context<Self>) {
+        self.values = values;
+        self.trim();
+        cx.notify();
+    }
+
+    pub fn push(&mut self, value: f32, cx: &mut Context<Self>) {
+        self.values.push(value);
+        self.trim();
+        cx.notify();
+    }
+
+    // Push every value that arrives on the channel
+    pub fn follow(&mut self, mut values: mpsc::UnboundedReceiver<f32>, cx: &mut Context<Self>) {
+        self._updates = Some(cx.spawn(async move |this, cx| {
+            while let Some(value) = values.next().await {
+                if this.update(cx, |this, cx| this.push(value, cx)).is_err() {
+                    return; // The chart is gone
+                }
+            }
+        }));
+    }
+
+    fn trim(&mut self) {
+        if let Some(capacity) = self.capacity {
+            let excess = self.values.len().saturating_sub(capacity);
+            self.values.drain(..excess);
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let bounds = self.bounds.get();
+        let count = self.values.len();
+        let hovered = (count > 0 && bounds.contains(&event.position)).then(|| {
+            let x = (event.position.x - bounds.left()) / bounds.size.width;
+            match self.style {
+                SparklineStyle::Line => (x * (count - 1) as f32).round() as usize,
+                SparklineStyle::Bars => ((x * count as f32) as usize).min(count - 1),
+            }
+        });
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            cx.notify();
+        }
+    }
+}
+
+// Everything the paint step needs, copied out of the entity
+struct Chart {
+    values: Vec<f32>,
+    style: SparklineStyle,
+    baseline: Option<f32>,
+    hovered: Option<usize>,
+    color: Rgba,
+}
+
+impl Chart {
+    // The value range to fit in the height, never empty
+    fn range(&self) -> (f32, f32) {
+        let all = self.values.iter().chain(self.baseline.iter()).copied();
+        let min = all.clone().fold(f32::INFINITY, f32::min);
+        let max = all.fold(f32::NEG_INFINITY, f32::max);
+        if min < max {
+            (min, max)
+        } else {
+            (min - 1.0, max + 1.0)
+        }
+    }
+
+    fn paint(&self, bounds: Bounds<Pixels>, window: &mut Window) {
+        let count = self.values.len();
+        if count == 0 {
+            return;
+        }
+        let (min, max) = self.range();
+        let y = |value: f32| bounds.bottom() - bounds.size.height * ((value - min) / (max - min));
+
+        match self.style {
+            SparklineStyle::Line => {
+                let step = bounds.size.width / (count.max(2) - 1) as f32;
+                let at = |index: usize| {
+                    point(bounds.left() + step * index as f32, y(self.values[index]))
+                };
+                let mut path = PathBuilder::stroke(px(1.5));
+                path.move_to(at(0));
+                for index in 1..count {
+                    path.line_to(at(index));
+                }
+                if let Ok(path) = path.build() {
+                    window.paint_path(path, self.color);
+                }
+                if let Some(index) = self.hovered {
+                    let dot = Bounds::centered_at(at(index), size(px(5.0), px(5.0)));
+                    window.paint_quad(fill(dot, self.color).corner_radii(px(2.5)));
+                }
+            }
+            SparklineStyle::Bars => {
+                let slot = bounds.size.width / count as f32;
+                // Bars grow from the baseline when there is one, so values
+                // below it hang down
+                let base = y(self.baseline.unwrap_or(min));
+                for (index, value) in self.values.iter().enumerate() {
+                    let (top, bottom) = (y(*value).min(base), y(*value).max(base));
+                    let bar = Bounds::from_corners(
+                        point(bounds.left() + slot * index as f32, top),
+                        point(
+                            bounds.left() + slot * (index + 1) as f32 - px(1.0),
+                            bottom.max(top + px(1.0)),
+                        ),
+                    );
+                    let mut color = self.color;
+                    if self.hovered.is_some_and(|hovered| hovered != index) {
+                        color.a = 0.5;
+                    }
+                    window.paint_quad(fill(bar, color));
+                }
+            }
+        }
+
+        if let Some(baseline) = self.baseline {
+            let mut path = PathBuilder::stroke(px(1.0)).dash_array(&[px(3.0), px(3.0)]);
+            path.move_to(point(bounds.left(), y(baseline)));
+            path.line_to(point(bounds.right(), y(baseline)));
+            if let Ok(path) = path.build() {
+                window.paint_path(path, rgb(0x8E8E8E));
+            }
+        }
+    }
+}
+
+impl Render for Sparkline {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let chart = Chart {
+            values: self.values.clone(),
+            style: self.style,
+            baseline: self.baseline,
+            hovered: self.hovered,
+            color: self.color,
+        };
+        let bounds = self.bounds.clone();
+        let hovered_value = self.hovered.and_then(|index| self.values.get(index));
+
+        div()
+            .id("sparkline")
+            .relative()
+            .w_full()
+            .h(self.height)
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_hover(cx.listener(|this, hovered: &bool, _, cx| {
+                if !hovered {
+                    this.hovered = None;
+                    cx.notify();
+                }
+            }))
+            .child(
+                canvas(
+                    move |chart_bounds, _, _| bounds.set(chart_bounds),
+                    move |chart_bounds, _, window, _| chart.paint(chart_bounds, window),
+                )
+                .size_full(),
+            )
+            // The value under the pointer, in the top right corner
+            .when_some(hovered_value, |this, value| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .right_0()
+                        .px_1()
+                        .rounded(px(3.0))
+                        .bg(rgba(0xFFFFFFCC))
+                        .text_size(px(10.0))
+                        .text_color(rgb(0x3C3C3C))
+                        .child(format!("{value}")),
+                )
+            })
+    }
+}
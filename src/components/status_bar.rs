@@ -0,0 +1,133 @@
+// ======================================================================
+// STATUS BAR COMPONENT
+// ======================================================================
+// A thin bar along the bottom of a larger dialog, with three zones of
+// small text (left, center and right), a progress segment on the right,
+// and short-lived messages that take over the left zone for a while.
+//
+// Create it up front, attach it with Dialog::status_bar(), and keep the
+// entity to update it while the dialog is open:
+//
+//     let status = cx.new(|_| StatusBar::new());
+//     Dialog::log("Building…", lines).status_bar(status.clone()).show(cx, ...);
+//     status.update(cx, |status, cx| status.flash("Saved", Duration::from_secs(3), cx));
+
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::ProgressBar;
+use crate::restyle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Zone {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Default)]
+pub struct StatusBar {
+    left: Option<SharedString>,
+    center: Option<SharedString>,
+    right: Option<SharedString>,
+    // Shown instead of the left zone until its timer runs out
+    message: Option<SharedString>,
+    progress: Option<f32>,
+    _message_timer: Option<Task<()>>,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_text(&mut self, zone: Zone, text: impl Into<SharedString>, cx: &mut Context<Self>) {
+        *self.zone_mut(zone) = Some(text.into());
+        cx.notify();
+    }
+
+    pub fn clear(&mut self, zone: Zone, cx: &mut Context<Self>) {
+        *self.zone_mut(zone) = None;
+        cx.notify();
+    }
+
+    // Show a message in place of the left zone for `duration`. A newer
+    // message replaces it (and its timer).
+    pub fn flash(
+        &mut self,
+        message: impl Into<SharedString>,
+        duration: Duration,
+        cx: &mut Context<Self>,
+    ) {
+        self.message = Some(message.into());
+        self._message_timer = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(duration).await;
+            this.update(cx, |this, cx| {
+                this.message = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    // A small progress bar at the right end, or None to hide it
+    pub fn set_progress(&mut self, fraction: Option<f32>, cx: &mut Context<Self>) {
+        self.progress = fraction;
+        cx.notify();
+    }
+
+    fn zone_mut(&mut self, zone: Zone) -> &mut Option<SharedString> {
+        match zone {
+            Zone::Left => &mut self.left,
+            Zone::Center => &mut self.center,
+            Zone::Right => &mut self.right,
+        }
+    }
+}
+
+impl Render for StatusBar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Left and right share the spare width equally, which keeps the
+        // center zone centered
+        let zone = || {
+            div()
+                .flex()
+                .items_center()
+                .overflow_hidden()
+                .whitespace_nowrap()
+        };
+
+        let left = match &self.message {
+            Some(message) => zone().text_color(rgb(0x000000)).child(message.clone()),
+            None => zone().children(self.left.clone()),
+        };
+
+        let status_bar = div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .h(px(22.0))
+            .px_3()
+            .bg(rgb(0xF5F5F5))
+            .border_t_1()
+            .border_color(rgb(0xD0D0D0))
+            .text_size(px(11.0))
+            .text_color(rgb(0x4D4D4D))
+            .child(left.flex_1())
+            .child(zone().flex_none().children(self.center.clone()))
+            .child(
+                zone()
+                    .flex_1()
+                    .justify_end()
+                    .gap_2()
+                    .children(self.right.clone())
+                    .when_some(self.progress, |this, fraction| {
+                        this.child(div().w(px(80.0)).child(ProgressBar::new(fraction)))
+                    }),
+            );
+        restyle::apply("StatusBar", status_bar, cx)
+    }
+}
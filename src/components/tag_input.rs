@@ -0,0 +1,297 @@
+// ======================================================================
+// TAG INPUT COMPONENT
+// ======================================================================
+// A text input that turns what you type into tags: press Enter or type a
+// comma and the text becomes a removable chip. Backspace in the empty
+// input removes the last chip.
+//
+// Optional suggestions appear under the input as you type (Up/Down to
+// move, Tab or a click to accept), and a limit caps the number of tags.
+// As a form field the value is the tags joined with commas.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::popup::popup;
+use crate::components::TextInput;
+use crate::rendering;
+
+// Suggestions shown at once under the input
+const MAX_SUGGESTIONS: usize = 5;
+
+actions!(tag_input, [RemoveLastTag]);
+
+// Inside the tag input's text input, where Backspace would otherwise be
+// the text input's own
+const KEY_CONTEXT: &str = "TagInput > TextInput";
+
+// After the text input's bindings, so this one is tried first
+pub(crate) fn bind_keys(cx: &mut App) {
+    cx.bind_keys([KeyBinding::new(
+        "backspace",
+        RemoveLastTag,
+        Some(KEY_CONTEXT),
+    )]);
+}
+
+pub struct TagInput {
+    input: Entity<TextInput>,
+    tags: Vec<SharedString>,
+    suggestions: Vec<SharedString>,
+    max_tags: Option<usize>,
+    // Index into matching_suggestions()
+    highlighted: usize,
+}
+
+impl TagInput {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new(cx).placeholder("Add a tag…"));
+
+        // A typed comma ends the tag in front of it
+        cx.observe(&input, |this, input, cx| {
+            let text = input.read(cx).text().to_string();
+            if text.contains(',') {
+                let (done, rest) = text.rsplit_once(',').unwrap_or_default();
+                for tag in done.split(',') {
+                    this.add_tag(tag, cx);
+                }
+                let rest = rest.trim_start().to_string();
+                input.update(cx, |input, cx| input.set_text(rest, cx));
+            }
+            this.highlighted = 0;
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            input,
+            tags: Vec::new(),
+            suggestions: Vec::new(),
+            max_tags: None,
+            highlighted: 0,
+        }
+    }
+
+    // Tags offered while typing (those starting with the typed text)
+    pub fn suggestions(mut self, suggestions: impl IntoIterator<Item = SharedString>) -> Self {
+        self.suggestions = suggestions.into_iter().collect();
+        self
+    }
+
+    pub fn max_tags(mut self, max_tags: Option<usize>) -> Self {
+        self.max_tags = max_tags;
+        self
+    }
+
+    pub fn tags(&self) -> &[SharedString] {
+        &self.tags
+    }
+
+    pub fn set_tags(
+        &mut self,
+        tags: impl IntoIterator<Item = SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.tags.clear();
+        for tag in tags {
+            self.add_tag(&tag, cx);
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.max_tags.is_some_and(|max| self.tags.len() >= max)
+    }
+
+    // Blank and duplicate tags are ignored, as is anything over the limit
+    fn add_tag(&mut self, tag: &str, cx: &mut Context<Self>) {
+        let tag = tag.trim();
+        if tag.is_empty() || self.is_full() || self.tags.iter().any(|other| other == tag) {
+            return;
+        }
+        self.tags.push(tag.to_string().into());
+        cx.notify();
+    }
+
+    fn remove_tag(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.tags.len() {
+            self.tags.remove(index);
+            cx.notify();
+        }
+    }
+
+    // Add the typed text (or the highlighted suggestion) and clear the input
+    fn commit(&mut self, tag: Option<SharedString>, cx: &mut Context<Self>) {
+        let tag = tag.unwrap_or_else(|| self.input.read(cx).text().to_string().into());
+        self.add_tag(&tag, cx);
+        self.input.update(cx, |input, cx| input.set_text("", cx));
+    }
+
+    fn matching_suggestions(&self, cx: &App) -> Vec<SharedString> {
+        let typed = self.input.read(cx).text().trim().to_lowercase();
+        if typed.is_empty() || self.is_full() {
+            return Vec::new();
+        }
+        self.suggestions
+            .iter()
+            .filter(|suggestion| suggestion.to_lowercase().starts_with(&typed))
+            .filter(|suggestion| !self.tags.contains(suggestion))
+            .take(MAX_SUGGESTIONS)
+            .cloned()
+            .collect()
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let suggestions = self.matching_suggestions(cx);
+        let typed = !self.input.read(cx).text().is_empty();
+
+        match event.keystroke.key.as_str() {
+            // Enter only submits the dialog once the input is empty
+            "enter" if typed => self.commit(None, cx),
+            "tab" if !suggestions.is_empty() => {
+                let suggestion = suggestions.get(self.highlighted).cloned();
+                self.commit(suggestion, cx);
+            }
+            "down" if !suggestions.is_empty() => {
+                self.highlighted = (self.highlighted + 1) % suggestions.len();
+                cx.notify();
+            }
+            "up" if !suggestions.is_empty() => {
+                self.highlighted = (self.highlighted + suggestions.len() - 1) % suggestions.len();
+                cx.notify();
+            }
+            _ => return,
+        }
+        cx.stop_propagation();
+    }
+
+    // Backspace with nothing typed; otherwise it goes on to the text input
+    fn remove_last_tag(&mut self, _: &RemoveLastTag, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.input.read(cx).text().is_empty() || self.tags.is_empty() {
+            cx.propagate();
+            return;
+        }
+        self.remove_tag(self.tags.len() - 1, cx);
+    }
+
+    fn render_chip(
+        &self,
+        index: usize,
+        tag: &SharedString,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .pl_2()
+            .pr_1()
+            .h(px(22.0))
+            .rounded_full()
+            .bg(rgb(0xDCEBFF))
+            .text_size(px(12.0))
+            .text_color(rgb(0x0050B3))
+            .child(tag.clone())
+            .child(
+                // The × removes the tag
+                div()
+                    .id(("remove-tag", index))
+                    .px_1()
+                    .rounded_full()
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0xB8D6FF)))
+                    .on_click(cx.listener(move |this, _, _, cx| this.remove_tag(index, cx)))
+                    .child("×"),
+            )
+    }
+
+    fn render_suggestions(
+        &self,
+        suggestions: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .w(px(240.0))
+            .py_1()
+            .bg(rgb(0xFFFFFF))
+            .border_1()
+            .border_color(rgb(0xB8B8B8))
+            .when(!rendering::lite(cx), |this| {
+                this.rounded(px(5.0)).shadow_md()
+            })
+            .text_size(px(13.0))
+            .children(
+                suggestions
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, suggestion)| {
+                        let highlighted = index == self.highlighted;
+                        div()
+                            .id(("suggestion", index))
+                            .px_2()
+                            .py(px(2.0))
+                            .cursor_pointer()
+                            .when(highlighted, |row| {
+                                row.bg(rgb(0x007AFF)).text_color(rgb(0xFFFFFF))
+                            })
+                            .when(!highlighted, |row| {
+                                row.hover(|style| style.bg(rgb(0xF0F0F0)))
+                            })
+                            .on_click(cx.listener({
+                                let suggestion = suggestion.clone();
+                                move |this, _, _, cx| this.commit(Some(suggestion.clone()), cx)
+                            }))
+                            .child(suggestion)
+                    }),
+            )
+    }
+}
+
+impl Focusable for TagInput {
+    // Focusing the tag input means typing into its text input
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl Render for TagInput {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let suggestions = self.matching_suggestions(cx);
+        let chips: Vec<_> = self
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(index, tag)| self.render_chip(index, tag, cx))
+            .collect();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .key_context("TagInput")
+            .on_action(cx.listener(Self::remove_last_tag))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .when(!chips.is_empty(), |this| {
+                this.child(div().flex().flex_wrap().gap_1().children(chips))
+            })
+            .child(
+                div()
+                    .relative()
+                    .child(self.input.clone())
+                    // Suggestions float over whatever is below the input
+                    .when(!suggestions.is_empty(), |this| {
+                        this.child(popup(
+                            point(px(0.0), px(30.0)),
+                            self.render_suggestions(suggestions, cx),
+                        ))
+                    }),
+            )
+            .when_some(self.max_tags, |this, max| {
+                this.child(
+                    div()
+                        .text_size(px(11.0))
+                        .text_color(rgb(0x8E8E8E))
+                        .child(format!("{} of {max} tags", self.tags.len())),
+                )
+            })
+    }
+}
@@ -0,0 +1,154 @@
+// ======================================================================
+// CRASH HANDLER
+// ======================================================================
+// A panic in an event handler or a render() leaves GPUI half way through
+// an update, so the crashed process can't safely show anything more. The
+// handler installed here writes a report (the panic message and a
+// backtrace), logs it, and starts the app again with a flag asking it to
+// show the report. That second process only opens a plain log dialog, so
+// whatever component panicked isn't involved.
+//
+// Only a panic that takes the app down is reported, once. One caught by
+// an ErrorBoundary (see components/error_boundary.rs) is only logged, as
+// the app carries on; so is one on a background thread.
+//
+//     fn main() {
+//         crash::install("--crash-report");
+//         // ...and when the app is started with `--crash-report <path>`:
+//         crash::show_report(&path, cx, |cx| cx.quit());
+//     }
+
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use gpui::App;
+
+use crate::dialog::{log_channel, Dialog, DialogButton, DialogHandle};
+use crate::error::DialogError;
+
+// Set in the environment of the process showing a report, so a crash
+// while showing it doesn't start yet another one
+const REPORTING: &str = "GPUI_DIALOGS_CRASH_REPORT";
+
+// Whether the report has been handed to a new process already
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // How many catch_unwind()s of error boundaries we're inside
+    static CAUGHT: Cell<usize> = const { Cell::new(0) };
+}
+
+// Run `f` where a panic is caught and handled, so the hook doesn't treat
+// it as a crash
+pub(crate) fn caught<R>(f: impl FnOnce() -> R) -> R {
+    struct Depth;
+    impl Drop for Depth {
+        fn drop(&mut self) {
+            CAUGHT.with(|caught| caught.set(caught.get() - 1));
+        }
+    }
+    CAUGHT.with(|caught| caught.set(caught.get() + 1));
+    let _depth = Depth;
+    f()
+}
+
+// Whether a panic here ends the app: it isn't caught, and it's on the
+// main thread, where the event loop runs
+fn fatal() -> bool {
+    CAUGHT.with(Cell::get) == 0 && thread::current().name() == Some("main")
+}
+
+// Install the panic hook. `flag` is what the app understands as "show
+// the report at this path", passed with the path when it's restarted.
+pub fn install(flag: &'static str) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // The usual message on stderr first
+        default_hook(info);
+
+        let report = report(info);
+        if !fatal() {
+            log::warn!("{report}");
+            return;
+        }
+        log::error!("{report}");
+        if std::env::var_os(REPORTING).is_some() || REPORTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let Some(path) = save(&report) else { return };
+        let Ok(exe) = std::env::current_exe() else {
+            return;
+        };
+        Command::new(exe)
+            .arg(flag)
+            .arg(&path)
+            .env(REPORTING, "1")
+            .spawn()
+            .ok();
+    }));
+}
+
+fn report(info: &PanicHookInfo) -> String {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    let location = info
+        .location()
+        .map(|location| format!(" at {location}"))
+        .unwrap_or_default();
+    format!(
+        "{} {} panicked{location}: {message}\n\n{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        Backtrace::force_capture()
+    )
+}
+
+// Write the report where the restarted app can read it
+fn save(report: &str) -> Option<PathBuf> {
+    let path = std::env::temp_dir().join(format!("gpui-dialogs-crash-{}.txt", std::process::id()));
+    fs::write(&path, report).ok()?;
+    Some(path)
+}
+
+// Show a report written by the crash handler. "Copy Report" puts it on
+// the clipboard before closing.
+pub fn show_report(
+    path: &Path,
+    cx: &mut App,
+    on_close: impl FnOnce(&mut App) + 'static,
+) -> Result<DialogHandle, DialogError> {
+    let report = fs::read_to_string(path)
+        .unwrap_or_else(|err| format!("The report at {} can't be read: {err}", path.display()));
+
+    let (lines, receiver) = log_channel();
+    for line in report.lines() {
+        lines.unbounded_send(line.to_string().into()).ok();
+    }
+
+    let message = format!(
+        "The app quit unexpectedly. The report is saved in {}.",
+        path.display()
+    );
+    Dialog::log(message, receiver)
+        .title("Crash Report")
+        .buttons([
+            DialogButton::secondary("Copy Report"),
+            DialogButton::primary("Close"),
+        ])
+        .show(cx, move |result, cx| {
+            if result.button() == Some("Copy Report") {
+                cx.write_to_clipboard(gpui::ClipboardItem::new_string(report));
+            }
+            on_close(cx);
+        })
+}
@@ -0,0 +1,2546 @@
+// ======================================================================
+// DIALOG
+// ======================================================================
+// A Dialog describes what to show (message, buttons, and the kind of
+// body: a plain question, a form, or a progress bar). Calling show()
+// opens the backdrop and the dialog window, and hands a DialogResult to
+// your callback once the user answers.
+//
+//     Dialog::question("Delete this file?")
+//         .show(cx, |result, cx| {
+//             if result.is_confirmed() { /* ... */ }
+//         });
+//
+// The window content is the DialogBox component further down.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+use futures::channel::mpsc;
+use futures::StreamExt;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{self, AuditSink};
+use crate::backdrop::{close_backdrop, keep_above, open_backdrop, primary_display, Backdrop};
+use crate::capture::Capture;
+use crate::components::color_swatches::parse_hex;
+use crate::components::list_view::{Activated, RowProvider};
+use crate::components::{
+    Badge, Button, ButtonStyle, CheckState, Checkbox, ColorSwatches, ListView, ProgressBar,
+    StatusBar, TagInput, TextInput,
+};
+use crate::error::DialogError;
+use crate::gesture::{self, Click, ClickCounter, LongPress};
+use crate::layout::{self, rem, Breakpoint};
+use crate::manager::Priority;
+use crate::pill::{self, ProgressPill};
+use crate::result::{DialogMetrics, DialogResult, Outcome};
+use crate::screen;
+use crate::scroll::Scroller;
+use crate::session::{self, SessionRecord};
+use crate::settings::{self, ButtonOrder, ThemeChoice};
+use crate::testing::{Kind, Live};
+use crate::theme::{self, Palette};
+use crate::{motion, power, privacy, rendering, restyle, trace};
+
+// ======================================================================
+// BUTTONS
+// ======================================================================
+
+// What a button means, independent of its label. The role decides the
+// button's style and what the result reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ButtonRole {
+    // The affirmative action (OK, Save, Delete). Triggered by Enter.
+    Primary,
+    // Backs out of the dialog
+    Cancel,
+    // Any other choice ("Don't Save", "Later", ...)
+    Secondary,
+}
+
+// What pressing ESC does
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EscapePolicy {
+    // Close the dialog with Outcome::Dismissed
+    #[default]
+    Dismiss,
+    // Press the Cancel button, so the result names it. Dismisses when
+    // there isn't one.
+    TriggerCancelButton,
+    // Nothing; the user has to pick a button
+    Ignore,
+    // Dismiss only on a second ESC within a second of the first, for
+    // dialogs where losing what was typed would hurt
+    RequireConfirm,
+}
+
+// How long RequireConfirm waits for the second ESC
+const ESCAPE_CONFIRM_WINDOW: Duration = Duration::from_secs(1);
+
+// What a dialog does once it has gone unanswered past its max age
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Expiry {
+    // Stay open but dimmed, noting that the prompt expired. Only buttons
+    // that don't go ahead still work.
+    #[default]
+    MarkStale,
+    // Close with Outcome::Expired
+    Resolve,
+}
+
+// How often an aging dialog looks at the clock. The wall clock, so a
+// laptop asleep overnight still finds its prompts a day older.
+const EXPIRY_CHECK: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DialogButton {
+    pub label: SharedString,
+    pub role: ButtonRole,
+    // A count shown in a bubble after the label ("Review 3")
+    pub badge: Option<usize>,
+    // Only pressed by holding it down (mouse or Enter) for a second, for
+    // actions that can't be undone
+    pub hold_to_confirm: bool,
+}
+
+impl DialogButton {
+    pub fn primary(label: impl Into<SharedString>) -> Self {
+        Self::new(label, ButtonRole::Primary)
+    }
+
+    pub fn cancel(label: impl Into<SharedString>) -> Self {
+        Self::new(label, ButtonRole::Cancel)
+    }
+
+    pub fn secondary(label: impl Into<SharedString>) -> Self {
+        Self::new(label, ButtonRole::Secondary)
+    }
+
+    fn new(label: impl Into<SharedString>, role: ButtonRole) -> Self {
+        Self {
+            label: label.into(),
+            role,
+            badge: None,
+            hold_to_confirm: false,
+        }
+    }
+
+    pub fn badge(mut self, count: usize) -> Self {
+        self.badge = Some(count);
+        self
+    }
+
+    pub fn hold_to_confirm(mut self) -> Self {
+        self.hold_to_confirm = true;
+        self
+    }
+}
+
+// How long a hold_to_confirm button has to be held
+const HOLD_TO_CONFIRM: Duration = Duration::from_secs(1);
+
+// How long the next step of a flow takes to grow or shrink from the size
+// of the one before
+const STEP_TRANSITION: Duration = Duration::from_millis(250);
+
+// ======================================================================
+// FORM FIELDS
+// ======================================================================
+
+// What kind of input a field gets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    // A single-line text input
+    Text,
+    // Color swatches; the value is "#RRGGBB"
+    Color,
+    // Tags typed as chips; the value is the tags joined with commas
+    Tags,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormField {
+    // Key of the value in DialogResult::values
+    pub id: SharedString,
+    pub label: SharedString,
+    pub kind: FieldKind,
+    pub placeholder: SharedString,
+    pub default_value: SharedString,
+    // Tag fields only: tags offered while typing, and the most allowed
+    pub suggestions: Vec<SharedString>,
+    pub max_tags: Option<usize>,
+}
+
+impl FormField {
+    // Height of the label plus the input, for sizing the window
+    fn height(&self) -> f32 {
+        match self.kind {
+            FieldKind::Text => 56.0,
+            FieldKind::Color => 120.0,
+            FieldKind::Tags => 100.0,
+        }
+    }
+
+    pub fn text(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self::new(id, label, FieldKind::Text)
+    }
+
+    // The default value, if any, is a "#RRGGBB" color
+    pub fn color(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self::new(id, label, FieldKind::Color)
+    }
+
+    // The default value, if any, is a comma-separated list of tags
+    pub fn tags(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self::new(id, label, FieldKind::Tags)
+    }
+
+    fn new(id: impl Into<SharedString>, label: impl Into<SharedString>, kind: FieldKind) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            kind,
+            placeholder: SharedString::default(),
+            default_value: SharedString::default(),
+            suggestions: Vec::new(),
+            max_tags: None,
+        }
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn default_value(mut self, value: impl Into<SharedString>) -> Self {
+        self.default_value = value.into();
+        self
+    }
+
+    pub fn suggestions(
+        mut self,
+        suggestions: impl IntoIterator<Item = impl Into<SharedString>>,
+    ) -> Self {
+        self.suggestions = suggestions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn max_tags(mut self, max_tags: usize) -> Self {
+        self.max_tags = Some(max_tags);
+        self
+    }
+}
+
+// ======================================================================
+// LISTS
+// ======================================================================
+// The rows of a list dialog. The result reports the chosen rows twice:
+// values["selected"] holds their indices ("0,3,4") and values["selection"]
+// their text, one per line. A reorderable list also reports values["order"],
+// the original indices in the order the user left them ("2,0,1").
+//
+//     Dialog::list("Which files?", ListItems::new(files).multi_select())
+//     Dialog::list("Rank these", ListItems::new(tasks).reorderable())
+//
+// For long lists, ListItems::lazy() fetches the rows from a RowProvider as
+// they scroll into view. values["selection"] then only has the selected
+// rows that were loaded.
+//
+//     Dialog::list("Pick a customer", ListItems::lazy(CustomerTable::open(db)?))
+
+#[derive(Clone)]
+pub struct ListItems {
+    pub rows: Vec<SharedString>,
+    // Where rows come from instead of `rows`, for lazy lists
+    pub provider: Option<Rc<dyn RowProvider>>,
+    pub multi_select: bool,
+    pub reorderable: bool,
+    // Rows selected when the dialog opens
+    pub selected: Vec<usize>,
+}
+
+impl ListItems {
+    pub fn new(rows: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        Self {
+            rows: rows.into_iter().map(Into::into).collect(),
+            provider: None,
+            multi_select: false,
+            reorderable: false,
+            selected: Vec::new(),
+        }
+    }
+
+    // Rows fetched a page at a time from `provider`
+    pub fn lazy(provider: impl RowProvider) -> Self {
+        Self {
+            provider: Some(Rc::new(provider)),
+            ..Self::new(Vec::<SharedString>::new())
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        match &self.provider {
+            Some(provider) => provider.row_count(),
+            None => self.rows.len(),
+        }
+    }
+
+    // Checkboxes, a select-all header and range selection
+    pub fn multi_select(mut self) -> Self {
+        self.multi_select = true;
+        self
+    }
+
+    // Drag handles (and Alt-Up/Alt-Down) to put the rows in order
+    pub fn reorderable(mut self) -> Self {
+        self.reorderable = true;
+        self
+    }
+
+    pub fn selected(mut self, rows: impl IntoIterator<Item = usize>) -> Self {
+        self.selected = rows.into_iter().collect();
+        self
+    }
+}
+
+// ======================================================================
+// PROGRESS UPDATES
+// ======================================================================
+// A progress dialog is driven from the outside: whoever does the work
+// keeps the sending half of a channel and reports through it.
+//
+//     let (progress, updates) = progress_channel();
+//     Dialog::progress("Copying files…", updates).show(cx, ...);
+//     progress.unbounded_send(ProgressUpdate::Fraction(0.5)).ok();
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgressUpdate {
+    // How far along the work is, from 0.0 to 1.0
+    Fraction(f32),
+    // Replace the status line under the bar
+    Status(SharedString),
+    // The work is done: the dialog closes with Outcome::Completed
+    Finished,
+}
+
+pub type ProgressSender = mpsc::UnboundedSender<ProgressUpdate>;
+pub type ProgressReceiver = mpsc::UnboundedReceiver<ProgressUpdate>;
+
+pub fn progress_channel() -> (ProgressSender, ProgressReceiver) {
+    mpsc::unbounded()
+}
+
+// ======================================================================
+// LOG LINES
+// ======================================================================
+// A log dialog shows text that arrives over time, such as a command's
+// output. Like progress, it is fed through a channel, one line per
+// message. Unlike progress, it stays open when the sender goes away so
+// the user can read the output.
+
+pub type LogSender = mpsc::UnboundedSender<SharedString>;
+pub type LogReceiver = mpsc::UnboundedReceiver<SharedString>;
+
+pub fn log_channel() -> (LogSender, LogReceiver) {
+    mpsc::unbounded()
+}
+
+// ======================================================================
+// CUSTOM CONTENT
+// ======================================================================
+// Presets (print, about, ...) put their own component between the message
+// and the buttons. The component implements DialogContent, and can emit
+// ContentEvents to close the dialog by itself (say, on double-click).
+//
+//     Dialog::custom("Pick a color", |_window, cx| cx.new(|cx| Swatches::new(cx)))
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContentEvent {
+    // Act as if the primary button was pressed (or, with no primary
+    // button, resolve as Completed)
+    Confirm,
+    // Act as if ESC was pressed
+    Dismiss,
+}
+
+pub trait DialogContent: Render + EventEmitter<ContentEvent> {
+    // Values reported in DialogResult::values when the dialog resolves
+    fn values(&self, _cx: &App) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    // Whether the primary button may resolve the dialog right now
+    // (e.g. false while a field holds an invalid value)
+    fn can_confirm(&self, _cx: &App) -> bool {
+        true
+    }
+
+    // What gets keyboard focus when the dialog opens
+    fn initial_focus(&self, _cx: &App) -> Option<FocusHandle> {
+        None
+    }
+}
+
+// DialogBox holds content of any type through this object-safe wrapper
+trait AnyDialogContent {
+    fn view(&self) -> AnyView;
+    fn values(&self, cx: &App) -> BTreeMap<String, String>;
+    fn can_confirm(&self, cx: &App) -> bool;
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle>;
+}
+
+impl<T: DialogContent> AnyDialogContent for Entity<T> {
+    fn view(&self) -> AnyView {
+        self.clone().into()
+    }
+
+    fn values(&self, cx: &App) -> BTreeMap<String, String> {
+        self.read(cx).values(cx)
+    }
+
+    fn can_confirm(&self, cx: &App) -> bool {
+        self.read(cx).can_confirm(cx)
+    }
+
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle> {
+        self.read(cx).initial_focus(cx)
+    }
+}
+
+// Creates the content entity inside the dialog window, and subscribes the
+// dialog to its events. Opaque outside this module.
+pub struct ContentBuilder(Box<BuildContent>);
+
+type BuildContent = dyn FnOnce(&mut Window, &mut Context<DialogBox>) -> BuiltContent;
+type BuiltContent = (Box<dyn AnyDialogContent>, Subscription);
+
+// ======================================================================
+// DIALOG DESCRIPTION
+// ======================================================================
+
+pub enum DialogKind {
+    // Just the message and the buttons
+    Question,
+    // A labelled text input per field, below the message
+    Form(Vec<FormField>),
+    // A progress bar fed by the receiver
+    Progress(ProgressReceiver),
+    // A scrolling monospace text area fed by the receiver
+    Log(LogReceiver),
+    // Rows to pick one or several of
+    List(ListItems),
+    // A component supplied by the caller, see DialogContent
+    Custom(ContentBuilder),
+}
+
+pub struct Dialog {
+    title: Option<SharedString>,
+    message: SharedString,
+    kind: DialogKind,
+    buttons: Vec<DialogButton>,
+    size: Option<Size<Pixels>>,
+    timeout: Option<Duration>,
+    max_age: Option<(Duration, Expiry)>,
+    status_bar: Option<Entity<StatusBar>>,
+    capture: Option<(Capture, CaptureCallback)>,
+    sensitive: bool,
+    session: Option<SessionRecord>,
+    // Dialog::suppressible()'s key
+    suppress_key: Option<String>,
+    escape: EscapePolicy,
+    answer_keys: bool,
+    collapsible: bool,
+    audit: Option<(String, Box<dyn AuditSink>)>,
+    theme: Option<ThemeChoice>,
+    // How far from centered the window opens
+    offset: Point<Pixels>,
+    // The size of the flow step before this one
+    step_from: Option<Size<Pixels>>,
+    // Read by the manager, which shows higher priorities first
+    pub(crate) priority: Priority,
+}
+
+type CaptureCallback = Box<dyn FnOnce(Result<(), String>, &mut App)>;
+
+// How long to wait after opening before a screenshot, so the window is
+// on screen and fully drawn
+const CAPTURE_DELAY: Duration = Duration::from_millis(400);
+
+impl Dialog {
+    // A message with Cancel and Ok buttons
+    pub fn question(message: impl Into<SharedString>) -> Self {
+        Self::new(message, DialogKind::Question)
+            .buttons([DialogButton::cancel("Cancel"), DialogButton::primary("Ok")])
+    }
+
+    // A message followed by one text input per field
+    pub fn form(
+        message: impl Into<SharedString>,
+        fields: impl IntoIterator<Item = FormField>,
+    ) -> Self {
+        Self::new(message, DialogKind::Form(fields.into_iter().collect()))
+            .buttons([DialogButton::cancel("Cancel"), DialogButton::primary("Ok")])
+    }
+
+    // A message with a progress bar, closed when the updates say Finished
+    // (or the sender is dropped)
+    pub fn progress(message: impl Into<SharedString>, updates: ProgressReceiver) -> Self {
+        Self::new(message, DialogKind::Progress(updates)).buttons([DialogButton::cancel("Cancel")])
+    }
+
+    // A message above a scrolling text area that lines are appended to
+    pub fn log(message: impl Into<SharedString>, lines: LogReceiver) -> Self {
+        Self::new(message, DialogKind::Log(lines)).buttons([DialogButton::primary("Close")])
+    }
+
+    // A message above a list of rows to choose from
+    pub fn list(message: impl Into<SharedString>, items: ListItems) -> Self {
+        Self::new(message, DialogKind::List(items))
+            .buttons([DialogButton::cancel("Cancel"), DialogButton::primary("Ok")])
+    }
+
+    // A message above a custom component, with Cancel and Ok buttons.
+    // `build` runs when the window opens.
+    pub fn custom<T: DialogContent>(
+        message: impl Into<SharedString>,
+        build: impl FnOnce(&mut Window, &mut App) -> Entity<T> + 'static,
+    ) -> Self {
+        let builder = ContentBuilder(Box::new(move |window, cx| {
+            let content = build(window, cx);
+            let subscription = cx.subscribe_in(&content, window, |this, _, event, window, cx| {
+                this.on_content_event(event, window, cx)
+            });
+            (Box::new(content), subscription)
+        }));
+        Self::new(message, DialogKind::Custom(builder))
+            .buttons([DialogButton::cancel("Cancel"), DialogButton::primary("Ok")])
+    }
+
+    fn new(message: impl Into<SharedString>, kind: DialogKind) -> Self {
+        Self {
+            title: None,
+            message: message.into(),
+            kind,
+            buttons: Vec::new(),
+            size: None,
+            timeout: None,
+            max_age: None,
+            status_bar: None,
+            capture: None,
+            sensitive: false,
+            session: None,
+            suppress_key: None,
+            escape: EscapePolicy::Dismiss,
+            answer_keys: false,
+            collapsible: false,
+            audit: None,
+            theme: None,
+            offset: Point::default(),
+            step_from: None,
+            priority: Priority::Normal,
+        }
+    }
+
+    // Text shown in the titlebar next to the traffic lights
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    // Buttons from left to right. By macOS convention the primary action
+    // goes last, on the right.
+    pub fn buttons(mut self, buttons: impl IntoIterator<Item = DialogButton>) -> Self {
+        self.buttons = buttons.into_iter().collect();
+        self
+    }
+
+    // Override the window size computed from the dialog's content
+    pub fn size(mut self, size: Size<Pixels>) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    // Close the dialog with Outcome::TimedOut if nobody answers in time
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    // Past this age an unanswered dialog expires, for prompts about
+    // something that won't matter any more by then. Unlike the timeout,
+    // it doesn't stretch on battery.
+    pub fn max_age(mut self, age: Duration, expiry: Expiry) -> Self {
+        self.max_age = Some((age, expiry));
+        self
+    }
+
+    // A status bar along the bottom of the window. Keep the entity to
+    // update it while the dialog is open.
+    pub fn status_bar(mut self, status_bar: Entity<StatusBar>) -> Self {
+        self.status_bar = Some(status_bar);
+        self
+    }
+
+    // What ESC does (by default, dismiss the dialog)
+    pub fn on_escape(mut self, policy: EscapePolicy) -> Self {
+        self.escape = policy;
+        self
+    }
+
+    // Question dialogs only: answer with a single key, shown on each
+    // button. Y and N for a yes-or-no question (one primary and one
+    // cancel button), 1 to 9 in order otherwise. Hold-to-confirm buttons
+    // get no key, as the point of them is not to be pressed in passing.
+    pub fn answer_keys(mut self) -> Self {
+        self.answer_keys = true;
+        self
+    }
+
+    // Progress dialogs only: the yellow titlebar button folds the dialog
+    // away into a small pill in the corner of the screen (see pill.rs)
+    pub fn collapsible(mut self) -> Self {
+        self.collapsible = true;
+        self
+    }
+
+    // Light or dark whatever the theme setting says (see theme.rs)
+    pub fn theme(mut self, theme: ThemeChoice) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub(crate) fn offset(mut self, offset: Point<Pixels>) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    // Open as the next step after a dialog of `size`, changing size from
+    // that one's instead of appearing at its own (see flow.rs)
+    pub(crate) fn step_from(mut self, size: Option<Size<Pixels>>) -> Self {
+        self.step_from = size;
+        self
+    }
+
+    // Take a screenshot of the dialog once it's on screen (see
+    // capture.rs). on_done hears whether it worked.
+    pub fn capture(
+        mut self,
+        capture: Capture,
+        on_done: impl FnOnce(Result<(), String>, &mut App) + 'static,
+    ) -> Self {
+        self.capture = Some((capture, Box::new(on_done)));
+        self
+    }
+
+    // Keep the window out of screenshots and screen sharing where the
+    // system allows it (see privacy.rs), for password prompts and the
+    // like. A shield in the titlebar shows whether it worked.
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
+    // Leave a record while the dialog is open, so it can be offered again
+    // if the app dies before it's answered (see session.rs). `template`
+    // and `params` are whatever the app needs to build it again.
+    pub fn restorable(
+        mut self,
+        template: impl Into<String>,
+        params: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.session = Some(SessionRecord::new(template, params));
+        self
+    }
+
+    // Offer a "Don't show this again" checkbox. Once the user ticks it
+    // and confirms, the dialog isn't shown again: show() returns
+    // DialogError::Suppressed instead, which the app can take as the same
+    // answer. `key` names the dialog in the settings (see settings.rs).
+    pub fn suppressible(mut self, key: impl Into<String>) -> Self {
+        self.suppress_key = Some(key.into());
+        self
+    }
+
+    // Write a record of how the dialog was answered to `sink` (see
+    // audit.rs). `id` names the dialog in the log.
+    pub fn audit(mut self, id: impl Into<String>, sink: impl AuditSink + 'static) -> Self {
+        self.audit = Some((id.into(), Box::new(sink)));
+        self
+    }
+
+    // How urgent the dialog is, when shown through the manager (see
+    // manager.rs): a higher priority hides lower ones until it's answered
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn message(&self) -> &SharedString {
+        &self.message
+    }
+
+    pub fn kind(&self) -> &DialogKind {
+        &self.kind
+    }
+
+    // Show the dialog over a backdrop. on_resolve runs once, after the
+    // user answers; both windows are closed right after it returns. If
+    // the dialog can't be shown, on_resolve never runs. The handle lets
+    // the app change the dialog while it's open (see DialogHandle).
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        self.validate()?;
+        self.check_suppressed(cx)?;
+        let backdrop = open_backdrop(cx)?;
+        match self.open(backdrop, cx, on_resolve) {
+            Ok(window) => Ok(DialogHandle { window }),
+            Err(err) => {
+                if let Some(backdrop) = backdrop {
+                    close_backdrop(backdrop, cx);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    // Mistakes in the description that would otherwise show up as a
+    // broken dialog (or a result the caller can't tell apart). show()
+    // checks for them too.
+    pub fn validate(&self) -> Result<(), DialogError> {
+        if let DialogKind::Form(fields) = &self.kind {
+            for (index, field) in fields.iter().enumerate() {
+                if field.id.is_empty() {
+                    return Err(DialogError::InvalidSpec(format!(
+                        "form field \"{}\" has no id",
+                        field.label
+                    )));
+                }
+                if fields[..index].iter().any(|other| other.id == field.id) {
+                    return Err(DialogError::InvalidSpec(format!(
+                        "two form fields have the id \"{}\"",
+                        field.id
+                    )));
+                }
+            }
+        }
+        for (index, button) in self.buttons.iter().enumerate() {
+            if self.buttons[..index]
+                .iter()
+                .any(|other| other.label == button.label)
+            {
+                return Err(DialogError::InvalidSpec(format!(
+                    "two buttons are labelled \"{}\"",
+                    button.label
+                )));
+            }
+        }
+        if let Some(size) = self.size {
+            if size.width <= px(0.0) || size.height <= px(0.0) {
+                return Err(DialogError::InvalidSpec(format!(
+                    "the size {size:?} is empty"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_suppressed(&self, cx: &App) -> Result<(), DialogError> {
+        match &self.suppress_key {
+            Some(key) if settings::get(cx).is_suppressed(key) => Err(DialogError::Suppressed),
+            _ => Ok(()),
+        }
+    }
+
+    // What identical requests have in common (see manager.rs), or None
+    // for dialogs fed by their requester, which are never identical
+    pub(crate) fn dedup_key(&self) -> Option<String> {
+        let body = match &self.kind {
+            DialogKind::Question => "question".to_string(),
+            DialogKind::Form(fields) => format!("form {fields:?}"),
+            DialogKind::List(items) if items.provider.is_none() => format!(
+                "list {:?} {} {:?}",
+                items.rows, items.multi_select, items.selected
+            ),
+            _ => return None,
+        };
+        let buttons: Vec<_> = self.buttons.iter().map(|button| &button.label).collect();
+        Some(format!(
+            "{:?} {:?} {buttons:?} {body}",
+            self.title, self.message
+        ))
+    }
+
+    // The default window size for this kind of dialog
+    fn default_size(&self) -> Size<Pixels> {
+        let dialog_width = px(460.0); // Dialog width in pixels
+        let dialog_height = match &self.kind {
+            DialogKind::Question => px(180.0),
+            // Each field adds a label and an input
+            DialogKind::Form(fields) => {
+                px(180.0 + fields.iter().map(FormField::height).sum::<f32>())
+            }
+            DialogKind::Progress(_) => px(200.0),
+            DialogKind::Log(_) => return size(px(560.0), px(380.0)),
+            // Room for up to ten rows, plus the header when multi-selecting
+            DialogKind::List(items) => {
+                let rows = items.row_count().clamp(3, 10) + usize::from(items.multi_select);
+                px(200.0 + 28.0 * rows as f32)
+            }
+            DialogKind::Custom(_) => px(320.0),
+        };
+        size(dialog_width, dialog_height)
+    }
+
+    // The default size grows with the text, so bigger text still fits
+    pub(crate) fn window_size(&self, cx: &App) -> Size<Pixels> {
+        self.size.unwrap_or_else(|| {
+            let scale = layout::text_scale(cx);
+            self.default_size().map(|length| length * scale)
+        })
+    }
+
+    // ==================================================
+    // CREATE DIALOG WINDOW
+    // ==================================================
+    // Opens just the dialog window. When `backdrop` is given, it is closed
+    // together with the dialog; flows pass None and manage their own.
+
+    pub(crate) fn open(
+        self,
+        backdrop: Option<WindowHandle<Backdrop>>,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<WindowHandle<DialogBox>, DialogError> {
+        self.validate()?;
+        self.check_suppressed(cx)?;
+        let display = primary_display(cx)?;
+
+        // Centered in the part of the screen not taken by the menu bar,
+        // Dock or taskbar, so it never ends up behind one
+        let dialog_size = self.window_size(cx);
+        // A step that changes size does so inside a window big enough for
+        // both, as windows can't move once open. When it shrinks, the
+        // window shrinks to match once it's done.
+        let step_sizes = self
+            .step_from
+            .filter(|from| *from != dialog_size && !motion::reduced_motion(cx))
+            .map(|from| (from, dialog_size));
+        let window_size = match step_sizes {
+            Some((from, to)) => size(from.width.max(to.width), from.height.max(to.height)),
+            None => dialog_size,
+        };
+        let mut dialog_bounds = screen::centered(display.as_ref(), window_size);
+        dialog_bounds.origin += self.offset;
+
+        let window = cx
+            .open_window(
+                WindowOptions {
+                    // Position and size: centered on screen
+                    window_bounds: Some(WindowBounds::Windowed(dialog_bounds)),
+
+                    titlebar: None, // No OS titlebar (we draw our own)
+                    focus: true,    // This window should have keyboard focus
+                    show: true,     // Make visible immediately
+
+                    // PopUp windows float above other windows
+                    kind: WindowKind::PopUp,
+                    is_movable: false,              // User can't drag the dialog
+                    display_id: Some(display.id()), // Show on primary display
+
+                    // Transparent background allows our rounded corners and
+                    // shadow to render properly
+                    window_background: WindowBackgroundAppearance::Transparent,
+
+                    ..Default::default() // Default values for other options
+                },
+                // Create the DialogBox component
+                |window, cx| {
+                    if let Some(choice) = self.theme {
+                        theme::set_window_theme(window, choice, cx);
+                    }
+                    cx.new(|cx| {
+                        let mut dialog =
+                            DialogBox::new(self, backdrop, Box::new(on_resolve), window, cx);
+                        dialog.step_sizes = step_sizes;
+                        dialog.shrink_after_step(window, cx);
+                        dialog
+                    })
+                },
+            )
+            .map_err(|err| DialogError::WindowCreation(err.to_string()))?;
+        if let Some(backdrop) = backdrop {
+            keep_above(backdrop, window.into(), cx);
+        }
+        Ok(window)
+    }
+}
+
+// ======================================================================
+// DIALOG HANDLE
+// ======================================================================
+// show() returns a handle to the open dialog, for when what it says
+// depends on something the app is still finding out:
+//
+//     let dialog = Dialog::question("Connecting…").buttons([]).show(cx, ...)?;
+//     // ...later, once connected
+//     dialog.update_message("Connected", cx);
+//     dialog.set_buttons([DialogButton::primary("Continue")], cx);
+//
+// or close it when the question answers itself (the file it asks about
+// was deleted elsewhere, say). on_resolve runs as if the user had
+// answered. Calls on a dialog that's already closed do nothing.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DialogHandle {
+    window: WindowHandle<DialogBox>,
+}
+
+impl DialogHandle {
+    pub(crate) fn new(window: WindowHandle<DialogBox>) -> Self {
+        Self { window }
+    }
+
+    pub fn update_message(&self, message: impl Into<SharedString>, cx: &mut App) {
+        let message = message.into();
+        self.window
+            .update(cx, |this, _, cx| {
+                this.message = message;
+                this.message_selection = None;
+                cx.notify();
+            })
+            .ok();
+    }
+
+    // Progress dialogs only: the same updates their channel carries, so
+    // Finished closes the dialog with Outcome::Completed
+    pub fn set_progress(&self, update: ProgressUpdate, cx: &mut App) {
+        self.window
+            .update(cx, |this, window, cx| match update {
+                ProgressUpdate::Finished => this.resolve(Outcome::Completed, window, cx),
+                update => this.apply_progress(update, cx),
+            })
+            .ok();
+    }
+
+    pub fn set_buttons(&self, buttons: impl IntoIterator<Item = DialogButton>, cx: &mut App) {
+        let buttons = buttons.into_iter().collect();
+        self.window
+            .update(cx, |this, _, cx| {
+                this.buttons = buttons;
+                cx.notify();
+            })
+            .ok();
+    }
+
+    // Close the dialog, reporting `outcome` with whatever values it
+    // holds at this point
+    pub fn close(&self, outcome: Outcome, cx: &mut App) {
+        self.window
+            .update(cx, |this, window, cx| this.resolve(outcome, window, cx))
+            .ok();
+    }
+
+    // Scroll a long form to the field holding `focus`
+    pub fn scroll_into_view(&self, focus: &FocusHandle, cx: &mut App) {
+        self.window
+            .update(cx, |this, window, cx| {
+                this.scroll_into_view(focus, window, cx)
+            })
+            .ok();
+    }
+
+    // Hide the dialog, or show it again as it was. Shown again, it takes
+    // the focus back.
+    pub(crate) fn set_preempted(&self, preempted: bool, cx: &mut App) {
+        self.window
+            .update(cx, |this, window, cx| {
+                this.set_preempted(preempted, window, cx)
+            })
+            .ok();
+    }
+
+    pub(crate) fn activate(&self, cx: &mut App) {
+        self.window
+            .update(cx, |_, window, _| window.activate_window())
+            .ok();
+    }
+
+    // Collapsible progress dialogs only: fold into the pill, and back
+    pub fn collapse(&self, cx: &mut App) {
+        self.window
+            .update(cx, |this, window, cx| this.collapse(window, cx))
+            .ok();
+    }
+
+    pub fn expand(&self, cx: &mut App) {
+        self.window
+            .update(cx, |this, window, cx| this.expand(window, cx))
+            .ok();
+    }
+
+    // False once the dialog has been answered or closed
+    pub fn is_open(&self, cx: &App) -> bool {
+        self.window
+            .read(cx)
+            .is_ok_and(|this| this.on_resolve.is_some())
+    }
+
+    // The dialog's component, to subscribe to its DialogEvents. None once
+    // the window is gone.
+    pub fn entity(&self, cx: &App) -> Option<Entity<DialogBox>> {
+        self.window.entity(cx).ok()
+    }
+}
+
+// ======================================================================
+// DIALOG EVENTS
+// ======================================================================
+// What happens to a dialog while it's open, for analytics, sounds or
+// anything else that wants to follow along without being the one that
+// answers it:
+//
+//     let dialog = Dialog::question("Delete the file?").show(cx, ...)?;
+//     if let Some(entity) = dialog.entity(cx) {
+//         cx.subscribe(&entity, |_, event, _| match event {
+//             DialogEvent::ButtonHovered(label) => play_tick(label),
+//             _ => {}
+//         })
+//         .detach();
+//     }
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DialogEvent {
+    // The window is open, right after show() returns
+    Opened,
+    // The window gained (true) or lost (false) keyboard focus
+    FocusChanged(bool),
+    // The pointer moved onto the button with this label
+    ButtonHovered(SharedString),
+    // The dialog was answered; delivered right after on_resolve returns
+    Resolved(DialogResult),
+}
+
+impl EventEmitter<DialogEvent> for DialogBox {}
+
+// ======================================================================
+// DIALOG BOX COMPONENT
+// ======================================================================
+// The component that displays content and interactive buttons.
+
+pub(crate) type ResolveCallback = Box<dyn FnOnce(DialogResult, &mut App)>;
+
+// The input behind a form field
+enum FieldInput {
+    Text(Entity<TextInput>),
+    Color(Entity<ColorSwatches>),
+    Tags(Entity<TagInput>),
+}
+
+impl FieldInput {
+    fn new(field: &FormField, cx: &mut App) -> Self {
+        match field.kind {
+            FieldKind::Text => FieldInput::Text(cx.new(|cx| {
+                let mut input = TextInput::new(cx).placeholder(field.placeholder.clone());
+                input.set_text(field.default_value.clone(), cx);
+                input
+            })),
+            FieldKind::Color => FieldInput::Color(cx.new(|cx| {
+                let mut swatches = ColorSwatches::new(cx);
+                if let Some(color) = parse_hex(&field.default_value) {
+                    swatches.set_color(color, cx);
+                }
+                swatches
+            })),
+            FieldKind::Tags => FieldInput::Tags(cx.new(|cx| {
+                let mut input = TagInput::new(cx)
+                    .suggestions(field.suggestions.clone())
+                    .max_tags(field.max_tags);
+                let tags = field
+                    .default_value
+                    .split(',')
+                    .map(|tag| tag.trim().to_string().into());
+                input.set_tags(tags, cx);
+                input
+            })),
+        }
+    }
+
+    fn value(&self, cx: &App) -> String {
+        match self {
+            FieldInput::Text(input) => input.read(cx).text().to_string(),
+            FieldInput::Color(swatches) => swatches.read(cx).hex(),
+            FieldInput::Tags(input) => input.read(cx).tags().join(","),
+        }
+    }
+
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match self {
+            FieldInput::Text(input) => input.focus_handle(cx),
+            FieldInput::Color(swatches) => swatches.focus_handle(cx),
+            FieldInput::Tags(input) => input.focus_handle(cx),
+        }
+    }
+
+    fn element(&self) -> AnyElement {
+        match self {
+            FieldInput::Text(input) => input.clone().into_any_element(),
+            FieldInput::Color(swatches) => swatches.clone().into_any_element(),
+            FieldInput::Tags(input) => input.clone().into_any_element(),
+        }
+    }
+}
+
+// The part between the message and the buttons
+enum DialogBody {
+    Question,
+    Form {
+        inputs: Vec<(FormField, FieldInput)>,
+        // Long forms scroll, following the focus as Tab moves it
+        scroller: Scroller,
+    },
+    Progress {
+        fraction: f32,
+        status: Option<SharedString>,
+        // Keeps the task reading progress updates alive
+        _updates: Task<()>,
+    },
+    Log {
+        lines: Vec<SharedString>,
+        scroller: Scroller,
+        _updates: Task<()>,
+    },
+    List {
+        list: Entity<ListView>,
+        _events: Subscription,
+    },
+    Custom {
+        content: Box<dyn AnyDialogContent>,
+        _events: Subscription,
+    },
+}
+
+pub struct DialogBox {
+    title: Option<SharedString>,
+    message: SharedString,
+    buttons: Vec<DialogButton>,
+    body: DialogBody,
+    status_bar: Option<Entity<StatusBar>>,
+    focus_handle: FocusHandle,
+    backdrop: Option<WindowHandle<Backdrop>>,
+    // Taken when the dialog resolves, so it can only resolve once
+    on_resolve: Option<ResolveCallback>,
+    // Usage metrics, reported in the result
+    opened_at: Instant,
+    metrics: DialogMetrics,
+    // For sensitive dialogs, whether they're hidden from screen capture
+    capture_protection: Option<bool>,
+    // The id of our session record, for restorable dialogs
+    session_id: Option<String>,
+    // The id and sink of audited dialogs
+    audit: Option<(String, Box<dyn AuditSink>)>,
+    // For suppressible dialogs: the key, and whether "Don't show this
+    // again" is ticked
+    suppress: Option<(String, bool)>,
+    escape: EscapePolicy,
+    answer_keys: bool,
+    // While waiting for the second ESC under RequireConfirm: the task
+    // that stops waiting
+    escape_armed: Option<Task<()>>,
+    // The message as laid out in the last frame, and the part of it
+    // selected by double or triple clicking, for copying
+    message_layout: Option<TextLayout>,
+    message_selection: Option<Range<usize>>,
+    message_clicks: ClickCounter,
+    // The hold_to_confirm button being held down, if any
+    hold: LongPress,
+    held_button: Option<SharedString>,
+    // Hidden while a more urgent dialog is answered (see manager.rs), and
+    // whether the dialog had a backdrop to bring back with it
+    preempted: Option<bool>,
+    // Past its max age, under Expiry::MarkStale
+    stale: bool,
+    collapsible: bool,
+    // Sizes to animate between, from the previous step of a flow to this
+    // dialog's own
+    step_sizes: Option<(Size<Pixels>, Size<Pixels>)>,
+    // While collapsed: the pill standing in for the dialog, and whether
+    // the dialog had a backdrop to bring back with it
+    pill: Option<(WindowHandle<ProgressPill>, bool)>,
+    // What was left of the timeout and the max age when the timers were
+    // last started; they stop while the dialog is preempted
+    timeout: Option<Duration>,
+    max_age: Option<(Duration, Expiry)>,
+    timers_started: (Instant, SystemTime),
+    _timeout: Option<Task<()>>,
+    _expiry: Option<Task<()>>,
+    _capture: Option<Task<()>>,
+    _activation: Subscription,
+    // Counted for testing::assert_no_entity_leaks()
+    _live: Live,
+}
+
+impl DialogBox {
+    fn new(
+        dialog: Dialog,
+        backdrop: Option<WindowHandle<Backdrop>>,
+        on_resolve: ResolveCallback,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        // Doubled on battery, where the laptop may have been set aside
+        let timeout = dialog.timeout.map(|timeout| {
+            if power::saving(cx) {
+                timeout * 2
+            } else {
+                timeout
+            }
+        });
+        let (timeout_task, expiry_task) = Self::timers(timeout, dialog.max_age, window, cx);
+
+        let session_id = dialog.session.map(|record| {
+            let id = session::next_id();
+            session::remember(&id, &record, cx);
+            id
+        });
+
+        let capture_protection = dialog
+            .sensitive
+            .then(|| privacy::exclude_from_capture(window));
+
+        let capture = match dialog.capture {
+            Some((_, on_done)) if dialog.sensitive => {
+                cx.defer(|cx| on_done(Err("the dialog is marked sensitive".to_string()), cx));
+                None
+            }
+            Some((capture, on_done)) => Some(cx.spawn_in(window, async move |this, cx| {
+                cx.background_executor().timer(CAPTURE_DELAY).await;
+                let Ok(shot) = this.update_in(cx, |_, window, cx| capture.take(window, cx)) else {
+                    return; // Answered before the screenshot was taken
+                };
+                let result = shot.await;
+                cx.update(|_, cx| on_done(result, cx)).ok();
+            })),
+            None => None,
+        };
+
+        // Subscribers get a chance to sign up before this goes out
+        cx.defer_in(window, |_, _, cx| cx.emit(DialogEvent::Opened));
+        let activation = cx.observe_window_activation(window, |_, window, cx| {
+            cx.emit(DialogEvent::FocusChanged(window.is_window_active()))
+        });
+
+        let body = match dialog.kind {
+            DialogKind::Question => {
+                window.focus(&focus_handle);
+                DialogBody::Question
+            }
+            DialogKind::Form(fields) => {
+                let inputs: Vec<_> = fields
+                    .into_iter()
+                    .map(|field| {
+                        let input = FieldInput::new(&field, cx);
+                        (field, input)
+                    })
+                    .collect();
+
+                // Start typing straight into the first field
+                match inputs.first() {
+                    Some((_, input)) => window.focus(&input.focus_handle(cx)),
+                    None => window.focus(&focus_handle),
+                }
+                DialogBody::Form {
+                    inputs,
+                    scroller: Scroller::new(ScrollHandle::new()),
+                }
+            }
+            DialogKind::Progress(mut updates) => {
+                window.focus(&focus_handle);
+
+                // Apply updates as they arrive. A closed channel counts as
+                // finished, so a worker that drops its sender doesn't leave
+                // the dialog hanging.
+                let task = cx.spawn_in(window, async move |this, cx| {
+                    while let Some(update) = updates.next().await {
+                        if update == ProgressUpdate::Finished {
+                            break;
+                        }
+                        let applied = this.update(cx, |this, cx| this.apply_progress(update, cx));
+                        if applied.is_err() {
+                            return; // The dialog is already gone
+                        }
+                    }
+                    this.update_in(cx, |this, window, cx| {
+                        this.resolve(Outcome::Completed, window, cx)
+                    })
+                    .ok();
+                });
+
+                DialogBody::Progress {
+                    fraction: 0.0,
+                    status: None,
+                    _updates: task,
+                }
+            }
+            DialogKind::Log(mut lines) => {
+                window.focus(&focus_handle);
+
+                let task = cx.spawn(async move |this, cx| {
+                    while let Some(line) = lines.next().await {
+                        let appended = this.update(cx, |this, cx| this.append_log_line(line, cx));
+                        if appended.is_err() {
+                            return; // The dialog is already gone
+                        }
+                    }
+                });
+
+                DialogBody::Log {
+                    lines: Vec::new(),
+                    scroller: Scroller::new(ScrollHandle::new()),
+                    _updates: task,
+                }
+            }
+            DialogKind::List(items) => {
+                let list = cx.new(|cx| {
+                    let list = match items.provider {
+                        Some(provider) => ListView::lazy(provider, items.multi_select, cx),
+                        None => ListView::new(items.rows, items.multi_select, cx),
+                    };
+                    let mut list = list.reorderable(items.reorderable);
+                    list.set_selected(items.selected, cx);
+                    list
+                });
+                window.focus(&list.focus_handle(cx));
+
+                // Double-clicking a row is a shortcut for selecting it and
+                // pressing the primary button
+                let events =
+                    cx.subscribe_in(&list, window, |this, list, _: &Activated, window, cx| {
+                        if !list.read(cx).multi_select() {
+                            this.press_primary(window, cx);
+                        }
+                    });
+                DialogBody::List {
+                    list,
+                    _events: events,
+                }
+            }
+            DialogKind::Custom(ContentBuilder(build)) => {
+                let (content, events) = build(window, cx);
+                match content.initial_focus(cx) {
+                    Some(handle) => window.focus(&handle),
+                    None => window.focus(&focus_handle),
+                }
+                DialogBody::Custom {
+                    content,
+                    _events: events,
+                }
+            }
+        };
+
+        Self {
+            title: dialog.title,
+            message: dialog.message,
+            buttons: dialog.buttons,
+            body,
+            status_bar: dialog.status_bar,
+            focus_handle,
+            backdrop,
+            on_resolve: Some(on_resolve),
+            opened_at: Instant::now(),
+            metrics: DialogMetrics::default(),
+            capture_protection,
+            session_id,
+            audit: dialog.audit,
+            suppress: dialog.suppress_key.map(|key| (key, false)),
+            escape: dialog.escape,
+            answer_keys: dialog.answer_keys,
+            escape_armed: None,
+            message_layout: None,
+            message_selection: None,
+            message_clicks: ClickCounter::default(),
+            hold: LongPress::default(),
+            held_button: None,
+            timeout,
+            max_age: dialog.max_age,
+            timers_started: (Instant::now(), SystemTime::now()),
+            _timeout: timeout_task,
+            _expiry: expiry_task,
+            _capture: capture,
+            preempted: None,
+            stale: false,
+            collapsible: dialog.collapsible,
+            step_sizes: None,
+            pill: None,
+            _activation: activation,
+            _live: Live::new(Kind::Dialog),
+        }
+    }
+
+    // Resolve on our own once `timeout` elapses, and age the dialog by
+    // `max_age`
+    fn timers(
+        timeout: Option<Duration>,
+        max_age: Option<(Duration, Expiry)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> (Option<Task<()>>, Option<Task<()>>) {
+        let timeout = timeout.map(|timeout| {
+            cx.spawn_in(window, async move |this, cx| {
+                cx.background_executor().timer(timeout).await;
+                this.update_in(cx, |this, window, cx| {
+                    this.resolve(Outcome::TimedOut, window, cx)
+                })
+                .ok();
+            })
+        });
+
+        let expiry = max_age.map(|(age, expiry)| {
+            let opened = SystemTime::now();
+            cx.spawn_in(window, async move |this, cx| {
+                loop {
+                    let elapsed = opened.elapsed().unwrap_or_default();
+                    if elapsed >= age {
+                        break;
+                    }
+                    let wait = (age - elapsed).min(EXPIRY_CHECK);
+                    cx.background_executor().timer(wait).await;
+                }
+                this.update_in(cx, |this, window, cx| {
+                    this.max_age = None;
+                    match expiry {
+                        Expiry::MarkStale => {
+                            this.stale = true;
+                            cx.notify();
+                        }
+                        Expiry::Resolve => this.resolve(Outcome::Expired, window, cx),
+                    }
+                })
+                .ok();
+            })
+        });
+        (timeout, expiry)
+    }
+
+    // Stop the timers, keeping what's left of them for resume_timers()
+    fn pause_timers(&mut self) {
+        let (instant, time) = self.timers_started;
+        let ran = instant.elapsed();
+        self.timeout = self.timeout.map(|timeout| timeout.saturating_sub(ran));
+        // By the wall clock, like the expiry timer
+        let aged = time.elapsed().unwrap_or_default();
+        self.max_age = self
+            .max_age
+            .map(|(age, expiry)| (age.saturating_sub(aged), expiry));
+        self._timeout = None;
+        self._expiry = None;
+    }
+
+    fn resume_timers(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        (self._timeout, self._expiry) = Self::timers(self.timeout, self.max_age, window, cx);
+        self.timers_started = (Instant::now(), SystemTime::now());
+    }
+
+    // Hidden for a more urgent dialog (see set_preempted)
+    pub(crate) fn is_preempted(&self) -> bool {
+        self.preempted.is_some()
+    }
+
+    // Hide the dialog for a more urgent one, or show it again as it was.
+    // While hidden its backdrop is closed (so it doesn't dim the urgent
+    // one, or keep raising us over it) and its timers stand still.
+    pub(crate) fn set_preempted(
+        &mut self,
+        preempted: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_preempted() == preempted {
+            return;
+        }
+        if preempted {
+            let backdrop = self.backdrop.take();
+            if let Some(backdrop) = backdrop {
+                close_backdrop(backdrop, cx);
+            }
+            self.preempted = Some(backdrop.is_some());
+            self.pause_timers();
+            cx.notify();
+            return;
+        }
+
+        let had_backdrop = self.preempted.take() == Some(true);
+        // Collapsed into its pill meanwhile: the backdrop comes back when
+        // it's expanded
+        if had_backdrop {
+            if let Some((_, with_backdrop)) = &mut self.pill {
+                *with_backdrop = true;
+            } else {
+                match open_backdrop(cx) {
+                    Ok(Some(backdrop)) => {
+                        keep_above(backdrop, window.window_handle(), cx);
+                        self.backdrop = Some(backdrop);
+                    }
+                    Ok(None) => {}
+                    Err(err) => log::error!("couldn't bring back the backdrop: {err}"),
+                }
+            }
+        }
+        self.resume_timers(window, cx);
+        if self.pill.is_none() {
+            window.activate_window();
+        }
+        cx.notify();
+    }
+
+    // How far along a progress dialog is
+    pub(crate) fn progress_fraction(&self) -> Option<f32> {
+        match &self.body {
+            DialogBody::Progress { fraction, .. } => Some(*fraction),
+            _ => None,
+        }
+    }
+
+    // Fold a collapsible progress dialog away into its pill, taking the
+    // backdrop along
+    pub(crate) fn collapse(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.collapsible || self.pill.is_some() || self.progress_fraction().is_none() {
+            return;
+        }
+        let Some(handle) = window.window_handle().downcast::<DialogBox>() else {
+            return;
+        };
+        match pill::open(handle, cx) {
+            Ok(pill) => {
+                let backdrop = self.backdrop.take();
+                if let Some(backdrop) = backdrop {
+                    close_backdrop(backdrop, cx);
+                }
+                self.pill = Some((pill, backdrop.is_some()));
+                cx.notify();
+            }
+            Err(err) => log::error!("couldn't collapse the dialog: {err}"),
+        }
+    }
+
+    // Bring a collapsed dialog back as it was
+    pub(crate) fn expand(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((pill, had_backdrop)) = self.pill.take() else {
+            return;
+        };
+        pill::close(pill, cx);
+        if had_backdrop {
+            match open_backdrop(cx) {
+                Ok(Some(backdrop)) => {
+                    keep_above(backdrop, window.window_handle(), cx);
+                    self.backdrop = Some(backdrop);
+                }
+                Ok(None) => {}
+                Err(err) => log::error!("couldn't bring back the backdrop: {err}"),
+            }
+        }
+        window.activate_window();
+        cx.notify();
+    }
+
+    fn apply_progress(&mut self, update: ProgressUpdate, cx: &mut Context<Self>) {
+        if let DialogBody::Progress {
+            fraction, status, ..
+        } = &mut self.body
+        {
+            match update {
+                ProgressUpdate::Fraction(value) => *fraction = value.clamp(0.0, 1.0),
+                ProgressUpdate::Status(text) => *status = Some(text),
+                ProgressUpdate::Finished => {}
+            }
+            cx.notify(); // Ask GPUI to re-render with the new state
+        }
+    }
+
+    fn append_log_line(&mut self, line: SharedString, cx: &mut Context<Self>) {
+        if let DialogBody::Log {
+            lines, scroller, ..
+        } = &mut self.body
+        {
+            lines.push(line);
+            // Follow the output, like `tail -f`
+            scroller.handle().scroll_to_bottom();
+            cx.notify();
+        }
+    }
+
+    // Current form values keyed by field id
+    fn values(&self, cx: &App) -> BTreeMap<String, String> {
+        match &self.body {
+            DialogBody::Form { inputs, .. } => inputs
+                .iter()
+                .map(|(field, input)| (field.id.to_string(), input.value(cx)))
+                .collect(),
+            DialogBody::List { list, .. } => {
+                let list = list.read(cx);
+                let selected: Vec<usize> = list.selected().collect();
+                let indices = selected.iter().map(usize::to_string);
+                let rows = selected
+                    .iter()
+                    .filter_map(|row| list.row(*row))
+                    .map(|row| row.to_string());
+                let mut values = BTreeMap::from([
+                    (
+                        "selected".to_string(),
+                        indices.collect::<Vec<_>>().join(","),
+                    ),
+                    ("selection".to_string(), rows.collect::<Vec<_>>().join("\n")),
+                ]);
+                if list.is_reorderable() {
+                    let order = list.order().iter().map(usize::to_string);
+                    values.insert("order".to_string(), order.collect::<Vec<_>>().join(","));
+                }
+                values
+            }
+            DialogBody::Custom { content, .. } => content.values(cx),
+            _ => BTreeMap::new(),
+        }
+    }
+
+    // Close the dialog and report the outcome. The callback runs before the
+    // windows are removed so it can open a follow-up dialog first; on Linux
+    // the app exits as soon as its last window closes.
+    fn resolve(&mut self, outcome: Outcome, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(on_resolve) = self.on_resolve.take() else {
+            return; // Already resolved
+        };
+
+        let metrics = DialogMetrics {
+            open_duration: self.opened_at.elapsed(),
+            timed_out: outcome == Outcome::TimedOut,
+            ..self.metrics.clone()
+        };
+        let result = DialogResult {
+            outcome,
+            values: self.values(cx),
+            metrics,
+        };
+        // Answered, so nothing to restore
+        if let Some(id) = self.session_id.take() {
+            session::forget(&id, cx);
+        }
+        if let Some((id, sink)) = self.audit.take() {
+            audit::record(sink.as_ref(), &id, &self.message, &result);
+        }
+        if let Some((key, true)) = self.suppress.take() {
+            if result.is_confirmed() {
+                settings::suppress(key, cx);
+            }
+        }
+        cx.emit(DialogEvent::Resolved(result.clone()));
+        // Removing the window drops us, and with us our subscribers, before
+        // the event goes out; hold on until it has
+        let this = cx.entity();
+        cx.defer(move |_| drop(this));
+        on_resolve(result, cx);
+
+        if let Some(backdrop) = self.backdrop.take() {
+            close_backdrop(backdrop, cx);
+        }
+        if let Some((pill, _)) = self.pill.take() {
+            pill::close(pill, cx);
+        }
+        window.remove_window();
+    }
+
+    fn press(&mut self, button: &DialogButton, window: &mut Window, cx: &mut Context<Self>) {
+        if button.role == ButtonRole::Primary && !self.can_confirm(cx) {
+            return;
+        }
+        if button.hold_to_confirm {
+            self.start_hold(button, window, cx);
+            return;
+        }
+        self.activate(button, window, cx);
+    }
+
+    // Press a hold_to_confirm button once it's been held long enough;
+    // release_hold() before then calls it off
+    fn start_hold(&mut self, button: &DialogButton, window: &mut Window, cx: &mut Context<Self>) {
+        if self.hold.is_pending() {
+            return;
+        }
+        let held = button.clone();
+        self.hold
+            .start(HOLD_TO_CONFIRM, window, cx, move |this, window, cx| {
+                this.held_button = None;
+                this.activate(&held, window, cx);
+            });
+        self.held_button = Some(button.label.clone());
+        cx.notify();
+    }
+
+    fn release_hold(&mut self, cx: &mut Context<Self>) {
+        if self.hold.cancel() {
+            self.held_button = None;
+            cx.notify();
+        }
+    }
+
+    fn activate(&mut self, button: &DialogButton, window: &mut Window, cx: &mut Context<Self>) {
+        let outcome = Outcome::Button {
+            label: button.label.clone(),
+            role: button.role,
+        };
+        self.resolve(outcome, window, cx);
+    }
+
+    fn on_content_event(
+        &mut self,
+        event: &ContentEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            // Without a primary button, confirming completes the dialog
+            ContentEvent::Confirm if !self.has_primary() => {
+                if self.can_confirm(cx) {
+                    self.resolve(Outcome::Completed, window, cx);
+                }
+            }
+            ContentEvent::Confirm => self.press_primary(window, cx),
+            ContentEvent::Dismiss => self.resolve(Outcome::Dismissed, window, cx),
+        }
+    }
+
+    // Custom content can hold back the primary action until it's valid
+    fn can_confirm(&self, cx: &App) -> bool {
+        if self.stale {
+            return false;
+        }
+        match &self.body {
+            DialogBody::Custom { content, .. } => content.can_confirm(cx),
+            // A single-selection list needs a row picked
+            DialogBody::List { list, .. } => {
+                let list = list.read(cx);
+                list.multi_select() || list.selected().next().is_some()
+            }
+            _ => true,
+        }
+    }
+
+    // A line of help above the buttons, when there's something the user
+    // is in the middle of
+    fn hint(&self) -> Option<SharedString> {
+        if self.stale {
+            Some("This prompt expired".into())
+        } else if let Some(label) = &self.held_button {
+            Some(format!("Keep holding “{label}”…").into())
+        } else if self.escape_armed.is_some() {
+            Some("Press Esc again to close".into())
+        } else {
+            None
+        }
+    }
+
+    fn has_primary(&self) -> bool {
+        self.buttons
+            .iter()
+            .any(|button| button.role == ButtonRole::Primary)
+    }
+
+    fn press_primary(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let primary = self
+            .buttons
+            .iter()
+            .find(|button| button.role == ButtonRole::Primary)
+            .cloned();
+        if let Some(button) = primary {
+            self.press(&button, window, cx);
+        }
+    }
+
+    // Interactions are observed in the capture phase, before any child
+    // handles (and possibly stops) the event
+    fn record_interaction(&mut self) {
+        if self.metrics.time_to_first_interaction.is_none() {
+            self.metrics.time_to_first_interaction = Some(self.opened_at.elapsed());
+        }
+    }
+
+    fn on_any_key_down(&mut self, _: &KeyDownEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.record_interaction();
+        self.metrics.used_keyboard = true;
+    }
+
+    // Any click also drops the message selection; a click on the message
+    // itself sets it again afterwards
+    fn on_any_mouse_down(&mut self, _: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.record_interaction();
+        self.metrics.used_mouse = true;
+        if self.message_selection.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    // Double click selects a word of the message, triple click its
+    // paragraph
+    fn on_message_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let click = self.message_clicks.down(event.position);
+        let Some(layout) = &self.message_layout else {
+            return;
+        };
+        let index = layout
+            .index_for_position(event.position)
+            .unwrap_or_else(|nearest| nearest);
+        self.message_selection = match click {
+            Click::Single => None,
+            Click::Double => Some(gesture::word_at(&self.message, index)),
+            Click::Triple => Some(gesture::paragraph_at(&self.message, index)),
+        };
+        cx.notify();
+    }
+
+    fn copy_message_selection(&self, cx: &mut App) -> bool {
+        let Some(selection) = self.message_selection.clone() else {
+            return false;
+        };
+        let text = self.message.get(selection).unwrap_or_default();
+        cx.write_to_clipboard(ClipboardItem::new_string(text.to_string()));
+        true
+    }
+
+    // Mouse event handler for the red close button
+    fn on_close_clicked(&mut self, _: &MouseUpEvent, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve(Outcome::Dismissed, window, cx);
+    }
+
+    // Scroll the form so the field holding `focus` is in view, as Tab
+    // does when it moves to a field that's scrolled out of sight
+    pub fn scroll_into_view(
+        &mut self,
+        focus: &FocusHandle,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let DialogBody::Form { inputs, scroller } = &mut self.body else {
+            return;
+        };
+        let field = inputs
+            .iter()
+            .position(|(_, input)| input.focus_handle(cx).contains(focus, window));
+        if let Some(bounds) = field.and_then(|field| scroller.handle().bounds_for_item(field)) {
+            scroller.reveal(bounds, cx);
+        }
+    }
+
+    // Keyboard event handler: ESC does what the escape policy says, Enter
+    // picks the primary button, Tab moves between fields, and Cmd/Ctrl-C
+    // copies the part of the message that's selected
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        match event.keystroke.key.as_str() {
+            "tab" => self.move_focus(!event.keystroke.modifiers.shift, window, cx),
+            "escape" => self.press_escape(window, cx),
+            // Holding Enter repeats; only the first press counts
+            "enter" if event.is_held => {}
+            "enter" => self.press_primary(window, cx),
+            "c" if event.keystroke.modifiers.secondary() && self.copy_message_selection(cx) => {
+                cx.stop_propagation()
+            }
+            key if !event.is_held && !event.keystroke.modifiers.modified() => {
+                let pressed = (0..self.buttons.len())
+                    .find(|&index| {
+                        self.answer_key(index, cx)
+                            .is_some_and(|answer| answer.eq_ignore_ascii_case(key))
+                    })
+                    .map(|index| self.buttons[index].clone());
+                if let Some(button) = pressed {
+                    self.press(&button, window, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The buttons' indices in the order they're laid out along the row,
+    // following the ButtonOrder setting
+    fn button_order(&self, cx: &App) -> Vec<usize> {
+        let (primary, rest): (Vec<_>, Vec<_>) = (0..self.buttons.len())
+            .partition(|&index| self.buttons[index].role == ButtonRole::Primary);
+        match settings::get(cx).button_order.resolve() {
+            // OK, then the other choices, then Cancel
+            ButtonOrder::AffirmativeFirst => {
+                let (cancel, others): (Vec<_>, Vec<_>) = rest
+                    .into_iter()
+                    .partition(|&index| self.buttons[index].role == ButtonRole::Cancel);
+                [primary, others, cancel].concat()
+            }
+            _ => [rest, primary].concat(),
+        }
+    }
+
+    // The key that answers with the button at `index`, with answer_keys.
+    // Numbers count the buttons from left to right.
+    fn answer_key(&self, index: usize, cx: &App) -> Option<SharedString> {
+        if !self.answer_keys || !matches!(self.body, DialogBody::Question) {
+            return None;
+        }
+        let button = self.buttons.get(index)?;
+        if button.hold_to_confirm {
+            return None;
+        }
+        let roles: Vec<_> = self.buttons.iter().map(|button| button.role).collect();
+        match roles.as_slice() {
+            [ButtonRole::Primary, ButtonRole::Cancel]
+            | [ButtonRole::Cancel, ButtonRole::Primary] => Some(
+                if button.role == ButtonRole::Primary {
+                    "Y"
+                } else {
+                    "N"
+                }
+                .into(),
+            ),
+            _ => {
+                let position = self.button_order(cx).iter().position(|&at| at == index)?;
+                (position < 9).then(|| (position + 1).to_string().into())
+            }
+        }
+    }
+
+    // Move the focus to the next (or previous) field. Past the last it
+    // wraps around to the first, so Tab never leaves the dialog.
+    fn move_focus(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if forward {
+            window.focus_next();
+        } else {
+            window.focus_prev();
+        }
+        match window.focused(cx) {
+            Some(focused) => self.scroll_into_view(&focused, window, cx),
+            // Nothing to tab to: keys keep coming to the dialog itself
+            None => window.focus(&self.focus_handle),
+        }
+    }
+
+    // The platform's keys for moving to the next pane or window (Ctrl-Tab,
+    // F6, Cmd-` on macOS) would take the focus out of a modal dialog.
+    // Caught before any field sees them, they move between the dialog's
+    // fields like Tab instead.
+    fn trap_focus(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = keystroke.modifiers;
+        let leaves = match keystroke.key.as_str() {
+            "tab" => modifiers.control,
+            "f6" => true,
+            "`" => modifiers.platform,
+            _ => false,
+        };
+        if leaves {
+            self.move_focus(!modifiers.shift, window, cx);
+            cx.stop_propagation();
+        }
+    }
+
+    // Letting go of Enter calls off a hold_to_confirm press
+    fn on_key_up(&mut self, event: &KeyUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if event.keystroke.key == "enter" {
+            self.release_hold(cx);
+        }
+    }
+
+    // Press the Cancel button, or dismiss when there isn't one
+    pub(crate) fn press_cancel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let cancel = self
+            .buttons
+            .iter()
+            .find(|button| button.role == ButtonRole::Cancel)
+            .cloned();
+        match cancel {
+            Some(button) => self.press(&button, window, cx),
+            None => self.resolve(Outcome::Dismissed, window, cx),
+        }
+    }
+
+    fn press_escape(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match self.escape {
+            EscapePolicy::Dismiss => self.resolve(Outcome::Dismissed, window, cx),
+            EscapePolicy::TriggerCancelButton => self.press_cancel(window, cx),
+            EscapePolicy::Ignore => {}
+            // The second press within the window closes the dialog
+            EscapePolicy::RequireConfirm if self.escape_armed.is_some() => {
+                self.resolve(Outcome::Dismissed, window, cx)
+            }
+            // The first one shows a hint until the window runs out
+            EscapePolicy::RequireConfirm => {
+                self.escape_armed = Some(cx.spawn_in(window, async move |this, cx| {
+                    cx.background_executor().timer(ESCAPE_CONFIRM_WINDOW).await;
+                    this.update(cx, |this, cx| {
+                        this.escape_armed = None;
+                        cx.notify();
+                    })
+                    .ok();
+                }));
+                cx.notify();
+            }
+        }
+    }
+
+    // ==================================================
+    // BODY
+    // ==================================================
+
+    fn render_body(
+        &self,
+        breakpoint: Breakpoint,
+        palette: &Palette,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        match &self.body {
+            DialogBody::Question => None,
+            DialogBody::Form { inputs, scroller } => Some(
+                div()
+                    .id("form")
+                    .flex()
+                    .flex_col()
+                    .gap_y_2() // 8px between fields
+                    .px_3()
+                    // Fields that don't fit scroll
+                    .overflow_y_scroll()
+                    .track_scroll(scroller.handle())
+                    .on_scroll_wheel(cx.listener(|this, event, _, cx| {
+                        if let DialogBody::Form { scroller, .. } = &mut this.body {
+                            scroller.on_scroll(event, cx);
+                        }
+                    }))
+                    // Wide windows fit two fields to a row
+                    .when(breakpoint.is_wide(), |this| {
+                        this.flex_row().flex_wrap().px_2()
+                    })
+                    .children(inputs.iter().map(|(field, input)| {
+                        // Label above its input
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .when(breakpoint.is_wide(), |this| this.w(relative(0.5)).px_1())
+                            .child(
+                                div()
+                                    .text_size(rem(0.92))
+                                    .text_color(palette.secondary_text)
+                                    .child(field.label.clone()),
+                            )
+                            .child(input.element())
+                    }))
+                    .into_any_element(),
+            ),
+            DialogBody::Progress {
+                fraction, status, ..
+            } => Some(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .px_3()
+                    .child(ProgressBar::new(*fraction))
+                    .child(
+                        div()
+                            .flex()
+                            .justify_between()
+                            .text_size(rem(0.85))
+                            .text_color(palette.secondary_text)
+                            .child(status.clone().unwrap_or_default())
+                            .child(format!("{:.0}%", fraction * 100.0)),
+                    )
+                    .into_any_element(),
+            ),
+            DialogBody::Log {
+                lines, scroller, ..
+            } => Some(
+                // A white, sunken text area that scrolls vertically, with
+                // momentum and a rubber band at the ends (see scroll.rs).
+                // Scrolling needs an element id so GPUI can remember the
+                // scroll position between frames.
+                div()
+                    .id("log")
+                    .flex_1()
+                    .mx_3()
+                    .p_2()
+                    .bg(palette.well)
+                    .border_1()
+                    .border_color(palette.border)
+                    .rounded(px(5.0))
+                    .overflow_y_scroll()
+                    .track_scroll(scroller.handle())
+                    .on_scroll_wheel(cx.listener(|this, event, _, cx| {
+                        if let DialogBody::Log { scroller, .. } = &mut this.body {
+                            scroller.on_scroll(event, cx);
+                        }
+                    }))
+                    .font_family(MONOSPACE_FONT)
+                    .text_size(rem(0.85))
+                    .text_color(palette.well_text)
+                    .child(
+                        div()
+                            .relative()
+                            .top(scroller.overscroll())
+                            .children(lines.iter().cloned()),
+                    )
+                    .into_any_element(),
+            ),
+            DialogBody::List { list, .. } => Some(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .px_3()
+                    .child(list.clone())
+                    .into_any_element(),
+            ),
+            DialogBody::Custom { content, .. } => Some(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .px_3()
+                    .child(content.view())
+                    .into_any_element(),
+            ),
+        }
+    }
+
+    // "Don't show this again", for suppressible dialogs
+    fn render_suppress(
+        &self,
+        palette: &Palette,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let (_, ticked) = self.suppress.as_ref()?;
+        let state = if *ticked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        };
+        Some(
+            div()
+                .id("suppress")
+                .flex()
+                .items_center()
+                .gap_2()
+                .mt_3()
+                .text_size(rem(0.85))
+                .text_color(palette.text)
+                .cursor_pointer()
+                .on_click(cx.listener(|this, _, _, cx| {
+                    if let Some((_, ticked)) = &mut this.suppress {
+                        *ticked = !*ticked;
+                        cx.notify();
+                    }
+                }))
+                .child(Checkbox::new(state))
+                .child("Don't show this again"),
+        )
+    }
+
+    // The dialog's chrome, going from the previous step's size to this
+    // one's when it's the next step of a flow. Interceptors registered for
+    // "Dialog" restyle it first (see restyle.rs).
+    fn grow_step(&self, chrome: Div, cx: &App) -> AnyElement {
+        let chrome = restyle::apply("Dialog", chrome, cx);
+        let Some((from, to)) = self.step_sizes else {
+            return chrome.into_any_element();
+        };
+        chrome
+            .with_animation(
+                "step-size",
+                Animation::new(STEP_TRANSITION).with_easing(ease_in_out),
+                move |chrome, delta| {
+                    chrome
+                        .w(from.width + (to.width - from.width) * delta)
+                        .h(from.height + (to.height - from.height) * delta)
+                },
+            )
+            .into_any_element()
+    }
+
+    // Whether this step is smaller than the one before in either direction
+    fn step_shrinks(&self) -> bool {
+        self.step_sizes
+            .is_some_and(|(from, to)| to.width < from.width || to.height < from.height)
+    }
+
+    // Once a shrinking step has changed size, shrink the window to match,
+    // rather than leave the edges it had to spare over what's behind it.
+    // The window keeps its top-left corner.
+    fn shrink_after_step(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((_, to)) = self.step_sizes.filter(|_| self.step_shrinks()) else {
+            return;
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(STEP_TRANSITION).await;
+            this.update_in(cx, |this, window, cx| {
+                this.step_sizes = None;
+                window.resize(to);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    // The content fading in as the chrome changes size, standing in for
+    // the previous step's content that went with its window
+    fn fade_in_step(&self, content: Div) -> AnyElement {
+        if self.step_sizes.is_none() {
+            return content.into_any_element();
+        }
+        content
+            .with_animation(
+                "step-fade",
+                Animation::new(STEP_TRANSITION).with_easing(ease_in_out),
+                |content, delta| content.opacity(delta),
+            )
+            .into_any_element()
+    }
+
+    // The message, body, hints and buttons inside the chrome
+    fn render_content(
+        &mut self,
+        breakpoint: Breakpoint,
+        palette: &Palette,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let order = self.button_order(cx);
+        // Stacked buttons have the primary one on top, so the stack runs
+        // from whichever end of the row it's at
+        let affirmative_first =
+            settings::get(cx).button_order.resolve() == ButtonOrder::AffirmativeFirst;
+
+        // The message, with the selection highlighted. Its layout is kept
+        // to find which word a click landed on.
+        let message = StyledText::new(self.message.clone()).with_highlights(
+            self.message_selection.clone().map(|selection| {
+                let highlight = HighlightStyle {
+                    background_color: Some(rgba(0x007AFF40).into()),
+                    ..Default::default()
+                };
+                (selection, highlight)
+            }),
+        );
+        self.message_layout = Some(message.layout().clone());
+
+        // Log, list and custom bodies take the spare height; otherwise the
+        // message does
+        let message_fills = !matches!(
+            self.body,
+            DialogBody::Log { .. } | DialogBody::List { .. } | DialogBody::Custom { .. }
+        );
+
+        // ==================================================
+        // MAIN CONTENT AREA
+        // ==================================================
+        // This contains the dialog message, the body and action buttons
+        div()
+            .flex()
+            .flex_col() // Stack message, body and buttons vertically
+            .bg(palette.background) // Light gray in the light theme (macOS style)
+            .flex_1() // Take up remaining space
+            .px_6() // 24px horizontal padding
+            .py_5() // 20px vertical padding
+            // Presets with their own header can leave the message empty
+            .when(!self.message.is_empty(), |this| {
+                this.child(
+                    // =================================================
+                    // MESSAGE TEXT CONTAINER
+                    // =================================================
+                    div()
+                        .flex() // Enable flex layout
+                        .when(message_fills, |this| this.flex_1()) // Expand to fill available space
+                        .when(self.stale, |this| this.opacity(0.5)) // Expired: dimmed
+                        .items_center() // Center text vertically
+                        .px_3() // 12px horizontal padding
+                        .py_4() // 16px vertical padding
+                        .child(
+                            // THE ACTUAL TEXT
+                            // In GPUI, text styling is applied via methods
+                            div()
+                                .text_size(rem(1.0)) // The body text size, 13px by default
+                                .text_color(palette.text) // Black in the light theme
+                                .font_weight(FontWeight::NORMAL) // Normal weight
+                                .line_height(relative(1.4)) // 1.4 line spacing
+                                // Double and triple clicks select
+                                // words and paragraphs
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(Self::on_message_mouse_down),
+                                )
+                                .child(message), // The text content
+                        ),
+                )
+            })
+            // Form fields or progress bar, depending on the kind
+            .children(self.render_body(breakpoint, palette, cx))
+            // Waiting for the second ESC, or for a button
+            // to be held long enough, or expired
+            .children(self.hint().map(|hint| {
+                div()
+                    .text_size(rem(0.85))
+                    .text_color(palette.muted_text)
+                    .child(hint)
+            }))
+            .children(self.render_suppress(palette, cx))
+            // A dialog may have no buttons at all (the command palette)
+            .when(!self.buttons.is_empty(), |this| {
+                this.child(
+                    // =================================================
+                    // ACTION BUTTONS CONTAINER
+                    // =================================================
+                    // macOS convention: buttons are right-aligned, with Cancel on
+                    // the left and the primary action (OK) on the right.
+                    // The ButtonOrder setting can put OK first instead.
+                    // A narrow window stacks them full width, the
+                    // primary action on top whatever the order.
+                    div()
+                        .flex() // Horizontal layout
+                        .gap_3() // 12px gap between buttons
+                        .justify_end() // Right-align buttons
+                        .w_full() // Full width
+                        .mt_3() // 12px top margin
+                        .when(breakpoint.is_narrow(), |this| {
+                            if affirmative_first {
+                                this.flex_col().gap_2()
+                            } else {
+                                this.flex_col_reverse().gap_2()
+                            }
+                        })
+                        .children(order.into_iter().map(|index| {
+                            let button = &self.buttons[index];
+                            let style = match button.role {
+                                ButtonRole::Primary => ButtonStyle::Primary,
+                                _ => ButtonStyle::Secondary,
+                            };
+                            let clicked = button.clone();
+                            let hovered = button.label.clone();
+                            Button::new(button.label.clone())
+                                .id(("dialog-button", index))
+                                .style(style)
+                                .when_some(button.badge, |this, count| {
+                                    this.badge(Badge::count(count))
+                                })
+                                .when_some(self.answer_key(index, cx), |this, key| {
+                                    this.shortcut(key)
+                                })
+                                .on_hover(cx.listener(move |_, entered: &bool, _, cx| {
+                                    if *entered {
+                                        cx.emit(DialogEvent::ButtonHovered(hovered.clone()));
+                                    }
+                                }))
+                                // Hold buttons go off when held long
+                                // enough, the others on click
+                                .map(|this| {
+                                    if clicked.hold_to_confirm {
+                                        this.on_press(cx.listener(move |this, _, window, cx| {
+                                            this.press(&clicked, window, cx)
+                                        }))
+                                        .on_release(
+                                            cx.listener(|this, _, _, cx| this.release_hold(cx)),
+                                        )
+                                    } else {
+                                        this.on_click(cx.listener(move |this, _, window, cx| {
+                                            this.press(&clicked, window, cx)
+                                        }))
+                                    }
+                                })
+                        })),
+                )
+            })
+    }
+}
+
+// The platform's standard fixed-width font, for log output
+const MONOSPACE_FONT: &str = if cfg!(target_os = "macos") {
+    "Menlo"
+} else if cfg!(target_os = "windows") {
+    "Consolas"
+} else {
+    "monospace"
+};
+
+// Implement the Render trait to define how the dialog looks
+impl Render for DialogBox {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        layout::apply_root_font_size(window, cx);
+        let breakpoint = Breakpoint::of(window);
+        let palette = theme::window_palette(window, cx);
+        let content = self.render_content(breakpoint, &palette, cx);
+
+        // ==================================================
+        // DIALOG CONTAINER
+        // ==================================================
+        // This is the actual dialog box with all its chrome (titlebar, content, buttons)
+        let chrome = div()
+            .flex()
+            .flex_col() // Stack children vertically
+            // 10px rounded corners, square in lite mode
+            .when(!rendering::lite(cx), |this| this.rounded(px(10.0)))
+            // Large shadow for elevation/depth. Its blur is
+            // costly to draw, so a border stands in when saving
+            // power or rendering lite.
+            .map(|this| {
+                if power::saving(cx) || rendering::lite(cx) {
+                    this.border_1().border_color(palette.border)
+                } else {
+                    this.shadow_lg()
+                }
+            })
+            .overflow_hidden() // Clip children to rounded corners
+            .w_full() // Fill parent width
+            .h_full() // Fill parent height
+            .child(
+                // ==================================================
+                // TITLEBAR WITH TRAFFIC LIGHTS
+                // ==================================================
+                // macOS dialogs have a gray titlebar with three colored buttons
+                // (red, yellow, green) on the left side.
+                div()
+                    .flex() // Horizontal layout
+                    .items_center() // Vertically center items
+                    .h(px(22.0)) // 22px height (macOS standard)
+                    .w_full() // Full width
+                    .bg(palette.titlebar) // Light gray in the light theme
+                    .border_b_1() // 1px border on bottom
+                    .border_color(palette.titlebar_border) // A shade darker
+                    .px_3() // Horizontal padding
+                    .gap_2() // 8px gap between items
+                    .child(
+                        // RED CLOSE BUTTON
+                        // Clicking this will close the dialog
+                        div()
+                            .w(px(12.0)) // 12px diameter
+                            .h(px(12.0)) // 12px diameter
+                            .rounded_full() // Fully rounded (circle)
+                            .bg(rgb(0xFF5F57)) // Red color
+                            .border_1() // 1px border
+                            .border_color(rgb(0xE04943)) // Darker red border
+                            .cursor_pointer() // Show pointer cursor on hover
+                            // Attach click handler
+                            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_close_clicked)),
+                    )
+                    .child(
+                        // YELLOW MINIMIZE BUTTON
+                        // Collapses collapsible progress dialogs;
+                        // decorative otherwise
+                        div()
+                            .w(px(12.0))
+                            .h(px(12.0))
+                            .rounded_full()
+                            .bg(rgb(0xFFBD2E))
+                            .border_1()
+                            .border_color(rgb(0xDEA123)) // Darker yellow border
+                            .when(self.collapsible, |this| {
+                                this.cursor_pointer().on_mouse_up(
+                                    MouseButton::Left,
+                                    cx.listener(|this, _, window, cx| this.collapse(window, cx)),
+                                )
+                            }),
+                    )
+                    .child(
+                        // GREEN MAXIMIZE BUTTON
+                        // Decorative only
+                        div()
+                            .w(px(12.0))
+                            .h(px(12.0))
+                            .rounded_full()
+                            .bg(rgb(0x28C940))
+                            .border_1()
+                            .border_color(rgb(0x1AAB29)), // Darker green border
+                    )
+                    // OPTIONAL TITLE
+                    // Takes the remaining width; the right padding
+                    // balances the traffic lights so it looks centered
+                    .when_some(self.title.clone(), |titlebar, title| {
+                        titlebar.child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .justify_center()
+                                .pr(px(52.0))
+                                .text_size(rem(0.92))
+                                .text_color(palette.secondary_text)
+                                .child(title),
+                        )
+                    })
+                    .when_some(self.capture_protection, |titlebar, protected| {
+                        titlebar.child(render_shield(protected))
+                    })
+                    .map(|titlebar| restyle::apply("Dialog::Titlebar", titlebar, cx)),
+            )
+            .child(self.fade_in_step(content))
+            // Optional status bar along the bottom edge
+            .children(self.status_bar.clone());
+
+        // ======================================================
+        // OUTER CONTAINER
+        // ======================================================
+        // This is the full-window container that centers the dialog.
+        // We use flexbox layout (similar to CSS flexbox) to center content.
+
+        let dialog = div()
+            .flex() // Enable flexbox layout
+            .size_full() // Take up the full window size
+            .justify_center() // Center content horizontally
+            .items_center() // Center content vertically
+            // A shrinking step keeps to the corner the window shrinks
+            // towards, so nothing jumps when it does
+            .when(self.step_shrinks(), |this| {
+                this.justify_start().items_start()
+            })
+            // Preempted dialogs keep their state but draw nothing, and
+            // with nothing drawn there's nothing to click or type into
+            .when(self.is_preempted() || self.pill.is_some(), |this| {
+                this.invisible()
+            })
+            // Receive keyboard events even when no text input is focused
+            .track_focus(&self.focus_handle)
+            // Attach keyboard event handler for ESC and Enter
+            // cx.listener() converts a method into an event listener
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_key_up(cx.listener(Self::on_key_up))
+            // Watch every key and mouse press for the usage metrics
+            .capture_key_down(cx.listener(Self::on_any_key_down))
+            .capture_key_down(cx.listener(Self::trap_focus))
+            .capture_any_mouse_down(cx.listener(Self::on_any_mouse_down))
+            .child(self.grow_step(chrome, cx));
+        // Timed for --trace (see trace.rs)
+        trace::traced("Dialog", dialog)
+    }
+}
+
+// The titlebar's right edge on a sensitive dialog: a shield saying
+// whether the window is kept out of screen capture
+fn render_shield(protected: bool) -> impl IntoElement {
+    let (text, color) = if protected {
+        ("Hidden from screen sharing", 0x4D4D4D)
+    } else {
+        ("Visible to screen sharing", 0xC93400)
+    };
+    div()
+        .ml_auto()
+        .flex()
+        .flex_none()
+        .items_center()
+        .gap_1()
+        .text_size(rem(0.77))
+        .text_color(rgb(color))
+        .child("🛡")
+        .child(text)
+}
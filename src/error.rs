@@ -0,0 +1,83 @@
+// ======================================================================
+// ERRORS
+// ======================================================================
+// What can go wrong when showing a dialog. show() and the other public
+// entry points return these instead of panicking, so an app can fall
+// back to something else (or a script can tell what happened from the
+// exit code):
+//
+//     if let Err(err) = Dialog::question("Delete the file?").show(cx, on_resolve) {
+//         eprintln!("error: {err}");
+//         std::process::exit(err.exit_code());
+//     }
+//
+// The exit codes follow BSD's sysexits.h, which shell scripts already
+// know how to read. The command line's own usage errors keep exiting
+// with 2.
+
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum DialogError {
+    // The platform refused to open the dialog or backdrop window
+    WindowCreation(String),
+    // There's no display to show the dialog on (e.g. a headless session)
+    DisplayNotFound,
+    // The dialog as described can't be shown, e.g. two form fields with
+    // the same id
+    InvalidSpec(String),
+    Io(io::Error),
+    // Talking to another process about a dialog failed
+    Ipc(String),
+    // Too many dialogs were requested in too short a time (see
+    // manager.rs)
+    RateLimited,
+    // The user asked not to see this dialog again (see
+    // Dialog::suppressible())
+    Suppressed,
+}
+
+impl DialogError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DialogError::InvalidSpec(_) => 65,    // EX_DATAERR
+            DialogError::DisplayNotFound => 69,   // EX_UNAVAILABLE
+            DialogError::WindowCreation(_) => 71, // EX_OSERR
+            DialogError::Io(_) => 74,             // EX_IOERR
+            DialogError::RateLimited => 75,       // EX_TEMPFAIL
+            DialogError::Ipc(_) => 76,            // EX_PROTOCOL
+            // Not a failure: the user already answered for good
+            DialogError::Suppressed => 0,
+        }
+    }
+}
+
+impl fmt::Display for DialogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialogError::WindowCreation(reason) => write!(f, "couldn't open a window: {reason}"),
+            DialogError::DisplayNotFound => write!(f, "no display to show the dialog on"),
+            DialogError::InvalidSpec(reason) => write!(f, "invalid dialog: {reason}"),
+            DialogError::Io(err) => write!(f, "{err}"),
+            DialogError::Ipc(reason) => write!(f, "couldn't reach the other process: {reason}"),
+            DialogError::RateLimited => write!(f, "too many dialogs at once; try again later"),
+            DialogError::Suppressed => write!(f, "the user asked not to see this dialog again"),
+        }
+    }
+}
+
+impl std::error::Error for DialogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DialogError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DialogError {
+    fn from(err: io::Error) -> Self {
+        DialogError::Io(err)
+    }
+}
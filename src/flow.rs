@@ -0,0 +1,217 @@
+// ======================================================================
+// DIALOG FLOW
+// ======================================================================
+// Chains several dialogs into one interaction, e.g.
+// question → form → progress, without going back to the caller between
+// steps. Each step sees the previous result and decides which dialog (if
+// any) comes next:
+//
+//     DialogFlow::new(Dialog::question("Create a new project?"))
+//         .then(|result, _ctx| {
+//             result.is_confirmed().then(|| {
+//                 Dialog::form("Project details", [FormField::text("name", "Name")])
+//             })
+//         })
+//         .then(|_result, ctx| {
+//             let name = ctx.get("name").unwrap_or_default().to_string();
+//             let updates = start_creating(&name); // your code, see progress_channel()
+//             Some(Dialog::progress(format!("Creating {name}…"), updates))
+//         })
+//         .run(cx, |result, cx| { /* result.values holds "name" */ });
+//
+// Values travel between steps in a FlowContext: every form's values are
+// merged into it automatically, and steps can add their own. The flow
+// ends when a step returns None, when the steps run out, when the user
+// dismisses a dialog (ESC or the close button), or when a dialog times
+// out. Either way the caller gets a single DialogResult: the outcome of
+// the last dialog plus everything in the context, with metrics covering
+// the whole flow.
+//
+// The backdrop stays up for the whole flow, so the screen doesn't flash
+// between steps. A step of a different size than the one before grows or
+// shrinks from that one's size, its content fading in, rather than
+// jumping (unless the user asked for reduced motion).
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::rc::Rc;
+
+use gpui::*;
+
+use crate::backdrop::{close_backdrop, keep_above, open_backdrop, Backdrop};
+use crate::dialog::{Dialog, ResolveCallback};
+use crate::error::DialogError;
+use crate::result::{DialogMetrics, DialogResult, Outcome};
+
+// Values shared between the steps of a flow
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlowContext {
+    values: BTreeMap<String, String>,
+}
+
+impl FlowContext {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    pub fn values(&self) -> &BTreeMap<String, String> {
+        &self.values
+    }
+}
+
+// Given the previous dialog's result, build the next dialog (None ends the flow)
+type Step = Box<dyn FnOnce(&DialogResult, &mut FlowContext) -> Option<Dialog>>;
+
+pub struct DialogFlow {
+    first: Dialog,
+    steps: VecDeque<Step>,
+}
+
+impl DialogFlow {
+    pub fn new(first: Dialog) -> Self {
+        Self {
+            first,
+            steps: VecDeque::new(),
+        }
+    }
+
+    // Add the next step
+    pub fn then(
+        mut self,
+        step: impl FnOnce(&DialogResult, &mut FlowContext) -> Option<Dialog> + 'static,
+    ) -> Self {
+        self.steps.push_back(Box::new(step));
+        self
+    }
+
+    // Start the flow. on_complete runs once, after the last dialog closes.
+    // An error means the first dialog couldn't be shown; if a later one
+    // can't, the flow ends there with the result of the one before.
+    pub fn run(
+        self,
+        cx: &mut App,
+        on_complete: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<(), DialogError> {
+        self.first.validate()?;
+        let run = FlowRun {
+            steps: self.steps,
+            context: FlowContext::default(),
+            metrics: DialogMetrics::default(),
+            backdrop: open_backdrop(cx)?,
+            on_complete: Box::new(on_complete),
+            last_size: None,
+        };
+        run.show(self.first, cx).map_err(|(err, run)| {
+            if let Some(backdrop) = run.backdrop {
+                close_backdrop(backdrop, cx);
+            }
+            err
+        })
+    }
+}
+
+// The state of a flow in progress. It is moved into each dialog's resolve
+// callback, so exactly one dialog owns it at a time.
+struct FlowRun {
+    steps: VecDeque<Step>,
+    context: FlowContext,
+    metrics: DialogMetrics,
+    // None when backdrops are off (see backdrop.rs)
+    backdrop: Option<WindowHandle<Backdrop>>,
+    on_complete: ResolveCallback,
+    // The size of the dialog shown last
+    last_size: Option<Size<Pixels>>,
+}
+
+impl FlowRun {
+    // When the dialog can't be opened, the run comes back with the error
+    fn show(mut self, dialog: Dialog, cx: &mut App) -> Result<(), (DialogError, Box<FlowRun>)> {
+        let size = dialog.window_size(cx);
+        let dialog = dialog.step_from(self.last_size.replace(size));
+        // Only the dialog's callback takes the run out, and that can't
+        // happen if the window never opened
+        let backdrop = self.backdrop;
+        let slot = Rc::new(RefCell::new(Some(self)));
+        let owner = slot.clone();
+        // The flow owns the backdrop, so the dialog opens without its own
+        let opened = dialog.open(None, cx, move |result, cx| {
+            if let Some(run) = owner.borrow_mut().take() {
+                run.advance(result, cx);
+            }
+        });
+        match opened {
+            Ok(window) => {
+                if let Some(backdrop) = backdrop {
+                    keep_above(backdrop, window.into(), cx);
+                }
+                Ok(())
+            }
+            Err(err) => match slot.borrow_mut().take() {
+                Some(run) => Err((err, Box::new(run))),
+                None => Ok(()),
+            },
+        }
+    }
+
+    // Fold one step's metrics into the flow's: durations add up, and the
+    // first interaction is measured from the start of the flow
+    fn add_step_metrics(&mut self, step: &DialogMetrics) {
+        let metrics = &mut self.metrics;
+        if metrics.time_to_first_interaction.is_none() {
+            metrics.time_to_first_interaction = step
+                .time_to_first_interaction
+                .map(|elapsed| metrics.open_duration + elapsed);
+        }
+        metrics.open_duration += step.open_duration;
+        metrics.used_keyboard |= step.used_keyboard;
+        metrics.used_mouse |= step.used_mouse;
+        metrics.timed_out = step.timed_out;
+    }
+
+    fn advance(mut self, result: DialogResult, cx: &mut App) {
+        self.context
+            .values
+            .extend(result.values.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.add_step_metrics(&result.metrics);
+
+        let next = if matches!(
+            result.outcome,
+            Outcome::Dismissed | Outcome::TimedOut | Outcome::Expired
+        ) {
+            None
+        } else {
+            self.steps
+                .pop_front()
+                .and_then(|step| step(&result, &mut self.context))
+        };
+
+        let outcome = result.outcome;
+        match next {
+            Some(dialog) => {
+                if let Err((err, run)) = self.show(dialog, cx) {
+                    log::error!("dialog flow ended early: {err}");
+                    run.finish(outcome, cx);
+                }
+            }
+            None => self.finish(outcome, cx),
+        }
+    }
+
+    fn finish(self, outcome: Outcome, cx: &mut App) {
+        if let Some(backdrop) = self.backdrop {
+            close_backdrop(backdrop, cx);
+        }
+        (self.on_complete)(
+            DialogResult {
+                outcome,
+                values: self.context.values,
+                metrics: self.metrics,
+            },
+            cx,
+        );
+    }
+}
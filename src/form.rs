@@ -0,0 +1,104 @@
+// ======================================================================
+// TYPED FORMS
+// ======================================================================
+// A form dialog that hands back a struct instead of a map of strings.
+// Derive DialogForm on a struct with named fields (see the
+// gpui-dialogs-derive crate for the attributes) and show it:
+//
+//     #[derive(DialogForm)]
+//     struct Connection {
+//         #[dialog(placeholder = "example.com")]
+//         host: String,
+//         #[dialog(default = "22")]
+//         port: u16,
+//     }
+//
+//     Connection::show_form("Connect to a server", cx, |connection, cx| {
+//         if let Some(connection) = connection { /* ... */ }
+//     })?;
+//
+// When a value doesn't parse or a validator rejects it, the form comes
+// back with the problem under the message and the text as it was typed,
+// until the user gets it right or cancels.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use gpui::{App, SharedString};
+
+use crate::dialog::{Dialog, DialogHandle, FormField};
+use crate::error::DialogError;
+
+pub trait DialogForm: Sized + 'static {
+    // One field per struct field, in order
+    fn fields() -> Vec<FormField>;
+
+    // The struct from the form's values, or what's wrong with them
+    fn from_values(values: &BTreeMap<String, String>) -> Result<Self, String>;
+
+    // Show the form. on_done gets the struct, or None if the user
+    // cancelled.
+    fn show_form(
+        message: impl Into<SharedString>,
+        cx: &mut App,
+        on_done: impl FnOnce(Option<Self>, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        ask(message.into(), Self::fields(), None, cx, Box::new(on_done))
+    }
+}
+
+type OnDone<T> = Box<dyn FnOnce(Option<T>, &mut App)>;
+
+fn ask<T: DialogForm>(
+    message: SharedString,
+    fields: Vec<FormField>,
+    problem: Option<String>,
+    cx: &mut App,
+    on_done: OnDone<T>,
+) -> Result<DialogHandle, DialogError> {
+    let shown = match &problem {
+        Some(problem) => format!("{message}\n\n{problem}").into(),
+        None => message.clone(),
+    };
+    Dialog::form(shown, fields.clone()).show(cx, move |result, cx| {
+        if !result.is_confirmed() {
+            on_done(None, cx);
+            return;
+        }
+        match T::from_values(&result.values) {
+            Ok(value) => on_done(Some(value), cx),
+            Err(problem) => {
+                // Again, keeping what was typed
+                let fields = fields
+                    .into_iter()
+                    .map(|field| {
+                        let typed = result.value(&field.id).unwrap_or_default().to_string();
+                        field.default_value(typed)
+                    })
+                    .collect();
+                // Shared, so it's still ours if the form doesn't open
+                let on_done = Rc::new(RefCell::new(Some(on_done)));
+                let pending = on_done.clone();
+                let asked = ask(
+                    message,
+                    fields,
+                    Some(problem),
+                    cx,
+                    Box::new(move |value, cx| {
+                        if let Some(on_done) = pending.borrow_mut().take() {
+                            on_done(value, cx);
+                        }
+                    }),
+                );
+                if let Err(err) = asked {
+                    log::error!("couldn't show the form again: {err}");
+                    let taken = on_done.borrow_mut().take();
+                    if let Some(on_done) = taken {
+                        on_done(None, cx);
+                    }
+                }
+            }
+        }
+    })
+}
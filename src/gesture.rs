@@ -0,0 +1,173 @@
+// ======================================================================
+// GESTURES
+// ======================================================================
+// GPUI reports raw mouse downs and ups. These helpers turn them into the
+// gestures people expect from native controls:
+//
+// ClickCounter tells single, double and triple clicks apart. Feed it
+// every mouse down; clicks close together in time and place count up:
+//
+//     match self.clicks.down(event.position) {
+//         Click::Single => self.move_to(index, cx),
+//         Click::Double => self.select_word(index, cx),
+//         Click::Triple => self.select_all(cx),
+//     }
+//
+// LongPress fires when the button is held down long enough, and not if
+// it's released first. Start it on mouse down, cancel it on mouse up:
+//
+//     self.hold.start(HOLD_TO_CONFIRM, window, cx, |this, window, cx| {
+//         this.delete(window, cx)
+//     });
+//     // ...on mouse up
+//     self.hold.cancel();
+
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use gpui::{px, Context, Pixels, Point, Task, Window};
+use unicode_segmentation::UnicodeSegmentation;
+
+// Clicks further apart than this start counting again
+pub const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+// ...and so do clicks that moved more than this
+pub const MULTI_CLICK_DISTANCE: Pixels = px(4.0);
+// How long a press has to last to count as a long press
+pub const LONG_PRESS: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Click {
+    Single,
+    Double,
+    // The third click and any after it
+    Triple,
+}
+
+#[derive(Debug, Default)]
+pub struct ClickCounter {
+    last: Option<(Instant, Point<Pixels>)>,
+    count: usize,
+}
+
+impl ClickCounter {
+    // Count a mouse down at `position`
+    pub fn down(&mut self, position: Point<Pixels>) -> Click {
+        let now = Instant::now();
+        let continues = self.last.is_some_and(|(at, last)| {
+            now.duration_since(at) <= MULTI_CLICK_INTERVAL
+                && (position.x - last.x).abs() <= MULTI_CLICK_DISTANCE
+                && (position.y - last.y).abs() <= MULTI_CLICK_DISTANCE
+        });
+        self.count = if continues { self.count + 1 } else { 1 };
+        self.last = Some((now, position));
+        match self.count {
+            1 => Click::Single,
+            2 => Click::Double,
+            _ => Click::Triple,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct LongPress {
+    // When the press started, how long it has to last, and the timer
+    // that fires it
+    pending: Option<(Instant, Duration, Task<()>)>,
+}
+
+impl LongPress {
+    // Call `on_long_press` once the press has lasted `duration`, unless
+    // it's cancelled first. Starting again restarts the wait.
+    pub fn start<T: 'static>(
+        &mut self,
+        duration: Duration,
+        window: &mut Window,
+        cx: &mut Context<T>,
+        on_long_press: impl FnOnce(&mut T, &mut Window, &mut Context<T>) + 'static,
+    ) {
+        let timer = cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(duration).await;
+            this.update_in(cx, on_long_press).ok();
+        });
+        self.pending = Some((Instant::now(), duration, timer));
+    }
+
+    // Stop waiting; returns whether a press was still pending
+    pub fn cancel(&mut self) -> bool {
+        let pending = self.is_pending();
+        self.pending = None;
+        pending
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.progress().is_some()
+    }
+
+    // How far along the press is, from 0 to 1, while it's pending
+    pub fn progress(&self) -> Option<f32> {
+        let (started, duration, _) = self.pending.as_ref()?;
+        let progress = started.elapsed().as_secs_f32() / duration.as_secs_f32().max(0.001);
+        (progress < 1.0).then_some(progress)
+    }
+}
+
+// The word around byte `offset` of `text`, for double-click selection.
+// Between words (on a space, say) that's the run of spaces, and past the
+// end (a click after the last word) it's the last word.
+pub fn word_at(text: &str, offset: usize) -> Range<usize> {
+    let offset = offset.min(text.len().saturating_sub(1));
+    text.split_word_bound_indices()
+        .map(|(start, word)| start..start + word.len())
+        .find(|range| range.contains(&offset))
+        .unwrap_or(text.len()..text.len())
+}
+
+// The paragraph around byte `offset`: the text between blank lines, for
+// triple-click selection
+pub fn paragraph_at(text: &str, offset: usize) -> Range<usize> {
+    let offset = offset.min(text.len());
+    let start = text[..offset].rfind("\n\n").map_or(0, |index| index + 2);
+    let end = text[offset..]
+        .find("\n\n")
+        .map_or(text.len(), |index| offset + index);
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paragraph_at, word_at};
+
+    #[test]
+    fn words_and_the_spaces_between() {
+        let text = "Save the file?";
+        assert_eq!(word_at(text, 0), 0..4);
+        assert_eq!(word_at(text, 6), 5..8);
+        assert_eq!(word_at(text, 4), 4..5);
+        assert_eq!(word_at(text, 13), 13..14);
+    }
+
+    #[test]
+    fn past_the_end_is_the_last_word() {
+        assert_eq!(word_at("Save the file", 13), 9..13);
+        assert_eq!(word_at("Save the file", 99), 9..13);
+        assert_eq!(word_at("", 0), 0..0);
+    }
+
+    #[test]
+    fn words_in_other_scripts() {
+        let text = "héllo wörld";
+        assert_eq!(word_at(text, 1), 0..6);
+        assert_eq!(word_at(text, text.len()), 7..text.len());
+    }
+
+    #[test]
+    fn paragraphs_between_blank_lines() {
+        let text = "One.\n\nTwo,\nstill two.\n\nThree.";
+        assert_eq!(paragraph_at(text, 0), 0..4);
+        assert_eq!(paragraph_at(text, 8), 6..21);
+        assert_eq!(paragraph_at(text, 15), 6..21);
+        assert_eq!(paragraph_at(text, text.len()), 23..29);
+        assert_eq!(paragraph_at(text, 99), 23..29);
+        assert_eq!(paragraph_at("", 0), 0..0);
+    }
+}
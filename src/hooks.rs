@@ -0,0 +1,137 @@
+// ======================================================================
+// EXIT HOOKS
+// ======================================================================
+// `--on-ok` and `--on-cancel` run a shell command once the dialog has
+// been answered, which turns one-liners like
+//
+//     gpui --on-ok "git push"
+//
+// into small automations without a wrapper script. The command sees the
+// result in its environment:
+//
+//     DIALOG_BUTTON         label of the clicked button (empty if none)
+//     DIALOG_VALUE_<ID>     each form value, ID upper-cased
+//
+// By default the command inherits our stdout/stderr and we wait for it
+// before exiting. With `--show-hook-output` its output is streamed into a
+// log dialog instead.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use gpui::*;
+use gpui_dialogs::cli::Args;
+use gpui_dialogs::{
+    log_channel, ButtonRole, Dialog, DialogError, DialogResult, LogSender, Outcome,
+};
+
+// The hook that applies to this result, if any. Secondary buttons
+// ("Later", "Don't Save", ...) run neither hook.
+pub fn command_for<'a>(result: &DialogResult, args: &'a Args) -> Option<&'a str> {
+    let cancelled = matches!(
+        result.outcome,
+        Outcome::Dismissed
+            | Outcome::Button {
+                role: ButtonRole::Cancel,
+                ..
+            }
+    );
+
+    if result.is_confirmed() {
+        args.on_ok.as_deref()
+    } else if cancelled {
+        args.on_cancel.as_deref()
+    } else {
+        None
+    }
+}
+
+// Run the command to completion with inherited stdio
+pub fn run(command: &str, result: &DialogResult) {
+    match shell(command, result).status() {
+        Ok(status) if !status.success() => eprintln!("`{command}` failed: {status}"),
+        Ok(_) => {}
+        Err(err) => eprintln!("failed to run `{command}`: {err}"),
+    }
+}
+
+// Open a log dialog and stream the command's output into it.
+// on_close runs when the user closes the log.
+pub fn run_in_log_dialog(
+    command: &str,
+    result: &DialogResult,
+    cx: &mut App,
+    on_close: impl FnOnce(&mut App) + 'static,
+) -> Result<(), DialogError> {
+    let (lines, receiver) = log_channel();
+    lines.unbounded_send(format!("$ {command}").into()).ok();
+
+    let mut shell = shell(command, result);
+    shell.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    match shell.spawn() {
+        Ok(mut child) => {
+            // Forward stdout and stderr line by line as they arrive, then
+            // report how the command exited
+            let stdout = child
+                .stdout
+                .take()
+                .map(|out| forward_lines(out, lines.clone()));
+            let stderr = child
+                .stderr
+                .take()
+                .map(|err| forward_lines(err, lines.clone()));
+            thread::spawn(move || {
+                for reader in [stdout, stderr].into_iter().flatten() {
+                    reader.join().ok();
+                }
+                let status = match child.wait() {
+                    Ok(status) => format!("[{status}]"),
+                    Err(err) => format!("[failed to wait for command: {err}]"),
+                };
+                lines.unbounded_send(status.into()).ok();
+            });
+        }
+        Err(err) => {
+            lines
+                .unbounded_send(format!("[failed to run command: {err}]").into())
+                .ok();
+        }
+    }
+
+    Dialog::log("Command output", receiver)
+        .title("Output")
+        .show(cx, move |_result, cx| on_close(cx))?;
+    Ok(())
+}
+
+fn forward_lines(output: impl Read + Send + 'static, lines: LogSender) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(output).lines() {
+            let Ok(line) = line else { break };
+            if lines.unbounded_send(line.into()).is_err() {
+                break; // The log dialog was closed
+            }
+        }
+    })
+}
+
+// The platform shell running `command`, with the result in its environment
+fn shell(command: &str, result: &DialogResult) -> Command {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+
+    shell.env("DIALOG_BUTTON", result.button().unwrap_or_default());
+    for (key, value) in &result.values {
+        shell.env(format!("DIALOG_VALUE_{}", key.to_uppercase()), value);
+    }
+    shell
+}
@@ -0,0 +1,388 @@
+// ======================================================================
+// KEYMAP
+// ======================================================================
+// Key bindings from a keymap.toml next to the other stored state (see
+// storage.rs), on top of the built-in ones. Each entry maps an action to
+// the key chords that trigger it, with sections for bindings that only
+// apply on one platform:
+//
+//     "shortcuts::ToggleShortcuts" = ["secondary-/", "f1"]
+//     "text_input::SelectAll" = { keys = ["secondary-a"], context = "TextInput" }
+//
+//     [macos]
+//     "text_input::Paste" = { keys = ["cmd-v", "ctrl-y"], context = "TextInput" }
+//
+//     [linux]
+//     "text_input::Paste" = { keys = ["ctrl-v", "shift-insert"], context = "TextInput" }
+//
+// A platform section's entry replaces the shared one for the same action.
+// A chord is keystrokes separated by spaces ("ctrl-k ctrl-s"), each one
+// modifiers and a key joined by "-"; "secondary" is cmd on macOS and ctrl
+// elsewhere.
+//
+// An entry with a context only applies where the focus is inside it, as
+// gpui's key contexts go ("TextInput", "EmojiPicker > TextInput", ...).
+// One without applies everywhere, except that a key which types something
+// ("?", or "space" with no modifier) is left to text inputs.
+//
+// It is read by init(). Entries that don't make sense (an unknown action,
+// a misspelled key) are skipped with a warning in the log, and the rest
+// still load. The shortcuts sheet marks the bindings that came from the
+// file and lists what was skipped:
+//
+//     for problem in keymap::problems(cx) { ... }
+
+use std::rc::Rc;
+
+use gpui::*;
+use toml::{Table, Value};
+
+use crate::storage;
+
+const FILE: &str = "keymap.toml";
+
+// Tags the bindings from the file in the app's keymap
+pub const FROM_FILE: KeyBindingMetaIndex = KeyBindingMetaIndex(1);
+
+const PLATFORMS: [&str; 3] = ["macos", "linux", "windows"];
+
+// Keys with a name rather than a character
+const NAMED_KEYS: [&str; 17] = [
+    "enter",
+    "escape",
+    "backspace",
+    "delete",
+    "insert",
+    "tab",
+    "space",
+    "left",
+    "right",
+    "up",
+    "down",
+    "home",
+    "end",
+    "pageup",
+    "pagedown",
+    "menu",
+    "capslock",
+];
+
+// The key chords for an action from the file, and where they apply
+struct Entry {
+    action: String,
+    chords: Vec<String>,
+    context: Option<String>,
+}
+
+#[derive(Default)]
+struct Loaded {
+    problems: Vec<String>,
+}
+
+impl Global for Loaded {}
+
+// What was wrong with keymap.toml when it was read, one line per entry
+// that was skipped
+pub fn problems(cx: &App) -> &[String] {
+    cx.try_global::<Loaded>()
+        .map(|loaded| loaded.problems.as_slice())
+        .unwrap_or_default()
+}
+
+// Read keymap.toml, if there is one, and bind what it asks for
+pub(crate) fn load(cx: &mut App) {
+    let Some(text) = storage::load_text(FILE) else {
+        return;
+    };
+    let (entries, mut problems) = parse(&text, current_platform());
+
+    let text_input = Rc::new(KeyBindingContextPredicate::Identifier("TextInput".into()));
+    let mut bindings = Vec::new();
+    for entry in entries {
+        let action = match cx.build_action(&entry.action, None) {
+            Ok(action) => action,
+            Err(_) => {
+                problems.push(unknown_action(&entry.action, cx));
+                continue;
+            }
+        };
+        // Checked by parse() too
+        let context = entry
+            .context
+            .as_deref()
+            .and_then(|context| KeyBindingContextPredicate::parse(context).ok())
+            .map(Rc::new);
+        for chord in &entry.chords {
+            let mapper = cx.keyboard_mapper().clone();
+            let binding = KeyBinding::load(
+                chord,
+                action.boxed_clone(),
+                context.clone(),
+                false,
+                None,
+                mapper.as_ref(),
+            );
+            // The chord was checked by parse(), so this shouldn't fail
+            match binding {
+                Ok(binding) => bindings.push(binding.with_meta(FROM_FILE)),
+                Err(err) => {
+                    problems.push(format!("{}: {err}", entry.action));
+                    continue;
+                }
+            }
+            // Unbound again where it's text. Without a meta, so it isn't
+            // taken for a default binding that a user's could override.
+            if context.is_none() && types_text(chord) {
+                let unbound = KeyBinding::load(
+                    chord,
+                    Box::new(NoAction),
+                    Some(text_input.clone()),
+                    false,
+                    None,
+                    mapper.as_ref(),
+                );
+                bindings.extend(unbound.ok());
+            }
+        }
+    }
+
+    for problem in &problems {
+        log::warn!("{FILE}: {problem}");
+    }
+    cx.bind_keys(bindings);
+    cx.set_global(Loaded { problems });
+}
+
+// What load() would skip of a keymap.toml with this text, on this
+// platform, apart from unknown actions (which only the app knows)
+pub fn check_text(text: &str) -> Vec<String> {
+    parse(text, current_platform()).1
+}
+
+fn current_platform() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else {
+        "linux"
+    }
+}
+
+// A table that's a section of entries rather than an entry of its own
+// (even one missing its keys)
+fn is_section(value: &Value) -> bool {
+    value
+        .as_table()
+        .is_some_and(|table| !table.contains_key("keys") && !table.contains_key("context"))
+}
+
+// The entries that apply on `platform`, and what was wrong with the rest
+fn parse(text: &str, platform: &str) -> (Vec<Entry>, Vec<String>) {
+    let table: Table = match text.parse() {
+        Ok(table) => table,
+        Err(err) => return (Vec::new(), vec![err.to_string()]),
+    };
+    let mut problems: Vec<String> = table
+        .iter()
+        .filter(|(key, value)| is_section(value) && !PLATFORMS.contains(&key.as_str()))
+        .map(|(key, _)| format!("unknown section [{key}], expected [macos], [linux] or [windows]"))
+        .collect();
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut add = |section: Option<&str>, action: &str, value: &Value| {
+        let at = match section {
+            Some(section) => format!("[{section}] {action}"),
+            None => action.to_string(),
+        };
+        let (keys, context) = match value.as_table() {
+            Some(entry) => (entry.get("keys"), entry.get("context")),
+            None => (Some(value), None),
+        };
+        let Some(chords) = keys.and_then(chords) else {
+            problems.push(format!(
+                "{at}: expected a list of key chords, like [\"ctrl-k\"], \
+                 or {{ keys = [...], context = \"...\" }}"
+            ));
+            return;
+        };
+        let context = match context.map(|context| (context.as_str(), context)) {
+            None => None,
+            Some((Some(context), _)) if KeyBindingContextPredicate::parse(context).is_ok() => {
+                Some(context.to_string())
+            }
+            Some((_, context)) => {
+                problems.push(format!(
+                    "{at}: {context} isn't a key context, like \"TextInput\""
+                ));
+                return;
+            }
+        };
+        let mut valid = Vec::new();
+        for chord in chords {
+            match check_chord(chord) {
+                Ok(()) => valid.push(chord.to_string()),
+                Err(problem) => problems.push(format!("{at}: {problem}")),
+            }
+        }
+        // A platform's entry replaces the shared one
+        entries.retain(|entry| entry.action != action);
+        entries.push(Entry {
+            action: action.to_string(),
+            chords: valid,
+            context,
+        });
+    };
+
+    // Shared entries first, so the platform's can replace them
+    for (key, value) in &table {
+        if !is_section(value) {
+            add(None, key, value);
+        }
+    }
+    if let Some(section) = table.get(platform).and_then(Value::as_table) {
+        for (action, value) in section {
+            add(Some(platform), action, value);
+        }
+    }
+    (entries, problems)
+}
+
+// The strings of a list of strings
+fn chords(value: &Value) -> Option<Vec<&str>> {
+    value.as_array()?.iter().map(Value::as_str).collect()
+}
+
+// Whether every keystroke in `chord` is something a keyboard can type
+fn check_chord(chord: &str) -> Result<(), String> {
+    if chord.trim().is_empty() {
+        return Err("empty key chord".to_string());
+    }
+    for source in chord.split_whitespace() {
+        let keystroke = Keystroke::parse(source)
+            .map_err(|_| format!("\"{source}\" isn't a keystroke, like \"ctrl-shift-k\""))?;
+        let key = keystroke.key.as_str();
+        let known = key.chars().count() == 1
+            || NAMED_KEYS.contains(&key)
+            || key
+                .strip_prefix('f')
+                .and_then(|number| number.parse::<u8>().ok())
+                .is_some_and(|number| (1..=24).contains(&number));
+        if !known {
+            return Err(format!("unknown key \"{key}\" in \"{source}\""));
+        }
+    }
+    Ok(())
+}
+
+// Whether any keystroke of `chord` types something (a character or a
+// space, with no modifier but shift), which a binding would take away
+// from text inputs
+pub(crate) fn types_text(chord: &str) -> bool {
+    chord.split_whitespace().any(|source| {
+        Keystroke::parse(source).is_ok_and(|keystroke| {
+            let modifiers = keystroke.modifiers;
+            let key = keystroke.key.as_str();
+            !(modifiers.control || modifiers.alt || modifiers.platform || modifiers.function)
+                && (key.chars().count() == 1 || key == "space")
+        })
+    })
+}
+
+// An unknown action, and the registered one it might have meant: the
+// same name in any namespace, or the same letters in another case
+fn unknown_action(name: &str, cx: &App) -> String {
+    let bare = name.rsplit("::").next().unwrap_or(name).to_lowercase();
+    let suggestion = cx.all_action_names().iter().find(|action| {
+        action.eq_ignore_ascii_case(name)
+            || action
+                .rsplit("::")
+                .next()
+                .is_some_and(|action| action.to_lowercase() == bare)
+    });
+    match suggestion {
+        Some(action) => format!("{name}: no such action; did you mean {action}?"),
+        None => format!("{name}: no such action"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_text, parse, types_text};
+
+    #[test]
+    fn a_good_keymap_has_no_problems() {
+        let text = r#"
+            "shortcuts::ToggleShortcuts" = ["secondary-/", "f1"]
+            "text_input::SelectAll" = { keys = ["secondary-a"], context = "TextInput" }
+            "emoji_picker::SelectLeft" = { keys = ["ctrl-b"], context = "EmojiPicker > TextInput" }
+
+            [linux]
+            "text_input::Paste" = ["ctrl-v", "shift-insert", "ctrl-k ctrl-v"]
+        "#;
+        assert_eq!(check_text(text), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_bad_entries_and_keeps_the_rest() {
+        let text = r#"
+            "a::One" = "ctrl-a"
+            "a::Two" = ["ctrl-shift-nope", "ctrl-b", ""]
+            "a::Three" = { context = "TextInput" }
+            "a::Four" = { keys = ["ctrl-d"], context = "Text Input >" }
+            "a::Five" = { keys = ["ctrl-e"], context = 5 }
+
+            [amiga]
+            "a::Six" = ["ctrl-f"]
+        "#;
+        let (entries, problems) = parse(text, "linux");
+        assert_eq!(
+            problems,
+            [
+                "unknown section [amiga], expected [macos], [linux] or [windows]",
+                "a::Five: 5 isn't a key context, like \"TextInput\"",
+                "a::Four: \"Text Input >\" isn't a key context, like \"TextInput\"",
+                "a::One: expected a list of key chords, like [\"ctrl-k\"], \
+                 or { keys = [...], context = \"...\" }",
+                "a::Three: expected a list of key chords, like [\"ctrl-k\"], \
+                 or { keys = [...], context = \"...\" }",
+                "a::Two: unknown key \"nope\" in \"ctrl-shift-nope\"",
+                "a::Two: empty key chord",
+            ]
+        );
+        let loaded: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.action.as_str(), entry.chords.clone()))
+            .collect();
+        assert_eq!(loaded, [("a::Two", vec!["ctrl-b".to_string()])]);
+    }
+
+    #[test]
+    fn a_platform_entry_replaces_the_shared_one() {
+        let text = r#"
+            "text_input::Paste" = ["secondary-v"]
+
+            [macos]
+            "text_input::Paste" = { keys = ["cmd-v", "ctrl-y"], context = "TextInput" }
+        "#;
+        let (entries, _) = parse(text, "macos");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].chords, ["cmd-v", "ctrl-y"]);
+        assert_eq!(entries[0].context.as_deref(), Some("TextInput"));
+
+        let (entries, _) = parse(text, "windows");
+        assert_eq!(entries[0].chords, ["secondary-v"]);
+        assert_eq!(entries[0].context, None);
+    }
+
+    #[test]
+    fn keys_that_type_are_told_apart() {
+        assert!(types_text("?"));
+        assert!(types_text("shift-a"));
+        assert!(types_text("space"));
+        assert!(types_text("ctrl-k x"));
+        assert!(!types_text("ctrl-a"));
+        assert!(!types_text("f1"));
+        assert!(!types_text("enter"));
+    }
+}
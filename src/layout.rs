@@ -0,0 +1,245 @@
+// ======================================================================
+// LAYOUT
+// ======================================================================
+// Helpers for laying out dialog content.
+//
+// Breakpoints sort the window's width into three classes so content can
+// switch layouts as the window is resized: narrow windows stack their
+// buttons, wide ones put form fields side by side.
+//
+//     let breakpoint = Breakpoint::of(window);
+//     div().when(breakpoint.is_narrow(), |this| this.flex_col())
+//
+// GPUI renders again whenever the window is resized, so reading the
+// breakpoint in render() is all it takes to keep the layout current.
+//
+// grid() lines cells up in columns, for the label-and-field rows of
+// forms that otherwise take a nest of flex divs:
+//
+//     grid([Track::Fixed(px(80.0)), Track::Fraction(1.0)])
+//         .gap(px(8.0))
+//         .cell(label("Name"))
+//         .cell(name_input)
+//         .span(2, hint)
+//
+// Cells fill the columns left to right, starting a new row when the next
+// one doesn't fit in what's left of the current row.
+//
+// Text, and whatever should grow along with it, is sized in rems: units
+// of the root font size, 13px unless the user picks another text size
+// in the settings. rem(1.0) is the dialog's body text.
+//
+//     div().text_size(rem(0.85)).p(em(0.5, rem(0.85)))
+
+use gpui::*;
+
+use crate::settings;
+
+// Narrower than this, buttons stack vertically
+pub const NARROW_BELOW: Pixels = px(360.0);
+// From this width on, form fields go two to a row
+pub const WIDE_FROM: Pixels = px(640.0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Breakpoint {
+    Narrow,
+    Regular,
+    Wide,
+}
+
+impl Breakpoint {
+    pub fn for_width(width: Pixels) -> Self {
+        if width < NARROW_BELOW {
+            Breakpoint::Narrow
+        } else if width >= WIDE_FROM {
+            Breakpoint::Wide
+        } else {
+            Breakpoint::Regular
+        }
+    }
+
+    // The breakpoint for the window's current content width
+    pub fn of(window: &Window) -> Self {
+        Self::for_width(window.viewport_size().width)
+    }
+
+    pub fn is_narrow(self) -> bool {
+        self == Breakpoint::Narrow
+    }
+
+    pub fn is_wide(self) -> bool {
+        self == Breakpoint::Wide
+    }
+}
+
+// ======================================================================
+// GRID
+// ======================================================================
+
+// The width of a grid column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Track {
+    Fixed(Pixels),
+    // A share of the width left over by the fixed columns and the gaps
+    Fraction(f32),
+    // As wide as the cell's content. Each row sizes these on its own, so
+    // they only line up when the content is the same width.
+    Auto,
+}
+
+pub fn grid(columns: impl IntoIterator<Item = Track>) -> Grid {
+    Grid {
+        columns: columns.into_iter().collect(),
+        column_gap: px(0.0),
+        row_gap: px(0.0),
+        cells: Vec::new(),
+    }
+}
+
+#[derive(IntoElement)]
+pub struct Grid {
+    columns: Vec<Track>,
+    column_gap: Pixels,
+    row_gap: Pixels,
+    // Each cell and how many columns it spans
+    cells: Vec<(AnyElement, usize)>,
+}
+
+impl Grid {
+    // The same gap between rows and between columns
+    pub fn gap(self, gap: Pixels) -> Self {
+        self.column_gap(gap).row_gap(gap)
+    }
+
+    pub fn column_gap(mut self, gap: Pixels) -> Self {
+        self.column_gap = gap;
+        self
+    }
+
+    pub fn row_gap(mut self, gap: Pixels) -> Self {
+        self.row_gap = gap;
+        self
+    }
+
+    pub fn cell(self, child: impl IntoElement) -> Self {
+        self.span(1, child)
+    }
+
+    pub fn cells(mut self, children: impl IntoIterator<Item = impl IntoElement>) -> Self {
+        self.cells.extend(
+            children
+                .into_iter()
+                .map(|child| (child.into_any_element(), 1)),
+        );
+        self
+    }
+
+    // A cell across `columns` columns (at most the whole row)
+    pub fn span(mut self, columns: usize, child: impl IntoElement) -> Self {
+        let columns = columns.clamp(1, self.columns.len().max(1));
+        self.cells.push((child.into_any_element(), columns));
+        self
+    }
+
+    // A cell across the whole row
+    pub fn full(self, child: impl IntoElement) -> Self {
+        let columns = self.columns.len();
+        self.span(columns, child)
+    }
+
+    // Size a cell covering `tracks`: fixed widths and the gaps between
+    // them add up, fractions share out the rest of the row
+    fn sized(&self, cell: Div, tracks: &[Track]) -> Div {
+        let gaps = self.column_gap * tracks.len().saturating_sub(1) as f32;
+        let mut fixed = gaps;
+        let mut fraction = 0.0;
+        let mut auto = false;
+        for track in tracks {
+            match track {
+                Track::Fixed(width) => fixed += *width,
+                Track::Fraction(share) => fraction += share,
+                Track::Auto => auto = true,
+            }
+        }
+        if auto {
+            cell.flex_none()
+        } else if fraction > 0.0 {
+            let mut cell = cell.flex_basis(fixed).flex_shrink().min_w_0();
+            cell.style().flex_grow = Some(fraction);
+            cell
+        } else {
+            cell.flex_none().w(fixed)
+        }
+    }
+}
+
+impl RenderOnce for Grid {
+    fn render(mut self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let column_count = self.columns.len().max(1);
+
+        // Deal the cells out into rows
+        let mut rows: Vec<Vec<(AnyElement, usize)>> = Vec::new();
+        let mut used = column_count;
+        for (cell, span) in std::mem::take(&mut self.cells) {
+            if used + span > column_count {
+                rows.push(Vec::new());
+                used = 0;
+            }
+            if let Some(row) = rows.last_mut() {
+                row.push((cell, span));
+            }
+            used += span;
+        }
+
+        let rows: Vec<Div> = rows
+            .into_iter()
+            .map(|cells| {
+                let mut row = div().flex().items_center().gap(self.column_gap);
+                let mut start = 0;
+                for (cell, span) in cells {
+                    let end = (start + span).min(self.columns.len());
+                    row = row.child(self.sized(div().child(cell), &self.columns[start..end]));
+                    start = end;
+                }
+                // Empty cells keep the columns of a short row in place
+                for column in start..self.columns.len() {
+                    row = row.child(self.sized(div(), &self.columns[column..column + 1]));
+                }
+                row
+            })
+            .collect();
+
+        div().flex().flex_col().gap(self.row_gap).children(rows)
+    }
+}
+
+// ======================================================================
+// SIZING
+// ======================================================================
+
+// The root font size when nobody has changed it
+pub const DEFAULT_ROOT_FONT_SIZE: Pixels = px(13.0);
+
+// `value` times the root font size
+pub fn rem(value: f32) -> Rems {
+    rems(value)
+}
+
+// `value` times `font_size`, for spacing that should keep its proportion
+// to the text next to it
+pub fn em(value: f32, font_size: Rems) -> Rems {
+    rems(font_size.0 * value)
+}
+
+// How much bigger (or smaller) than the default the text is, for sizes
+// that can't be given in rems such as a window's
+pub fn text_scale(cx: &App) -> f32 {
+    settings::get(cx).root_font_size / DEFAULT_ROOT_FONT_SIZE
+}
+
+// Resolve rems in `window` against the user's root font size. Windows
+// call this at the start of render(), so a change shows up on the next
+// frame.
+pub fn apply_root_font_size(window: &mut Window, cx: &App) {
+    window.set_rem_size(settings::get(cx).root_font_size);
+}
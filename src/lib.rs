@@ -0,0 +1,113 @@
+// GPUI Dialogs: native-looking dialogs built with GPUI.
+//
+// The tutorial binary (src/main.rs) started out as one hard-coded dialog.
+// Its pieces live here so they can be reused and combined:
+//
+//   - audit:      an append-only record of how confirmations were answered
+//   - backdrop:   the full-screen overlay that dims everything behind a dialog
+//   - capture:    screenshots of a dialog, for documentation and bug reports
+//   - cli:        the tutorial binary's command line flags
+//   - crash:      a report dialog instead of vanishing when something panics
+//   - dialog:     the dialog itself (question, form, list, progress, ...)
+//   - error:      what can go wrong when showing one
+//   - flow:       several dialogs chained into one interaction
+//   - form:       form dialogs generated from a struct (derive(DialogForm))
+//   - gesture:    double and triple clicks, long presses
+//   - keymap:     key bindings from keymap.toml, per platform
+//   - layout:     responsive breakpoints, grids and rem sizing
+//   - manager:    coalescing repeated requests and refusing floods of them
+//   - motion:     whether the user asked for less animation
+//   - pill:       the corner pill a collapsed progress dialog folds into
+//   - power:      whether we're on battery, to go easy on it
+//   - presets:    ready-made dialogs for common jobs (printing, about, ...)
+//   - privacy:    keeping sensitive dialogs out of screen capture
+//   - rendering:  lite mode, plain rectangles for software rendering and VMs
+//   - restyle:    restyling components from the embedding app
+//   - result:     what a dialog reports back when it closes
+//   - screen:     the work area of a display, clear of menu bar and taskbar
+//   - scroll:     momentum and rubber-band overscroll for scrolling content
+//   - session:    offering again the dialogs a crash left unanswered
+//   - settings:   preferences shared by all dialogs (theme, text size, ...)
+//   - storage:    small bits of state remembered between runs
+//   - testing:    leak checks and heap counts for tests
+//   - theme:      light and dark colors, and previewing both side by side
+//   - touch:      touch mode, drag scrolling, bigger hit targets
+//   - trace:      Chrome trace files of frame times and event dispatch
+//   - components: building blocks shared by the dialogs (buttons, inputs, ...)
+//
+// Call init() once at startup, then show dialogs from anywhere you have
+// an App context.
+
+pub mod audit;
+pub mod backdrop;
+pub mod capture;
+pub mod cli;
+pub mod components;
+pub mod crash;
+pub mod dialog;
+pub mod error;
+pub mod flow;
+pub mod form;
+pub mod gesture;
+pub mod keymap;
+pub mod layout;
+pub mod manager;
+pub mod motion;
+pub mod pill;
+pub mod power;
+pub mod presets;
+mod privacy;
+pub mod rendering;
+pub mod restyle;
+pub mod result;
+pub mod screen;
+pub mod scroll;
+pub mod session;
+pub mod settings;
+mod storage;
+pub mod testing;
+pub mod theme;
+pub mod touch;
+pub mod trace;
+
+pub use backdrop::BackdropMode;
+pub use capture::{Capture, CaptureTarget};
+pub use components::{ImageSource, RowProvider};
+pub use dialog::{
+    log_channel, progress_channel, ButtonRole, ContentEvent, Dialog, DialogButton, DialogContent,
+    DialogEvent, DialogHandle, EscapePolicy, Expiry, FieldKind, FormField, ListItems, LogSender,
+    ProgressSender, ProgressUpdate,
+};
+pub use error::DialogError;
+pub use flow::{DialogFlow, FlowContext};
+pub use form::DialogForm;
+pub use layout::{em, grid, rem, Breakpoint, Grid, Track};
+pub use manager::Priority;
+// The derive macro, from the companion gpui-dialogs-derive crate
+pub use gpui_dialogs_derive::DialogForm;
+pub use presets::{
+    AboutDialog, ColorPicker, CommandPalette, DirectoryPicker, EmojiPicker, PrintDialog,
+    ShortcutsDialog,
+};
+pub use result::{DialogMetrics, DialogResult, Outcome};
+pub use settings::{ButtonOrder, Settings, ThemeChoice};
+
+use gpui::App;
+
+// Register the key bindings the components rely on (text editing keys)
+// and the ones from keymap.toml, load the settings, read the system's
+// reduced-motion setting, look for a touch screen and start watching the
+// power state
+pub fn init(cx: &mut App) {
+    components::text_input::bind_keys(cx);
+    components::tag_input::bind_keys(cx);
+    presets::emoji::bind_keys(cx);
+    keymap::load(cx);
+    settings::load(cx);
+    theme::load(cx);
+    motion::detect(cx);
+    touch::detect(cx);
+    power::watch(cx);
+    rendering::detect(cx);
+    backdrop::detect(cx);
+}
@@ -9,8 +9,10 @@
 // =======================================================================
 //
 // 1. COMPONENTS: Structs that implement the Render trait
-//      - Backdrop: A simple full-screen overlay
+//      - Backdrop: A simple full-screen overlay (src/backdrop.rs)
 //      - DialogBox: A complex component with state and event handling
+//        (src/dialog.rs)
+//      - Button, TextInput, ...: smaller building blocks (src/components/)
 //
 // 2. RENDER TRAIT: Defines how a component looks
 //      - Returns an element tree using the builder pattern
@@ -38,379 +40,237 @@
 //      - px(10.0): 10 pixels
 //      - relative(1.4): Relative to parent size (for line-height, etc)
 //
+// 8. LIBRARY: The components live in the gpui_dialogs library (src/lib.rs)
+//      - Dialog::question(), ::form(), ::progress() describe a dialog
+//      - .show() opens it and reports a DialogResult when it closes
+//      - DialogFlow chains several dialogs together
+//
 // =======================================================================
 //
 
+// How the result is reported back to scripts, the commands run after the
+// dialog closes, and the --stress test. The flags are parsed by the
+// library's cli module.
+mod hooks;
+mod output;
+mod stress;
+
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::time::Instant;
+
 // Import all GPUI types and traits
 use gpui::*;
 
-// ======================================================================
-// BACKDROP COMPONENT
-// ======================================================================
-// The Backdrop creates a semi-transparent overlay behind the dialog.
-// This is a common pattern in modal dialogs to dim the background and
-// focus user attention on the dialog itself.
-
-struct Backdrop;
+// The dialog library built alongside this tutorial
+use gpui_dialogs::audit::{FileSink, SyslogSink};
+use gpui_dialogs::cli::{self, Args};
+use gpui_dialogs::session::{self, SessionRecord};
+#[cfg(feature = "count-allocations")]
+use gpui_dialogs::testing;
+use gpui_dialogs::{backdrop, crash, rendering, theme, trace, Capture, Dialog, DialogError};
 
-// The Render trait is required for all GPUI components that display UI.
-// It has one method: render(), which returns the component's visual representation.
-
-impl Render for Backdrop {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        // div() creates a container element (similar to HTML's <div>)
-        // GPUI uses a builder pattern where you chain methods to configure the element.
-        div()
-            .size_full() // Full width and height
-            .bg(hsla(0.0, 0.0, 0.0, 0.3)) // Semi-transparent black background (30% opacity)
-                                          // hsla(hue, saturation, lightness, alpha)
-    }
-}
+// Counts the heap, for --stress's report. Only when asked for, as every
+// allocation pays for it.
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: testing::CountingAllocator = testing::CountingAllocator;
 
 // ======================================================================
-// DIALOG BOX COMPONENT
+// MAIN APPLICATION ENTRY POINT
+// ======================================================================
+// This is where we initialize the GPUI application and show our dialog.
+// Dialog::show() creates two windows for us:
+// 1. A backdrop window (full-screen, transparent overlay)
+// 2. The dialog window (centered, with the DialogBox component)
 // ======================================================================
-// The main dialog component that displays content and interactive buttons.
-
-struct DialogBox;
-
-// Implementation block for event handlers
-// In GPUI, event handlers are methods that receive events and can modify state
-
-impl DialogBox {
-    // Mouse event handler for the "OK" button
-    // Parameters:
-    // - &mut self: mutable reference to this component
-    // - MouseUpEvent: the event that triggered this handler
-    // - &mut Window: reference to the window (unuused here)
-    // - cx: Context provides access to app-level operations
-
-    fn on_ok_clicked(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
-        cx.quit(); // Quit the application
-    }
-
-    // Mouse event handler for the "Cancel" button
-    // Parameters:
-    // - &mut self: mutable reference to this component
-    // - MouseUpEvent: the event that triggered this handler
-    // - &mut Window: reference to the window (unuused here)
-    // - cx: Context provides access to app-level operations
 
-    fn on_cancel_clicked(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
-        cx.quit(); // Quit the application
+fn main() {
+    // Parse the command line before touching any windows, so a typo
+    // fails fast with a usage message
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", cli::USAGE);
+        return;
     }
-
-    // Keyboard event handler for ESC key
-    // This allows users to close the dialog with the keyboard
-    // Parameters:
-    // - &mut self: mutable reference to this component
-    // - KeyDownEvent: the event that triggered this handler
-    // - &mut Window: reference to the window (unuused here)
-    // - cx: Context provides access to app-level operations
-
-    fn on_escape(&mut self, _: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
-        cx.quit(); // Quit the application
+    if let Some(path) = &args.check_theme {
+        std::process::exit(check_theme(path));
     }
-}
 
-// Implement the Render trait to define how the dialog looks
-impl Render for DialogBox {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        // ======================================================
-        // OUTER CONTAINER
-        // ======================================================
-        // This is the full-window container that centers the dialog.
-        // We use flexbox layout (similar to CSS flexbox) to center content.
+    // A panic from here on restarts us with --crash-report, to show what
+    // happened instead of just vanishing
+    crash::install("--crash-report");
 
-        div()
-            .flex() // Enable flexbox layout
-            .size_full() // Take up the full window size
-            .justify_center() // Center content horizontally
-            .items_center() // Center content vertically
-            // Attach keyboard event handler for ESC key
-            // cx.listener() converts a method into an event listener
-            .on_key_down(cx.listener(Self::on_escape))
-            .child(
-                // ==================================================
-                // DIALOG CONTAINER
-                // ==================================================
-                // This is the actual dialog box with all its chrome (titlebar, content, buttons)
-                div()
-                    .flex()
-                    .flex_col() // Stack children vertically
-                    .rounded(px(10.0)) // 10px rounded corners
-                    .shadow_lg() // Large shadow for elevation/depth
-                    .overflow_hidden() // Clip children to rounded corners
-                    .w_full() // Fill parent width
-                    .h_full() // Fill parent height
-                    .child(
-                        // ==================================================
-                        // TITLEBAR WITH TRAFFIC LIGHTS
-                        // ==================================================
-                        // macOS dialogs have a gray titlebar with three colored buttons
-                        // (red, yellow, green) on the left side.
-                        div()
-                            .flex() // Horizontal layout
-                            .items_center() // Vertically center items
-                            .h(px(22.0)) // 22px height (macOS standard)
-                            .w_full() // Full width
-                            .bg(rgb(0xE8E8E8)) // Light gray background
-                            .border_b_1() // 1px border on bottom
-                            .border_color(rgb(0xD0D0D0)) // Darker gray border
-                            .px_3() // Horizontal padding
-                            .gap_2() // 8px gap between items
-                            .child(
-                                // RED CLOSE BUTTON
-                                // Clicking this will close the dialog
-                                div()
-                                    .w(px(12.0)) // 12px diameter
-                                    .h(px(12.0)) // 12px diameter
-                                    .rounded_full() // Fully rounded (circle)
-                                    .bg(rgb(0xFF5F57)) // Red color
-                                    .border_1() // 1px border
-                                    .border_color(rgb(0xE04943)) // Darker red border
-                                    .cursor_pointer() // Show pointer cursor on hover
-                                    // Attach click handler
-                                    .on_mouse_up(
-                                        MouseButton::Left,
-                                        cx.listener(Self::on_cancel_clicked),
-                                    ),
-                            )
-                            .child(
-                                // YELLOW MINIMIZE BUTTON
-                                // In this example, it's decorative only
-                                div()
-                                    .w(px(12.0))
-                                    .h(px(12.0))
-                                    .rounded_full()
-                                    .bg(rgb(0xFFBD2E))
-                                    .border_1()
-                                    .border_color(rgb(0xDEA123)), // Darker yellow border
-                            )
-                            .child(
-                                // GREEN MAXIMIZE BUTTON
-                                // In this example, it's decorative only
-                                div()
-                                    .w(px(12.0))
-                                    .h(px(12.0))
-                                    .rounded_full()
-                                    .bg(rgb(0x28C940))
-                                    .border_1()
-                                    .border_color(rgb(0x1AAB29)), // Darker green border
-                            ),
-                    )
-                    .child(
-                        // ==================================================
-                        // MAIN CONTENT AREA
-                        // ==================================================
-                        // This contains the dialog message and action buttons
-                        div()
-                            .flex()
-                            .flex_col() // Stack message and buttons vertically
-                            .bg(rgb(0xEFEFEF)) // Light gray background (macOS style)
-                            .flex_1() // Take up remaining space
-                            .px_6() // 24px horizontal padding
-                            .py_5() // 20px vertical padding
-                            .child(
-                                // =================================================
-                                // MESSAGE TEXT CONTAINER
-                                // =================================================
-                                div()
-                                    .flex() // Enable flex layout
-                                    .flex_1() // Expand to fill available space
-                                    .items_center() // Center text vertically
-                                    .px_3() // 12px horizontal padding
-                                    .py_4() // 16px vertical padding
-                                    .child(
-                                        // THE ACTIAL TEXT
-                                        // In GPUI, text styling is applied via methods
-                                        div()
-                                            .text_size(px(13.0)) // 13px font size
-                                            .text_color(rgb(0x000000)) // Black text color
-                                            .font_weight(FontWeight::NORMAL) // Normal weight
-                                            .line_height(relative(1.4)) // 1.4 line spacing
-                                            .child("Hello world!"), // The text content
-                                    ),
-                            )
-                            .child(
-                                // =================================================
-                                // ACTION BUTTONS CONTAINER
-                                // =================================================
-                                // macOS convention: buttons are right-aligned, with Cancel on
-                                // the left and the primary action (OK) on the right.
-                                div()
-                                    .flex() // Horizontal layout
-                                    .gap_3() // 12px gap between buttons
-                                    .justify_end() // Right-align buttons
-                                    .w_full() // Full width
-                                    .mt_3() // 12px top margin
-                                    .child(
-                                        // CANCEL BUTTON
-                                        // Secondary action button - uses white/gray style
-                                        div()
-                                            .flex()
-                                            .items_center() // Center text vertically
-                                            .justify_center() // Center text horizontally
-                                            .px_6() // 24px horizontal padding
-                                            .h(px(32.0)) // 32px height
-                                            .min_w(px(90.0)) // Minimum width 90px
-                                            .bg(rgb(0xFFFFFF)) // White background
-                                            .text_color(rgb(0x000000)) // Black text
-                                            .text_size(px(13.0)) // 13px font size
-                                            .font_weight(FontWeight::NORMAL) // Normal weight
-                                            .rounded(px(6.0)) // 6px rounded corners
-                                            .border_1() // 1px border
-                                            .border_color(rgb(0xB8B8B8)) // Gray border
-                                            .cursor_pointer() // Pointer cursor on hover
-                                            .shadow_sm() // Small shadow
-                                            // Hover state: slightly darken the background
-                                            // The closure receives a mutable style object
-                                            .hover(|style| style.bg(rgb(0xF8F8F8)))
-                                            // Attach click handler
-                                            .on_mouse_up(
-                                                MouseButton::Left,
-                                                cx.listener(Self::on_cancel_clicked),
-                                            )
-                                            .child("Cancel"), // Button text
-                                    )
-                                    .child(
-                                        // OK BUTTON
-                                        // Primary action button - uses blue style
-                                        div()
-                                            .flex()
-                                            .items_center()
-                                            .justify_center()
-                                            .px_6()
-                                            .h(px(32.0))
-                                            .min_w(px(90.0))
-                                            .bg(rgb(0x007AFF)) // Blue background
-                                            .text_color(rgb(0xFFFFFF)) // White text
-                                            .text_size(px(13.0))
-                                            .font_weight(FontWeight::NORMAL)
-                                            .rounded(px(6.0)) // 6px rounded corners
-                                            .cursor_pointer() // Pointer cursor on hover
-                                            .shadow_sm() // Small shadow
-                                            // Hover state: darken the blue background
-                                            .hover(|style| style.bg(rgb(0x0068DB)))
-                                            // Attach click handler
-                                            .on_mouse_up(
-                                                MouseButton::Left,
-                                                cx.listener(Self::on_ok_clicked),
-                                            )
-                                            .child("Ok"), // Button text
-                                    ),
-                            ),
-                    ),
-            )
-    }
-}
+    let started = Instant::now();
 
-// ======================================================================
-// MAIN APPLICATION ENTRY POINT
-// ======================================================================
-// This is where we initialize the GPUI application and create our windows.
-// A GPUI app can have multiple windows, and we're creating two:
-// 1. A backdrop window (full-screen, transparent overlay)
-// 2. The dialog window (centered, with our DialogBox component)
-// ======================================================================
-
-fn main() {
     // Create a new GPUI application and run it
     // The closure receives an App context (cx) which provides access to
     // app-level operations like creating windows, accessing displays, etc.
-    Application::new().run(|cx: &mut App| {
-        // ==================================================
-        // GET DISPLAY INFORMATION
-        // ==================================================
-        // We need to know the screen size to position our windows correctly
-
-        let displays = cx.displays(); // Get all connected displays
-        let display = displays.first().unwrap(); // Use the primary display
-        let screen_size = display.bounds().size; // Get the dimensions of the display
-
-        // ==================================================
-        // CREATE BACKDROP WINDOW
-        // ==================================================
-        // This creates a full-screen, semi transparent overlay behind the dialog.
-        // It dims the background and gives the dialog a modal appearance.
-
-        cx.open_window(
-            // WindowOptions configures how the window behaves and appears
-            WindowOptions {
-                // Position and size: full screen (covers entire display)
-                window_bounds: Some(WindowBounds::Windowed(Bounds {
-                    origin: point(px(0.0), px(0.0)), // Top-left corner
-                    size: screen_size,
-                })),
-
-                titlebar: None, // No titlebar (we want a borderless window)
-                focus: false,   // Don't steal focus (the dialog should be focused)
-                show: true,     // Make window visible immediately
-
-                // WindowKind::PopUp creates a floating window without OS chrome
-                kind: WindowKind::PopUp,
-
-                is_movable: false,              // User can't drag this window
-                display_id: Some(display.id()), // Show on the primary display
-
-                // Transparent background lets the backdrop's semi-transparent
-                // color show through
-                window_background: WindowBackgroundAppearance::Transparent,
-
-                ..Default::default() // Use default values for other options
-            },
-            // Window content factory: creates the Backdrop component
-            // |_, cx| receives (WindowHandle, Context)
-            // cx.new() creates a new component instance
-            |_, cx| cx.new(|_cx| Backdrop),
-        )
-        .unwrap(); // Panic if window creation fails (for this example)
-
-        // ==================================================
-        // CREATE DIALOG POSITION
-        // ================================================
-        // We want the dialog centered on the screen.
-
-        let dialog_width = px(460.0); // Dialog width in pixels
-        let dialog_height = px(180.0); // Dialog height in pixels
-
-        // Calculate centered position
-        let x = (screen_size.width - dialog_width) / 2.0; // Horizontal center
-        let y = (screen_size.height - dialog_height) / 2.0; // Vertical center
-
-        let dialog_bounds = Bounds {
-            origin: point(x, y),
-            size: size(dialog_width, dialog_height),
+    Application::new().run(move |cx: &mut App| {
+        // Register the key bindings used by the dialog components
+        gpui_dialogs::init(cx);
+        if let Some(path) = &args.trace {
+            trace::start(path, cx);
+        }
+        if args.lite {
+            rendering::set_lite(true, cx);
+        }
+        if let Some(mode) = args.backdrop {
+            backdrop::set_mode(mode, cx);
+        }
+
+        if let Some(path) = &args.crash_report {
+            if let Err(err) = crash::show_report(path, cx, |cx| cx.quit()) {
+                fail(err);
+            }
+            return;
+        }
+
+        // Theme authoring: just the dialog, twice over
+        if args.theme_preview {
+            let previewed = theme::preview(
+                || Dialog::question(MESSAGE),
+                cx,
+                move |result, cx| {
+                    output::report(&result, started.elapsed(), &args);
+                    cx.quit();
+                },
+            );
+            if let Err(err) = previewed {
+                fail(err);
+            }
+            return;
+        }
+
+        if let Some(count) = args.stress {
+            stress::run(count, args.stress_random, cx);
+            return;
+        }
+
+        // Prompts a crash left unanswered last time come first, if the
+        // user wants them back. Not when a script is reading our output:
+        // it's waiting for its own answer, not for old ones.
+        let unanswered = if interactive() {
+            session::unanswered()
+        } else {
+            Vec::new()
         };
+        if unanswered.is_empty() {
+            if let Err(err) = show_dialog(args, started, cx) {
+                fail(err);
+            }
+            return;
+        }
+        let restore_args = args.clone();
+        // Whoever was waiting for those answers is gone, so they aren't
+        // reported: stdout is for the answer to this run's dialog
+        let offered = session::offer_restore(unanswered, cx, rebuild, move |_results, cx| {
+            if let Err(err) = show_dialog(restore_args, started, cx) {
+                fail(err);
+            }
+        });
+        // Without the offer, go straight to our own dialog
+        if let Err(err) = offered {
+            eprintln!("couldn't offer to restore dialogs: {err}");
+            if let Err(err) = show_dialog(args, started, cx) {
+                fail(err);
+            }
+        }
+    });
+}
 
-        // ==================================================
-        // CREATE DIALOG WINDOW
-        // ==================================================
-        // This is the actual modal dialog that contains our DialogBox component
+// Run from a terminal rather than by a script capturing the result
+fn interactive() -> bool {
+    io::stdout().is_terminal()
+}
 
-        cx.open_window(
-            WindowOptions {
-                // Position and size: centered on screen
-                window_bounds: Some(WindowBounds::Windowed(dialog_bounds)),
+// Report what's wrong with a theme file, for the exit code: 0 when
+// nothing, 1 when something, 2 when it can't be read
+fn check_theme(path: &Path) -> i32 {
+    match theme::check(path) {
+        Ok(problems) if problems.is_empty() => {
+            println!("{}: ok", path.display());
+            0
+        }
+        Ok(problems) => {
+            for problem in problems {
+                println!("{}: {problem}", path.display());
+            }
+            1
+        }
+        Err(err) => {
+            eprintln!("couldn't read {}: {err}", path.display());
+            2
+        }
+    }
+}
 
-                titlebar: None, // No OS titlebar (we draw our own)
-                focus: true,    // This window should have keyboard focus
-                show: true,     // Make visible immediately
+// A dialog couldn't be shown: say why, and exit with a code the calling
+// script can tell apart from an answer
+fn fail(err: DialogError) -> ! {
+    eprintln!("error: {err}");
+    std::process::exit(err.exit_code());
+}
 
-                // PopUp windows float above other windows
-                kind: WindowKind::PopUp,
-                is_movable: false,              // User can't drag the dialog
-                display_id: Some(display.id()), // Show on primary display
+const MESSAGE: &str = "Hello world!";
 
-                // Transparent background allows our rounded corners and
-                // shadow to render properly
-                window_background: WindowBackgroundAppearance::Transparent,
+// Show the dialog. The closure runs once the user clicks a button or
+// presses ESC: we report the result, run the matching exit hook (if any),
+// then quit the application.
+fn show_dialog(args: Args, started: Instant, cx: &mut App) -> Result<(), DialogError> {
+    let mut dialog = Dialog::question(MESSAGE);
+    if interactive() {
+        dialog = dialog.restorable("question", [("message", MESSAGE)]);
+    }
+    if args.answer_keys {
+        dialog = dialog.answer_keys();
+    }
+    if let Some(timeout) = args.timeout {
+        dialog = dialog.timeout(timeout);
+    }
+    if let Some(path) = args.screenshot.clone() {
+        dialog = dialog.capture(Capture::file(&path), move |result, _| match result {
+            Ok(()) => eprintln!("saved screenshot to {}", path.display()),
+            Err(err) => eprintln!("screenshot failed: {err}"),
+        });
+    }
+    match args.audit_log.as_deref() {
+        Some("syslog") => dialog = dialog.audit("question", SyslogSink::new()),
+        Some(path) => dialog = dialog.audit("question", FileSink::new(path)),
+        None => {}
+    }
+    dialog.show(cx, move |result, cx| {
+        output::report(&result, started.elapsed(), &args);
+
+        match hooks::command_for(&result, &args).map(str::to_owned) {
+            // The log dialog opens before this dialog closes, which
+            // keeps the application alive until the log is closed
+            Some(command) if args.show_hook_output => {
+                if let Err(err) = hooks::run_in_log_dialog(&command, &result, cx, |cx| cx.quit()) {
+                    fail(err);
+                }
+            }
+            // Deferred so the dialog is gone while the command runs
+            Some(command) => cx.defer(move |cx| {
+                hooks::run(&command, &result);
+                cx.quit();
+            }),
+            None => cx.quit(),
+        }
+    })?;
+    Ok(())
+}
 
-                ..Default::default() // Default values for other options
-            },
-            // Create the DialogBox component
-            |_, cx| cx.new(|_cx| DialogBox),
-        )
-        .unwrap(); // Panic if window creation fails
-    });
+// Build a dialog left unanswered by an earlier run again
+fn rebuild(record: &SessionRecord) -> Option<Dialog> {
+    match record.template.as_str() {
+        "question" => Some(Dialog::question(record.param("message").to_string())),
+        _ => None,
+    }
 }
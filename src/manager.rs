@@ -0,0 +1,378 @@
+// ======================================================================
+// DIALOG MANAGER
+// ======================================================================
+// An app that shows dialogs on behalf of others (a daemon taking prompt
+// requests from scripts, say) can't count on them to behave. Requests
+// made through the manager instead of Dialog::show() are kept in check:
+//
+//   - Identical requests coalesce. A repeat of a dialog that is still
+//     open, or that was first requested less than `dedup_window` ago,
+//     doesn't open a second one: it gets the answer of the first, and
+//     every requester hears the same result.
+//   - Floods are refused. Past `max_dialogs` new dialogs within `per`,
+//     request() fails with DialogError::RateLimited, so a script stuck
+//     in a loop can't bury the screen in prompts.
+//
+//     manager::request(Dialog::question("Disk almost full"), cx, |result, cx| {
+//         ...
+//     })?;
+//
+// Requests are identical when their title, message, buttons and fields
+// match. Progress, log and custom dialogs are never coalesced, as each
+// is fed by its own requester.
+//
+// Dialogs can also be given a priority. Only the most urgent of the
+// dialogs the manager has open are on screen: a critical error preempts
+// an informational note, which is hidden (not closed) until the error is
+// answered and then comes back as it was left, typed text and all. Its
+// backdrop goes while it's hidden, and its timeout and max age don't run.
+//
+//     manager::request(Dialog::question(error).priority(Priority::Critical), cx, ...)?;
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use gpui::{App, Global};
+
+use crate::dialog::{Dialog, DialogHandle, ResolveCallback};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Critical,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    pub dedup_window: Duration,
+    pub max_dialogs: usize,
+    pub per: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            dedup_window: Duration::from_secs(2),
+            max_dialogs: 5,
+            per: Duration::from_secs(10),
+        }
+    }
+}
+
+struct Entry {
+    requested: Instant,
+    state: EntryState,
+}
+
+enum EntryState {
+    // Everyone waiting for the answer, the first requester included
+    Open(Vec<ResolveCallback>),
+    Answered(DialogResult),
+}
+
+#[derive(Default)]
+struct Manager {
+    limits: Limits,
+    // Coalescable dialogs by their key
+    entries: HashMap<String, Entry>,
+    // When each recent dialog opened, oldest first
+    opened: VecDeque<Instant>,
+    // The dialogs we have open, each under a serial number
+    shown: Vec<(usize, Priority, DialogHandle)>,
+    next_serial: usize,
+}
+
+impl Global for Manager {}
+
+impl Manager {
+    // Drop what's too old to matter to the next request
+    fn expire(&mut self, now: Instant) {
+        let limits = self.limits;
+        while self
+            .opened
+            .front()
+            .is_some_and(|opened| now.duration_since(*opened) >= limits.per)
+        {
+            self.opened.pop_front();
+        }
+        self.entries.retain(|_, entry| {
+            matches!(entry.state, EntryState::Open(_))
+                || now.duration_since(entry.requested) < limits.dedup_window
+        });
+    }
+}
+
+pub fn limits(cx: &App) -> Limits {
+    cx.try_global::<Manager>()
+        .map(|manager| manager.limits)
+        .unwrap_or_default()
+}
+
+pub fn set_limits(limits: Limits, cx: &mut App) {
+    cx.default_global::<Manager>().limits = limits;
+}
+
+// Show `dialog` unless an identical one can answer for it, or too many
+// have opened lately. on_resolve runs once with the answer, like with
+// Dialog::show().
+pub fn request(
+    dialog: Dialog,
+    cx: &mut App,
+    on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+) -> Result<(), DialogError> {
+    dialog.validate()?;
+    let key = dialog.dedup_key();
+    let now = Instant::now();
+    let manager = cx.default_global::<Manager>();
+    manager.expire(now);
+
+    if let Some(entry) = key.as_ref().and_then(|key| manager.entries.get_mut(key)) {
+        match &mut entry.state {
+            EntryState::Open(waiters) => waiters.push(Box::new(on_resolve)),
+            EntryState::Answered(result) => {
+                let result = result.clone();
+                cx.defer(move |cx| on_resolve(result, cx));
+            }
+        }
+        return Ok(());
+    }
+
+    if manager.opened.len() >= manager.limits.max_dialogs {
+        return Err(DialogError::RateLimited);
+    }
+
+    // Only what actually opens counts towards the limit
+    let Some(key) = key else {
+        show(dialog, cx, on_resolve)?;
+        cx.default_global::<Manager>().opened.push_back(now);
+        return Ok(());
+    };
+    manager.entries.insert(
+        key.clone(),
+        Entry {
+            requested: now,
+            state: EntryState::Open(vec![Box::new(on_resolve)]),
+        },
+    );
+    let shown_key = key.clone();
+    let shown = show(dialog, cx, move |result, cx| {
+        let manager = cx.default_global::<Manager>();
+        let Some(entry) = manager.entries.get_mut(&shown_key) else {
+            return;
+        };
+        let state = std::mem::replace(&mut entry.state, EntryState::Answered(result.clone()));
+        if let EntryState::Open(waiters) = state {
+            for waiter in waiters {
+                waiter(result.clone(), cx);
+            }
+        }
+    });
+    let manager = cx.default_global::<Manager>();
+    if let Err(err) = shown {
+        manager.entries.remove(&key);
+        return Err(err);
+    }
+    manager.opened.push_back(now);
+    Ok(())
+}
+
+// Show a dialog and keep track of it until it's answered, hiding it or
+// others by priority
+fn show(
+    dialog: Dialog,
+    cx: &mut App,
+    on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+) -> Result<(), DialogError> {
+    let priority = dialog.priority;
+    let manager = cx.default_global::<Manager>();
+    let serial = manager.next_serial;
+    manager.next_serial += 1;
+
+    let handle = dialog.show(cx, move |result, cx| {
+        cx.default_global::<Manager>()
+            .shown
+            .retain(|(shown, ..)| *shown != serial);
+        on_resolve(result, cx);
+        // Whatever this one preempted comes back
+        arrange(cx);
+    })?;
+    cx.default_global::<Manager>()
+        .shown
+        .push((serial, priority, handle));
+    arrange(cx);
+    // Opened behind a more urgent dialog: that one keeps the focus
+    let top = cx
+        .default_global::<Manager>()
+        .shown
+        .iter()
+        .max_by_key(|(_, priority, _)| *priority)
+        .filter(|(_, top, _)| *top > priority)
+        .map(|(_, _, handle)| *handle);
+    if let Some(top) = top {
+        top.activate(cx);
+    }
+    Ok(())
+}
+
+// Hide every dialog less urgent than the most urgent one open, and show
+// the rest
+fn arrange(cx: &mut App) {
+    let shown = cx.default_global::<Manager>().shown.clone();
+    let Some(top) = shown.iter().map(|(_, priority, _)| *priority).max() else {
+        return;
+    };
+    for (_, priority, handle) in shown {
+        handle.set_preempted(priority < top, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeSet;
+    use std::rc::Rc;
+
+    use gpui::{App, TestAppContext, WindowHandle};
+
+    use super::{request, set_limits, Limits, Priority};
+    use crate::dialog::{Dialog, DialogBox, DialogHandle};
+    use crate::error::DialogError;
+    use crate::result::{DialogResult, Outcome};
+    use crate::settings::Settings;
+    use crate::testing::{assert_no_entity_leaks, entity_leaks};
+
+    type Answers = Rc<RefCell<Vec<DialogResult>>>;
+
+    fn record(answers: &Answers) -> impl FnOnce(DialogResult, &mut App) + 'static {
+        let answers = answers.clone();
+        move |result, _| answers.borrow_mut().push(result)
+    }
+
+    // The dialog windows open, oldest first
+    fn dialogs(cx: &App) -> Vec<WindowHandle<DialogBox>> {
+        let mut windows = cx.windows();
+        windows.sort_by_key(|window| window.window_id().as_u64());
+        windows
+            .iter()
+            .filter_map(|window| window.downcast::<DialogBox>())
+            .collect()
+    }
+
+    fn close_all(cx: &mut TestAppContext) {
+        for dialog in cx.update(|cx| dialogs(cx)) {
+            cx.update(|cx| DialogHandle::new(dialog).close(Outcome::Dismissed, cx));
+            cx.run_until_parked();
+        }
+        cx.update(assert_no_entity_leaks);
+    }
+
+    #[gpui::test]
+    fn identical_requests_share_one_dialog_and_its_answer(cx: &mut TestAppContext) {
+        let answers = Answers::default();
+        for _ in 0..2 {
+            cx.update(|cx| request(Dialog::question("Disk almost full"), cx, record(&answers)))
+                .unwrap();
+        }
+        cx.run_until_parked();
+        let open = cx.update(|cx| dialogs(cx));
+        assert_eq!(open.len(), 1);
+
+        cx.update(|cx| DialogHandle::new(open[0]).close(Outcome::Dismissed, cx));
+        cx.run_until_parked();
+        assert_eq!(answers.borrow().len(), 2);
+        assert!(answers.borrow().iter().all(DialogResult::is_dismissed));
+
+        // Asked again soon after: the same answer, without a dialog
+        cx.update(|cx| request(Dialog::question("Disk almost full"), cx, record(&answers)))
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(answers.borrow().len(), 3);
+        assert!(answers.borrow()[2].is_dismissed());
+        assert!(cx.update(|cx| dialogs(cx)).is_empty());
+        cx.update(assert_no_entity_leaks);
+    }
+
+    #[gpui::test]
+    fn floods_are_refused(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            set_limits(
+                Limits {
+                    max_dialogs: 2,
+                    ..Limits::default()
+                },
+                cx,
+            );
+            cx.set_global(Settings {
+                suppressed: BTreeSet::from(["tip".to_string()]),
+                ..Settings::default()
+            });
+        });
+        let answers = Answers::default();
+        let ask = |message: &str, cx: &mut TestAppContext| {
+            let dialog = Dialog::question(message.to_string()).suppressible(message);
+            cx.update(|cx| request(dialog, cx, record(&answers)))
+        };
+
+        // Dialogs that don't open don't count
+        assert!(matches!(ask("tip", cx), Err(DialogError::Suppressed)));
+        ask("One", cx).unwrap();
+        ask("Two", cx).unwrap();
+        assert!(matches!(ask("Three", cx), Err(DialogError::RateLimited)));
+        // A repeat isn't a new dialog
+        ask("Two", cx).unwrap();
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| dialogs(cx)).len(), 2);
+
+        close_all(cx);
+        assert_eq!(answers.borrow().len(), 3);
+    }
+
+    #[gpui::test]
+    fn urgent_dialogs_hide_the_others_until_answered(cx: &mut TestAppContext) {
+        let answers = Answers::default();
+        let note = Dialog::question("Update available").priority(Priority::Low);
+        cx.update(|cx| request(note, cx, record(&answers))).unwrap();
+        cx.run_until_parked();
+        let error = Dialog::question("Disk failing").priority(Priority::Critical);
+        cx.update(|cx| request(error, cx, record(&answers)))
+            .unwrap();
+        cx.run_until_parked();
+
+        let open = cx.update(|cx| dialogs(cx));
+        let preempted = |cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                open.iter()
+                    .map(|dialog| dialog.read(cx).is_ok_and(DialogBox::is_preempted))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(preempted(cx), [true, false]);
+        // Only the error's backdrop is left
+        assert_eq!(
+            cx.update(|cx| entity_leaks(cx)),
+            [
+                "2 dialog window(s) still open",
+                "1 backdrop window(s) still open"
+            ]
+        );
+
+        cx.update(|cx| DialogHandle::new(open[1]).close(Outcome::Dismissed, cx));
+        cx.run_until_parked();
+        assert_eq!(answers.borrow().len(), 1);
+        assert!(!preempted(cx)[0]);
+        assert_eq!(
+            cx.update(|cx| entity_leaks(cx)),
+            [
+                "1 dialog window(s) still open",
+                "1 backdrop window(s) still open"
+            ]
+        );
+
+        close_all(cx);
+    }
+}
@@ -0,0 +1,52 @@
+// ======================================================================
+// REDUCED MOTION
+// ======================================================================
+// Some people get dizzy or distracted by things moving on screen and ask
+// their system for less motion. Decorative animation (an animated
+// picture, say) checks reduced_motion() and holds still when it's set.
+//
+// init() reads the system setting once at startup; an app with its own
+// preference can override it:
+//
+//     motion::set_reduced_motion(true, cx);
+
+use std::process::Command;
+
+use gpui::{App, Global};
+
+struct ReducedMotion(bool);
+
+impl Global for ReducedMotion {}
+
+pub fn reduced_motion(cx: &App) -> bool {
+    cx.try_global::<ReducedMotion>()
+        .is_some_and(|reduced| reduced.0)
+}
+
+pub fn set_reduced_motion(reduced: bool, cx: &mut App) {
+    cx.set_global(ReducedMotion(reduced));
+}
+
+// Ask the system, quietly answering "no" when we can't tell
+pub(crate) fn detect(cx: &mut App) {
+    let output = |program: &str, args: &[&str]| {
+        let output = Command::new(program).args(args).output().ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let reduced = if cfg!(target_os = "macos") {
+        output(
+            "defaults",
+            &["read", "com.apple.universalaccess", "reduceMotion"],
+        )
+        .is_some_and(|value| value == "1")
+    } else if cfg!(target_os = "linux") {
+        output(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "enable-animations"],
+        )
+        .is_some_and(|value| value == "false")
+    } else {
+        false
+    };
+    set_reduced_motion(reduced, cx);
+}
@@ -0,0 +1,85 @@
+// ======================================================================
+// RESULT OUTPUT
+// ======================================================================
+// Prints the dialog result for the calling script, and optionally writes
+// the same text to a file.
+//
+// The file is written atomically (to a temporary file next to it, then
+// renamed into place), so a script polling for it never reads half a
+// result. It is written before stdout: if the parent has stopped reading
+// our stdout (say, a shell timeout killed the pipe), the file still
+// arrives, and the failed stdout write is ignored rather than panicking
+// the way println! would.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use gpui_dialogs::cli::{Args, ResultFormat};
+use gpui_dialogs::{DialogResult, Outcome};
+use serde_json::json;
+
+pub fn report(result: &DialogResult, elapsed: Duration, args: &Args) {
+    let text = format_result(result, elapsed, args.result_format);
+
+    if let Some(path) = &args.result_file {
+        if let Err(err) = write_atomically(path, &text) {
+            eprintln!("failed to write result to {}: {err}", path.display());
+        }
+    }
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(text.as_bytes()).ok();
+    stdout.flush().ok();
+}
+
+pub fn format_result(result: &DialogResult, elapsed: Duration, format: ResultFormat) -> String {
+    let elapsed_ms = elapsed.as_millis();
+
+    match format {
+        ResultFormat::Plain => {
+            // First line: the button, or what happened instead
+            let mut text = match &result.outcome {
+                Outcome::Button { label, .. } => label.to_string(),
+                Outcome::Dismissed => "dismissed".to_string(),
+                Outcome::Completed => "completed".to_string(),
+                Outcome::TimedOut => "timeout".to_string(),
+                Outcome::Expired => "expired".to_string(),
+            };
+            text.push('\n');
+            for (key, value) in &result.values {
+                text.push_str(&format!("{key}={value}\n"));
+            }
+            text.push_str(&format!("elapsed_ms={elapsed_ms}\n"));
+            text
+        }
+        ResultFormat::Json => {
+            // The result as the library serializes it, plus a couple of
+            // conveniences for scripts
+            let mut value = serde_json::to_value(result).unwrap_or_default();
+            if let Some(object) = value.as_object_mut() {
+                object.insert("confirmed".into(), result.is_confirmed().into());
+                object.insert("elapsed_ms".into(), json!(elapsed_ms));
+            }
+            format!("{value}\n")
+        }
+    }
+}
+
+// Write to `<path>.tmp`, flush it to disk, then rename over `path`.
+// A rename within one directory is atomic on every platform we run on.
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        fs::remove_file(&tmp_path).ok();
+    })
+}
@@ -0,0 +1,158 @@
+// ======================================================================
+// PROGRESS PILL
+// ======================================================================
+// A long operation doesn't need a dialog in the middle of the screen the
+// whole time. A collapsible progress dialog folds away into this pill, a
+// small window docked in the bottom-right corner of the work area that
+// shows how far along the work is:
+//
+//     Dialog::progress("Copying files…", updates).collapsible()
+//
+// The dialog's yellow titlebar button collapses it (as does
+// DialogHandle::collapse()). Clicking the pill brings the dialog back as
+// it was; its × presses the dialog's Cancel button. The pill goes away
+// with the dialog once the work is done.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::backdrop::primary_display;
+use crate::dialog::DialogBox;
+use crate::error::DialogError;
+use crate::testing::{Kind, Live};
+use crate::{rendering, screen};
+
+const PILL_SIZE: Size<Pixels> = size(px(200.0), px(36.0));
+// Room between the pill and the edges of the work area
+const MARGIN: Pixels = px(16.0);
+
+pub struct ProgressPill {
+    dialog: WindowHandle<DialogBox>,
+    _progress: Option<Subscription>,
+    _live: Live,
+}
+
+impl ProgressPill {
+    fn new(dialog: WindowHandle<DialogBox>, cx: &mut Context<Self>) -> Self {
+        // Redraw along with the dialog, which is where the progress lives
+        let progress = dialog
+            .entity(cx)
+            .ok()
+            .map(|entity| cx.observe(&entity, |_, _, cx| cx.notify()));
+        Self {
+            dialog,
+            _progress: progress,
+            _live: Live::new(Kind::Pill),
+        }
+    }
+}
+
+impl Render for ProgressPill {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let fraction = self
+            .dialog
+            .read(cx)
+            .ok()
+            .and_then(DialogBox::progress_fraction)
+            .unwrap_or(0.0);
+        let dialog = self.dialog;
+
+        div()
+            .id("pill")
+            .size_full()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_3()
+            .bg(rgb(0xFFFFFF))
+            .border_1()
+            .border_color(rgb(0xB8B8B8))
+            .when(!rendering::lite(cx), |this| this.rounded_full().shadow_md())
+            .text_size(px(12.0))
+            .cursor_pointer()
+            .on_click(move |_, _, cx| {
+                dialog
+                    .update(cx, |this, window, cx| this.expand(window, cx))
+                    .ok();
+            })
+            .child(
+                div()
+                    .flex_1()
+                    .h(px(4.0))
+                    .rounded_full()
+                    .bg(rgb(0xE0E0E0))
+                    .child(
+                        div()
+                            .h_full()
+                            .rounded_full()
+                            .bg(rgb(0x007AFF))
+                            .w(relative(fraction)),
+                    ),
+            )
+            .child(
+                div()
+                    .w(px(34.0))
+                    .text_color(rgb(0x4D4D4D))
+                    .child(format!("{:.0}%", fraction * 100.0)),
+            )
+            .child(
+                div()
+                    .id("cancel")
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .size(px(18.0))
+                    .rounded_full()
+                    .text_color(rgb(0x8E8E8E))
+                    .hover(|style| style.bg(rgb(0xECECEC)).text_color(rgb(0x000000)))
+                    .on_click(move |_, _, cx| {
+                        // Not a click on the pill as well
+                        cx.stop_propagation();
+                        dialog
+                            .update(cx, |this, window, cx| this.press_cancel(window, cx))
+                            .ok();
+                    })
+                    .child("×"),
+            )
+    }
+}
+
+// Open the pill for `dialog` in the corner of the work area
+pub(crate) fn open(
+    dialog: WindowHandle<DialogBox>,
+    cx: &mut App,
+) -> Result<WindowHandle<ProgressPill>, DialogError> {
+    let display = primary_display(cx)?;
+    let area = screen::work_area(display.as_ref());
+    let bounds = Bounds {
+        origin: point(
+            area.right() - PILL_SIZE.width - MARGIN,
+            area.bottom() - PILL_SIZE.height - MARGIN,
+        ),
+        size: PILL_SIZE,
+    };
+    cx.open_window(
+        WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(bounds)),
+            titlebar: None,
+            // The work goes on in the background; so does whatever the
+            // user was doing
+            focus: false,
+            show: true,
+            kind: WindowKind::PopUp,
+            is_movable: false,
+            display_id: Some(display.id()),
+            window_background: WindowBackgroundAppearance::Transparent,
+            ..Default::default()
+        },
+        |_, cx| cx.new(|cx| ProgressPill::new(dialog, cx)),
+    )
+    .map_err(|err| DialogError::WindowCreation(err.to_string()))
+}
+
+// Deferred, as this is usually called from a click on the pill itself
+pub(crate) fn close(pill: WindowHandle<ProgressPill>, cx: &mut App) {
+    cx.defer(move |cx| {
+        pill.update(cx, |_, window, _| window.remove_window()).ok();
+    });
+}
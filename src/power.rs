@@ -0,0 +1,132 @@
+// ======================================================================
+// POWER
+// ======================================================================
+// Whether the computer is running on battery or in a low-power mode, so
+// dialogs can go easy on it: animations run at a lower frame rate (or
+// hold still), the dialog's soft blurred shadow gives way to a plain
+// border, and timeouts are doubled since a laptop on battery is more
+// likely to be set aside mid-question.
+//
+//     if power::saving(cx) { ... }
+//
+// init() reads the state at startup and checks again every half minute:
+// /sys/class/power_supply and the ACPI platform profile on Linux, pmset on
+// macOS. Elsewhere, or when those can't be read, we assume mains power.
+
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+use gpui::{App, AppContext, Global};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PowerState {
+    pub on_battery: bool,
+    // macOS Low Power Mode, or the "low-power" platform profile on Linux
+    pub low_power: bool,
+}
+
+impl PowerState {
+    pub fn saving(&self) -> bool {
+        self.on_battery || self.low_power
+    }
+}
+
+impl Global for PowerState {}
+
+pub fn state(cx: &App) -> PowerState {
+    cx.try_global::<PowerState>().copied().unwrap_or_default()
+}
+
+// Shorthand for state(cx).saving()
+pub fn saving(cx: &App) -> bool {
+    state(cx).saving()
+}
+
+// Override what was detected, for an app that knows better
+pub fn set_state(state: PowerState, cx: &mut App) {
+    cx.set_global(state);
+    cx.refresh_windows();
+}
+
+// Read the state now and keep it current
+pub(crate) fn watch(cx: &mut App) {
+    cx.set_global(detect());
+    cx.spawn(async move |cx| loop {
+        cx.background_executor().timer(POLL_INTERVAL).await;
+        let detected = cx.background_spawn(async { detect() }).await;
+        let changed = cx.update(|cx| {
+            if state(cx) != detected {
+                set_state(detected, cx);
+            }
+        });
+        if changed.is_err() {
+            return; // The app is shutting down
+        }
+    })
+    .detach();
+}
+
+fn detect() -> PowerState {
+    if cfg!(target_os = "linux") {
+        detect_linux()
+    } else if cfg!(target_os = "macos") {
+        detect_macos()
+    } else {
+        PowerState::default()
+    }
+}
+
+fn detect_linux() -> PowerState {
+    let low_power = fs::read_to_string("/sys/firmware/acpi/platform_profile")
+        .is_ok_and(|profile| profile.trim() == "low-power");
+    let mut on_battery = false;
+    for supply in fs::read_dir("/sys/class/power_supply")
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        let path = supply.path();
+        let attribute = |name: &str| {
+            fs::read_to_string(path.join(name))
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        };
+        match attribute("type").as_str() {
+            // Plugged in: that settles it
+            "Mains" if attribute("online") == "1" => {
+                on_battery = false;
+                break;
+            }
+            "Battery" if attribute("status") == "Discharging" => on_battery = true,
+            _ => {}
+        }
+    }
+    PowerState {
+        on_battery,
+        low_power,
+    }
+}
+
+fn detect_macos() -> PowerState {
+    let pmset = |args: &[&str]| {
+        let output = Command::new("pmset").args(args).output().ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    // "Now drawing from 'Battery Power'"
+    let on_battery = pmset(&["-g", "batt"]).is_some_and(|text| text.contains("'Battery Power'"));
+    // " lowpowermode         1"
+    let low_power = pmset(&["-g"]).is_some_and(|text| {
+        text.lines().any(|line| {
+            let mut words = line.split_whitespace();
+            words.next() == Some("lowpowermode") && words.next() == Some("1")
+        })
+    });
+    PowerState {
+        on_battery,
+        low_power,
+    }
+}
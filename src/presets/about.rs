@@ -0,0 +1,245 @@
+// ======================================================================
+// ABOUT DIALOG PRESET
+// ======================================================================
+// The "About <App>" window every application needs: icon, name, version
+// (with a Copy button for bug reports), authors, license, a homepage link
+// and the credits in a scrolling box.
+//
+//     AboutDialog::new("Notes", "1.4.2", ["Ada", "Grace"], "MIT")
+//         .icon("assets/notes.png")
+//         .homepage("https://example.com/notes")
+//         .credits(["gpui by Zed Industries"])
+//         .show(cx, |_, _| {});
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::{Button, ImageSource, ImageView};
+use crate::dialog::{ContentEvent, Dialog, DialogButton, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+
+#[derive(Clone)]
+pub struct AboutDialog {
+    app_name: SharedString,
+    version: SharedString,
+    authors: Vec<SharedString>,
+    license: SharedString,
+    icon: Option<ImageSource>,
+    homepage: Option<SharedString>,
+    credits: Vec<SharedString>,
+}
+
+impl AboutDialog {
+    pub fn new(
+        app_name: impl Into<SharedString>,
+        version: impl Into<SharedString>,
+        authors: impl IntoIterator<Item = impl Into<SharedString>>,
+        license: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            app_name: app_name.into(),
+            version: version.into(),
+            authors: authors.into_iter().map(Into::into).collect(),
+            license: license.into(),
+            icon: None,
+            homepage: None,
+            credits: Vec::new(),
+        }
+    }
+
+    // A picture to show as the app icon (a file, a URL or bytes). Without
+    // one, or if it can't be loaded, the first letter of the app name
+    // stands in.
+    pub fn icon(mut self, icon: impl Into<ImageSource>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    // A URL opened in the browser when the link is clicked
+    pub fn homepage(mut self, url: impl Into<SharedString>) -> Self {
+        self.homepage = Some(url.into());
+        self
+    }
+
+    // One line per credit (libraries, artwork, translators, ...)
+    pub fn credits(mut self, credits: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.credits = credits.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        let height = if self.credits.is_empty() {
+            330.0
+        } else {
+            440.0
+        };
+        let title = format!("About {}", self.app_name);
+
+        // The header replaces the message, so it stays empty
+        Dialog::custom("", move |_window, cx| cx.new(|_| AboutPanel::new(self)))
+            .title(title)
+            .buttons([DialogButton::primary("Ok")])
+            .size(size(px(400.0), px(height)))
+            .show(cx, on_resolve)
+    }
+
+    // What the Copy button puts on the clipboard: enough to identify the
+    // build in a bug report
+    fn version_info(&self) -> String {
+        format!(
+            "{} {} ({} {})",
+            self.app_name,
+            self.version,
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    }
+}
+
+// ======================================================================
+// ABOUT PANEL COMPONENT
+// ======================================================================
+
+struct AboutPanel {
+    about: AboutDialog,
+    // Set once the version has been copied, to confirm it on the button
+    copied: bool,
+}
+
+impl EventEmitter<ContentEvent> for AboutPanel {}
+
+impl DialogContent for AboutPanel {}
+
+impl AboutPanel {
+    fn new(about: AboutDialog) -> Self {
+        Self {
+            about,
+            copied: false,
+        }
+    }
+
+    fn copy_version(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(self.about.version_info()));
+        self.copied = true;
+        cx.notify();
+    }
+
+    fn render_icon(&self) -> AnyElement {
+        let app_name = self.about.app_name.clone();
+        match &self.about.icon {
+            Some(icon) => ImageView::new(icon.clone())
+                .size(px(64.0), px(64.0))
+                .fallback(move |_| render_initial(&app_name))
+                .into_any_element(),
+            None => render_initial(&app_name),
+        }
+    }
+}
+
+// The app name's first letter on a blue square, for apps without an icon
+fn render_initial(app_name: &str) -> AnyElement {
+    let initial = app_name
+        .chars()
+        .next()
+        .map(|letter| letter.to_uppercase().to_string())
+        .unwrap_or_default();
+    div()
+        .flex()
+        .items_center()
+        .justify_center()
+        .size(px(64.0))
+        .rounded(px(14.0))
+        .bg(rgb(0x007AFF))
+        .text_color(rgb(0xFFFFFF))
+        .text_size(px(30.0))
+        .font_weight(FontWeight::BOLD)
+        .child(initial)
+        .into_any_element()
+}
+
+impl Render for AboutPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let about = &self.about;
+        let secondary_text = |text: SharedString| {
+            div()
+                .text_size(px(12.0))
+                .text_color(rgb(0x4D4D4D))
+                .child(text)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .gap_1()
+            .size_full()
+            .pt_2()
+            .child(self.render_icon())
+            .child(
+                div()
+                    .mt_2()
+                    .text_size(px(17.0))
+                    .font_weight(FontWeight::BOLD)
+                    .child(about.app_name.clone()),
+            )
+            .child(
+                // Version, with a button to copy the full version info
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(secondary_text(format!("Version {}", about.version).into()))
+                    .child(
+                        Button::new(if self.copied { "Copied" } else { "Copy" })
+                            .on_click(cx.listener(Self::copy_version)),
+                    ),
+            )
+            .when(!about.authors.is_empty(), |this| {
+                this.child(secondary_text(
+                    format!("By {}", about.authors.join(", ")).into(),
+                ))
+            })
+            .child(secondary_text(
+                format!("Licensed under {}", about.license).into(),
+            ))
+            .when_some(about.homepage.clone(), |this, url| {
+                // HOMEPAGE LINK
+                // Styled like a web link, opened in the default browser
+                this.child(
+                    div()
+                        .id("homepage")
+                        .text_size(px(12.0))
+                        .text_color(rgb(0x007AFF))
+                        .cursor_pointer()
+                        .hover(|style| style.underline())
+                        .on_click(move |_, _, cx| cx.open_url(&url))
+                        .child(about.homepage.clone().unwrap_or_default()),
+                )
+            })
+            .when(!about.credits.is_empty(), |this| {
+                // CREDITS
+                // A white scrolling box, in case the list is long
+                this.child(
+                    div()
+                        .id("credits")
+                        .mt_3()
+                        .w_full()
+                        .flex_1()
+                        .p_2()
+                        .overflow_y_scroll()
+                        .bg(rgb(0xFFFFFF))
+                        .border_1()
+                        .border_color(rgb(0xB8B8B8))
+                        .rounded(px(5.0))
+                        .text_size(px(12.0))
+                        .line_height(relative(1.4))
+                        .children(about.credits.iter().map(|line| div().child(line.clone()))),
+                )
+            })
+    }
+}
@@ -0,0 +1,215 @@
+// ======================================================================
+// COLOR PICKER PRESET
+// ======================================================================
+// The full color picker: a spectrum of hues and shades to click, and a
+// hex field for exact values. The chosen color comes back as "#RRGGBB" in
+// values["color"]:
+//
+//     ColorPicker::new().color(rgb(0x007AFF)).show(cx, |result, _| { ... });
+//
+// For quick choices inside a form, see the ColorSwatches component, whose
+// "Other…" chip opens this dialog.
+
+use std::collections::BTreeMap;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::color_swatches::{parse_hex, to_hex};
+use crate::components::TextInput;
+use crate::dialog::{ContentEvent, Dialog, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+
+// The spectrum: one column per hue, one row per lightness, then a row of
+// grays
+const HUES: usize = 12;
+const LIGHTNESS: [f32; 7] = [0.9, 0.8, 0.68, 0.56, 0.44, 0.32, 0.2];
+
+pub struct ColorPicker {
+    color: Rgba,
+}
+
+impl Default for ColorPicker {
+    fn default() -> Self {
+        Self {
+            color: rgb(0x007AFF),
+        }
+    }
+}
+
+impl ColorPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The color the picker starts from
+    pub fn color(mut self, color: impl Into<Rgba>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        let initial = self.color;
+        Dialog::custom("", move |_window, cx| {
+            cx.new(|cx| PickerPanel::new(initial, cx))
+        })
+        .title("Colors")
+        .size(size(px(420.0), px(400.0)))
+        .show(cx, on_resolve)
+    }
+}
+
+fn spectrum() -> Vec<Vec<Rgba>> {
+    let mut rows: Vec<Vec<Rgba>> = LIGHTNESS
+        .iter()
+        .map(|&lightness| {
+            (0..HUES)
+                .map(|hue| hsla(hue as f32 / HUES as f32, 0.85, lightness, 1.0).into())
+                .collect()
+        })
+        .collect();
+    rows.push(
+        (0..HUES)
+            .map(|step| {
+                let lightness = 1.0 - step as f32 / (HUES - 1) as f32;
+                hsla(0.0, 0.0, lightness, 1.0).into()
+            })
+            .collect(),
+    );
+    rows
+}
+
+// ======================================================================
+// PICKER PANEL COMPONENT
+// ======================================================================
+
+struct PickerPanel {
+    initial: Rgba,
+    hex: Entity<TextInput>,
+    spectrum: Vec<Vec<Rgba>>,
+}
+
+impl EventEmitter<ContentEvent> for PickerPanel {}
+
+impl PickerPanel {
+    fn new(initial: Rgba, cx: &mut Context<Self>) -> Self {
+        let hex = cx.new(|cx| {
+            let mut input = TextInput::new(cx).placeholder("#RRGGBB");
+            input.set_text(to_hex(initial), cx);
+            input
+        });
+        cx.observe(&hex, |_, _, cx| cx.notify()).detach();
+
+        Self {
+            initial,
+            hex,
+            spectrum: spectrum(),
+        }
+    }
+
+    // The color in the hex field, if it parses
+    fn color(&self, cx: &App) -> Option<Rgba> {
+        parse_hex(self.hex.read(cx).text())
+    }
+
+    fn set_color(&mut self, color: Rgba, cx: &mut Context<Self>) {
+        self.hex
+            .update(cx, |input, cx| input.set_text(to_hex(color), cx));
+    }
+}
+
+impl Render for PickerPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let current = self.color(cx);
+        // Compared as hex, since the spectrum's colors don't round-trip
+        let current_hex = current.map(to_hex);
+
+        let grid = self.spectrum.iter().enumerate().map(|(row, colors)| {
+            div()
+                .flex()
+                .children(colors.iter().enumerate().map(|(column, color)| {
+                    let color = *color;
+                    let chosen = current_hex == Some(to_hex(color));
+                    div()
+                        .id(row * HUES + column)
+                        .size(px(24.0))
+                        .bg(color)
+                        .cursor_pointer()
+                        .when(chosen, |cell| cell.border_2().border_color(rgb(0xFFFFFF)))
+                        .on_click(cx.listener(move |this, event: &ClickEvent, _, cx| {
+                            this.set_color(color, cx);
+                            // Double-click picks the color and closes
+                            if event.click_count() > 1 {
+                                cx.emit(ContentEvent::Confirm);
+                            }
+                        }))
+                }))
+        });
+
+        // Before and after, side by side
+        let preview = div()
+            .flex()
+            .w(px(64.0))
+            .h(px(32.0))
+            .rounded(px(6.0))
+            .overflow_hidden()
+            .border_1()
+            .border_color(rgb(0xB8B8B8))
+            .child(div().flex_1().h_full().bg(self.initial))
+            .child(div().flex_1().h_full().bg(current.unwrap_or(self.initial)));
+
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .gap_3()
+            .size_full()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .rounded(px(5.0))
+                    .overflow_hidden()
+                    .children(grid),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .w(px(288.0))
+                    .child(preview)
+                    .child(div().flex_1().child(self.hex.clone())),
+            )
+            .when(current.is_none(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(11.0))
+                        .text_color(rgb(0xD70015))
+                        .child("Enter a color as #RRGGBB"),
+                )
+            })
+    }
+}
+
+impl DialogContent for PickerPanel {
+    fn values(&self, cx: &App) -> BTreeMap<String, String> {
+        self.color(cx)
+            .map(|color| ("color".to_string(), to_hex(color)))
+            .into_iter()
+            .collect()
+    }
+
+    fn can_confirm(&self, cx: &App) -> bool {
+        self.color(cx).is_some()
+    }
+
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle> {
+        Some(self.hex.focus_handle(cx))
+    }
+}
@@ -0,0 +1,383 @@
+// ======================================================================
+// COMMAND PALETTE PRESET
+// ======================================================================
+// A search box over a list of commands, as in editors: type a few letters
+// of a command's name, pick it with the arrow keys and press Enter.
+//
+//     CommandPalette::new()
+//         .command("Reload config", |cx| reload(cx))
+//         .action("Select all", SelectAll)
+//         .show(cx, |_, _| {});
+//
+// Commands are either closures, or gpui actions that get dispatched to
+// the window that was active when the palette opened, exactly as if their
+// key binding had been pressed (the binding is shown next to the name).
+//
+// Matching is fuzzy: the typed letters must appear in order, and matches
+// at word starts or in runs rank higher ("rc" finds "Reload config").
+
+use std::ops::Range;
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::{EmptyState, Kbd, TextInput};
+use crate::dialog::{ContentEvent, Dialog, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::result::{DialogResult, Outcome};
+
+enum Run {
+    Callback(Rc<dyn Fn(&mut App)>),
+    Action(Box<dyn Action>),
+}
+
+struct Command {
+    label: SharedString,
+    run: Run,
+}
+
+#[derive(Default)]
+pub struct CommandPalette {
+    commands: Vec<Command>,
+    placeholder: Option<SharedString>,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A command that runs `run` when chosen
+    pub fn command(
+        mut self,
+        label: impl Into<SharedString>,
+        run: impl Fn(&mut App) + 'static,
+    ) -> Self {
+        self.commands.push(Command {
+            label: label.into(),
+            run: Run::Callback(Rc::new(run)),
+        });
+        self
+    }
+
+    // A command that dispatches `action` when chosen
+    pub fn action(mut self, label: impl Into<SharedString>, action: impl Action) -> Self {
+        self.commands.push(Command {
+            label: label.into(),
+            run: Run::Action(Box::new(action)),
+        });
+        self
+    }
+
+    // Text shown in the empty search box
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    // Show the palette. A chosen command runs before on_resolve; its
+    // label is in values["command"].
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        // Actions go back to whatever window the user was working in
+        let target = cx.active_window();
+
+        let entries: Vec<Entry> = self
+            .commands
+            .iter()
+            .map(|command| Entry {
+                label: command.label.clone(),
+                keystrokes: match &command.run {
+                    Run::Action(action) => shortcut_for(action.as_ref(), cx),
+                    Run::Callback(_) => Vec::new(),
+                },
+            })
+            .collect();
+        let placeholder = self.placeholder.unwrap_or_else(|| "Type a command…".into());
+        let commands = self.commands;
+
+        // No buttons: Enter runs the selected command and ESC closes
+        Dialog::custom("", move |_window, cx| {
+            cx.new(|cx| PalettePanel::new(entries, placeholder, cx))
+        })
+        .buttons([])
+        .size(size(px(560.0), px(400.0)))
+        .show(cx, move |result, cx| {
+            if result.outcome == Outcome::Completed {
+                let chosen = result
+                    .value("index")
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .and_then(|index| commands.into_iter().nth(index));
+                if let Some(command) = chosen {
+                    run(command.run, target, cx);
+                }
+            }
+            on_resolve(result, cx);
+        })
+    }
+}
+
+fn run(run: Run, target: Option<AnyWindowHandle>, cx: &mut App) {
+    match run {
+        Run::Callback(callback) => callback(cx),
+        // Deferred until the palette window is gone, so the action reaches
+        // the target window's focused element
+        Run::Action(action) => cx.defer(move |cx| match target {
+            Some(target) => {
+                target
+                    .update(cx, |_, window, cx| window.dispatch_action(action, cx))
+                    .ok();
+            }
+            None => cx.dispatch_action(action.as_ref()),
+        }),
+    }
+}
+
+// The action's key binding (the one that takes precedence), if any
+fn shortcut_for(action: &dyn Action, cx: &App) -> Vec<KeybindingKeystroke> {
+    let keymap = cx.key_bindings();
+    let keymap = keymap.borrow();
+    let binding = keymap.bindings_for_action(action).next_back();
+    binding
+        .map(|binding| binding.keystrokes().to_vec())
+        .unwrap_or_default()
+}
+
+// ======================================================================
+// FUZZY MATCHING
+// ======================================================================
+
+// Score `text` against `query`, or None if the query's characters don't
+// all appear in order. Also returns the byte ranges that matched, for
+// highlighting. Case-insensitive.
+pub(crate) fn fuzzy_match(query: &str, text: &str) -> Option<(i32, Vec<Range<usize>>)> {
+    let mut score = 0;
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+
+    for (offset, c) in text.char_indices() {
+        let Some(wanted) = query_chars.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq(wanted.to_lowercase());
+        if matched {
+            query_chars.next();
+            let word_start = previous.is_none_or(|p| !p.is_alphanumeric())
+                || (c.is_uppercase() && previous.is_some_and(char::is_lowercase));
+            score += 1;
+            if word_start {
+                score += 8;
+            }
+            if previous_matched {
+                score += 5;
+                // Extend the previous highlight instead of starting a new one
+                if let Some(last) = ranges.last_mut() {
+                    last.end = offset + c.len_utf8();
+                }
+            } else {
+                ranges.push(offset..offset + c.len_utf8());
+            }
+        } else if !ranges.is_empty() {
+            // Gaps after the first match cost a little
+            score -= 1;
+        }
+        previous = Some(c);
+        previous_matched = matched;
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    Some((score, ranges))
+}
+
+// ======================================================================
+// PALETTE PANEL COMPONENT
+// ======================================================================
+
+struct Entry {
+    label: SharedString,
+    keystrokes: Vec<KeybindingKeystroke>,
+}
+
+struct PalettePanel {
+    entries: Vec<Entry>,
+    search: Entity<TextInput>,
+    // (entry index, highlighted ranges), best match first
+    matches: Vec<(usize, Vec<Range<usize>>)>,
+    // Index into `matches`
+    selected: usize,
+    scroll_handle: ScrollHandle,
+}
+
+impl EventEmitter<ContentEvent> for PalettePanel {}
+
+impl PalettePanel {
+    fn new(entries: Vec<Entry>, placeholder: SharedString, cx: &mut Context<Self>) -> Self {
+        let search = cx.new(|cx| TextInput::new(cx).placeholder(placeholder));
+        cx.observe(&search, |this, _, cx| this.update_matches(cx))
+            .detach();
+
+        let mut panel = Self {
+            entries,
+            search,
+            matches: Vec::new(),
+            selected: 0,
+            scroll_handle: ScrollHandle::new(),
+        };
+        panel.update_matches(cx);
+        panel
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let query = self.search.read(cx).text().to_string();
+        let mut scored: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_match(&query, &entry.label).map(|(score, ranges)| (score, index, ranges))
+            })
+            .collect();
+        // Best score first; ties keep the order the commands were added in
+        scored.sort_by_key(|(score, index, _)| (-score, *index));
+
+        self.matches = scored
+            .into_iter()
+            .map(|(_, index, ranges)| (index, ranges))
+            .collect();
+        self.selected = 0;
+        self.scroll_handle.scroll_to_item(0);
+        cx.notify();
+    }
+
+    fn select(&mut self, selected: usize, cx: &mut Context<Self>) {
+        self.selected = selected;
+        self.scroll_handle.scroll_to_item(selected);
+        cx.notify();
+    }
+
+    // Up/Down move the selection (wrapping around), Enter runs it. The
+    // search box keeps focus throughout, so typing keeps filtering.
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.matches.len();
+        match event.keystroke.key.as_str() {
+            "down" if count > 0 => self.select((self.selected + 1) % count, cx),
+            "up" if count > 0 => self.select((self.selected + count - 1) % count, cx),
+            "enter" => cx.emit(ContentEvent::Confirm),
+            _ => return,
+        }
+        cx.stop_propagation();
+    }
+
+    fn render_row(&self, row: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let (index, ranges) = &self.matches[row];
+        let entry = &self.entries[*index];
+        let selected = row == self.selected;
+
+        // Bold the letters that matched the query
+        let highlight = HighlightStyle {
+            font_weight: Some(FontWeight::BOLD),
+            ..Default::default()
+        };
+        let label = StyledText::new(entry.label.clone())
+            .with_highlights(ranges.iter().map(|range| (range.clone(), highlight)));
+
+        div()
+            .id(row)
+            .flex()
+            .items_center()
+            .justify_between()
+            .px_3()
+            .py_1()
+            .rounded(px(5.0))
+            .cursor_pointer()
+            .when(selected, |row| {
+                row.bg(rgb(0x007AFF)).text_color(rgb(0xFFFFFF))
+            })
+            .when(!selected, |row| row.hover(|style| style.bg(rgb(0xF0F0F0))))
+            // Click selects and runs in one go
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.select(row, cx);
+                cx.emit(ContentEvent::Confirm);
+            }))
+            .child(label)
+            .child(
+                div().flex().gap_2().text_color(rgb(0x000000)).children(
+                    entry
+                        .keystrokes
+                        .iter()
+                        .map(|keystroke| div().flex().gap_1().children(Kbd::keystroke(keystroke))),
+                ),
+            )
+    }
+}
+
+impl Render for PalettePanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows: Vec<_> = (0..self.matches.len())
+            .map(|row| self.render_row(row, cx))
+            .collect();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .size_full()
+            .text_size(px(13.0))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .child(self.search.clone())
+            .child(
+                div()
+                    .id("commands")
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .p_1()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .bg(rgb(0xFFFFFF))
+                    .border_1()
+                    .border_color(rgb(0xB8B8B8))
+                    .rounded(px(5.0))
+                    .children(rows)
+                    .when(self.matches.is_empty(), |list| {
+                        list.child(
+                            EmptyState::new("No Matching Commands")
+                                .icon("🔍")
+                                .hint("Try a different search"),
+                        )
+                    }),
+            )
+    }
+}
+
+impl DialogContent for PalettePanel {
+    fn values(&self, _cx: &App) -> std::collections::BTreeMap<String, String> {
+        let Some((index, _)) = self.matches.get(self.selected) else {
+            return Default::default();
+        };
+        [
+            (
+                "command".to_string(),
+                self.entries[*index].label.to_string(),
+            ),
+            ("index".to_string(), index.to_string()),
+        ]
+        .into()
+    }
+
+    fn can_confirm(&self, _cx: &App) -> bool {
+        !self.matches.is_empty()
+    }
+
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle> {
+        Some(self.search.focus_handle(cx))
+    }
+}
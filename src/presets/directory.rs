@@ -0,0 +1,228 @@
+// ======================================================================
+// DIRECTORY PICKER PRESET
+// ======================================================================
+// Choose a folder. A breadcrumb shows where you are, above the folders
+// inside it: double-click a folder to open it, click a breadcrumb segment
+// to go back up. Choose returns the selected folder, or the open one when
+// nothing is selected, in values["path"]:
+//
+//     DirectoryPicker::new().directory("/srv").show(cx, |result, _| { ... });
+//
+// It starts in the home directory. Hidden folders (names starting with
+// ".") are left out. The status bar counts the folders, and says so
+// briefly when a folder can't be opened.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use gpui::*;
+
+use crate::components::breadcrumb::{Breadcrumb, Navigate};
+use crate::components::list_view::Activated;
+use crate::components::{ListView, StatusBar, Zone};
+use crate::dialog::{ContentEvent, Dialog, DialogButton, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+
+pub struct DirectoryPicker {
+    directory: PathBuf,
+}
+
+impl Default for DirectoryPicker {
+    fn default() -> Self {
+        Self {
+            directory: dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+        }
+    }
+}
+
+impl DirectoryPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The folder the picker opens in
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        let directory = self.directory;
+        let status_bar = cx.new(|_| StatusBar::new());
+        let panel_status_bar = status_bar.clone();
+        Dialog::custom("", move |_window, cx| {
+            cx.new(|cx| BrowserPanel::new(directory, panel_status_bar, cx))
+        })
+        .title("Choose a Folder")
+        .status_bar(status_bar)
+        .buttons([
+            DialogButton::cancel("Cancel"),
+            DialogButton::primary("Choose"),
+        ])
+        .size(size(px(520.0), px(420.0)))
+        .show(cx, on_resolve)
+    }
+}
+
+// The breadcrumb for a path: one segment per ancestor, outermost first.
+// Everything above the home directory folds into a single "Home".
+fn breadcrumb_segments(path: &Path) -> Vec<(SharedString, PathBuf)> {
+    let home = dirs::home_dir();
+    let mut segments = Vec::new();
+    for ancestor in path.ancestors() {
+        if Some(ancestor) == home.as_deref() {
+            segments.push(("Home".into(), ancestor.to_path_buf()));
+            break;
+        }
+        let label = match ancestor.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => ancestor.display().to_string(), // The root
+        };
+        segments.push((label.into(), ancestor.to_path_buf()));
+    }
+    segments.reverse();
+    segments
+}
+
+// The visible folders in a directory, sorted by name
+fn subfolders(directory: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut folders: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    folders.sort_by_key(|path| path.file_name().map(|name| name.to_ascii_lowercase()));
+    Ok(folders)
+}
+
+// ======================================================================
+// BROWSER PANEL COMPONENT
+// ======================================================================
+
+struct BrowserPanel {
+    directory: PathBuf,
+    // Where each breadcrumb segment leads
+    ancestors: Vec<PathBuf>,
+    folders: Vec<PathBuf>,
+    breadcrumb: Entity<Breadcrumb>,
+    list: Entity<ListView>,
+    status_bar: Entity<StatusBar>,
+}
+
+impl EventEmitter<ContentEvent> for BrowserPanel {}
+
+impl BrowserPanel {
+    fn new(directory: PathBuf, status_bar: Entity<StatusBar>, cx: &mut Context<Self>) -> Self {
+        let breadcrumb = cx.new(|_| Breadcrumb::new(Vec::<SharedString>::new()));
+        let list = cx.new(|cx| {
+            ListView::new(Vec::new(), false, cx)
+                .empty_title("No Folders")
+                .empty_hint("There are no folders inside this one")
+        });
+
+        cx.subscribe(&breadcrumb, |this, _, Navigate(index), cx| {
+            if let Some(path) = this.ancestors.get(*index).cloned() {
+                this.open(path, cx);
+            }
+        })
+        .detach();
+        cx.subscribe(&list, |this, _, Activated(row), cx| {
+            if let Some(path) = this.folders.get(*row).cloned() {
+                this.open(path, cx);
+            }
+        })
+        .detach();
+
+        let mut panel = Self {
+            directory: directory.clone(),
+            ancestors: Vec::new(),
+            folders: Vec::new(),
+            breadcrumb,
+            list,
+            status_bar,
+        };
+        panel.open(directory, cx);
+        panel
+    }
+
+    // Show the folders in `directory`. If it can't be read, stay where we
+    // are and say why.
+    fn open(&mut self, directory: PathBuf, cx: &mut Context<Self>) {
+        let folders = match subfolders(&directory) {
+            Ok(folders) => folders,
+            Err(err) => {
+                let message = format!("Couldn't open “{}”: {err}", directory.display());
+                self.status_bar.update(cx, |status_bar, cx| {
+                    status_bar.flash(message, Duration::from_secs(5), cx)
+                });
+                return;
+            }
+        };
+
+        let (labels, ancestors): (Vec<_>, Vec<_>) =
+            breadcrumb_segments(&directory).into_iter().unzip();
+        self.breadcrumb
+            .update(cx, |breadcrumb, cx| breadcrumb.set_segments(labels, cx));
+
+        let names = folders
+            .iter()
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default();
+                SharedString::from(name.to_string_lossy().into_owned())
+            })
+            .collect();
+        self.list.update(cx, |list, cx| list.set_rows(names, cx));
+        let count = match folders.len() {
+            1 => "1 folder".to_string(),
+            count => format!("{count} folders"),
+        };
+        self.status_bar.update(cx, |status_bar, cx| {
+            status_bar.set_text(Zone::Left, count, cx)
+        });
+
+        self.directory = directory;
+        self.ancestors = ancestors;
+        self.folders = folders;
+        cx.notify();
+    }
+
+    // The selected folder, or the open one
+    fn chosen(&self, cx: &App) -> &Path {
+        self.list
+            .read(cx)
+            .selected()
+            .next()
+            .and_then(|row| self.folders.get(row))
+            .unwrap_or(&self.directory)
+    }
+}
+
+impl Render for BrowserPanel {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .size_full()
+            .child(self.breadcrumb.clone())
+            .child(div().flex_1().child(self.list.clone()))
+    }
+}
+
+impl DialogContent for BrowserPanel {
+    fn values(&self, cx: &App) -> BTreeMap<String, String> {
+        BTreeMap::from([("path".to_string(), self.chosen(cx).display().to_string())])
+    }
+
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle> {
+        Some(self.list.focus_handle(cx))
+    }
+}
@@ -0,0 +1,741 @@
+// ======================================================================
+// EMOJI PICKER PRESET
+// ======================================================================
+// A grid of emoji with category tabs and a search box. The chosen emoji
+// comes back in values["emoji"]:
+//
+//     EmojiPicker::new().show(cx, |result, _| {
+//         if let Some(emoji) = result.value("emoji") { ... }
+//     });
+//
+// Keyboard: type to search, arrows move through the grid (even from the
+// search box, where Home and End still move the cursor), Enter picks.
+// People and hands can take a skin tone, chosen in the row at the bottom.
+// The last few picks (and the skin tone) are remembered between runs and
+// shown under "Recent".
+//
+// The emoji list is a hand-picked set of common ones, not the full Unicode
+// table; it only needs to cover what people reach for in a dialog.
+
+use std::collections::BTreeMap;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use serde_json::json;
+
+use crate::components::{EmptyState, TextInput};
+use crate::dialog::{ContentEvent, Dialog, DialogButton, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+use crate::storage;
+
+const COLUMNS: usize = 9;
+const MAX_RECENT: usize = 18;
+const STORAGE_FILE: &str = "emoji.json";
+
+actions!(
+    emoji_picker,
+    [SelectLeft, SelectRight, SelectUp, SelectDown]
+);
+
+// The search box keeps the focus, so the grid's bindings apply inside it,
+// ahead of its own left and right
+const KEY_CONTEXT: &str = "EmojiPicker > TextInput";
+
+// After the text input's bindings, so these take precedence
+pub(crate) fn bind_keys(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("left", SelectLeft, Some(KEY_CONTEXT)),
+        KeyBinding::new("right", SelectRight, Some(KEY_CONTEXT)),
+        KeyBinding::new("up", SelectUp, Some(KEY_CONTEXT)),
+        KeyBinding::new("down", SelectDown, Some(KEY_CONTEXT)),
+    ]);
+}
+
+// Appended to an emoji to give it a skin tone (light to dark)
+const SKIN_TONES: [&str; 5] = [
+    "\u{1F3FB}",
+    "\u{1F3FC}",
+    "\u{1F3FD}",
+    "\u{1F3FE}",
+    "\u{1F3FF}",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Category {
+    Recent,
+    Smileys,
+    People,
+    Nature,
+    Food,
+    Activities,
+    Travel,
+    Objects,
+    Symbols,
+}
+
+impl Category {
+    const ALL: [Category; 9] = [
+        Category::Recent,
+        Category::Smileys,
+        Category::People,
+        Category::Nature,
+        Category::Food,
+        Category::Activities,
+        Category::Travel,
+        Category::Objects,
+        Category::Symbols,
+    ];
+
+    // The emoji shown on the category's tab
+    fn icon(self) -> &'static str {
+        match self {
+            Category::Recent => "🕘",
+            Category::Smileys => "😀",
+            Category::People => "👋",
+            Category::Nature => "🐶",
+            Category::Food => "🍎",
+            Category::Activities => "⚽",
+            Category::Travel => "🚗",
+            Category::Objects => "💡",
+            Category::Symbols => "❤️",
+        }
+    }
+}
+
+struct Emoji {
+    emoji: &'static str,
+    name: &'static str,
+    category: Category,
+    // Whether a skin tone modifier may follow it
+    toned: bool,
+}
+
+const fn emoji(emoji: &'static str, name: &'static str, category: Category) -> Emoji {
+    Emoji {
+        emoji,
+        name,
+        category,
+        toned: false,
+    }
+}
+
+const fn toned(emoji: &'static str, name: &'static str) -> Emoji {
+    Emoji {
+        emoji,
+        name,
+        category: Category::People,
+        toned: true,
+    }
+}
+
+use Category::*;
+
+#[rustfmt::skip]
+const EMOJI: &[Emoji] = &[
+    emoji("😀", "grinning face", Smileys),
+    emoji("😃", "grinning face with big eyes", Smileys),
+    emoji("😄", "grinning face with smiling eyes", Smileys),
+    emoji("😁", "beaming face", Smileys),
+    emoji("😆", "laughing", Smileys),
+    emoji("😅", "sweat smile", Smileys),
+    emoji("🤣", "rolling on the floor laughing", Smileys),
+    emoji("😂", "tears of joy", Smileys),
+    emoji("🙂", "slightly smiling face", Smileys),
+    emoji("🙃", "upside-down face", Smileys),
+    emoji("😉", "winking face", Smileys),
+    emoji("😊", "smiling face with smiling eyes", Smileys),
+    emoji("😇", "halo angel", Smileys),
+    emoji("🥰", "smiling face with hearts", Smileys),
+    emoji("😍", "heart eyes", Smileys),
+    emoji("🤩", "star-struck", Smileys),
+    emoji("😘", "blowing a kiss", Smileys),
+    emoji("😋", "yum savoring food", Smileys),
+    emoji("😛", "tongue out", Smileys),
+    emoji("😜", "winking face with tongue", Smileys),
+    emoji("🤪", "zany face", Smileys),
+    emoji("🤔", "thinking face", Smileys),
+    emoji("🤨", "raised eyebrow", Smileys),
+    emoji("😐", "neutral face", Smileys),
+    emoji("😑", "expressionless", Smileys),
+    emoji("😶", "no mouth", Smileys),
+    emoji("🙄", "rolling eyes", Smileys),
+    emoji("😏", "smirk", Smileys),
+    emoji("😬", "grimacing", Smileys),
+    emoji("😌", "relieved", Smileys),
+    emoji("😴", "sleeping", Smileys),
+    emoji("🤒", "sick thermometer", Smileys),
+    emoji("🤯", "mind blown exploding head", Smileys),
+    emoji("🥳", "party face", Smileys),
+    emoji("😎", "sunglasses cool", Smileys),
+    emoji("🤓", "nerd", Smileys),
+    emoji("😕", "confused", Smileys),
+    emoji("😟", "worried", Smileys),
+    emoji("😮", "open mouth surprised", Smileys),
+    emoji("😢", "crying", Smileys),
+    emoji("😭", "sobbing", Smileys),
+    emoji("😱", "screaming in fear", Smileys),
+    emoji("😤", "triumph huffing", Smileys),
+    emoji("😡", "pouting angry", Smileys),
+    emoji("💀", "skull", Smileys),
+    emoji("💩", "pile of poo", Smileys),
+    emoji("🤖", "robot", Smileys),
+    emoji("👻", "ghost", Smileys),
+
+    toned("👋", "waving hand hello"),
+    toned("🤚", "raised back of hand"),
+    toned("✋", "raised hand high five"),
+    toned("👌", "ok hand"),
+    toned("🤌", "pinched fingers"),
+    toned("✌", "victory peace"),
+    toned("🤞", "crossed fingers luck"),
+    toned("🤟", "love-you gesture"),
+    toned("🤘", "sign of the horns rock"),
+    toned("👈", "pointing left"),
+    toned("👉", "pointing right"),
+    toned("👆", "pointing up"),
+    toned("👇", "pointing down"),
+    toned("👍", "thumbs up yes"),
+    toned("👎", "thumbs down no"),
+    toned("✊", "raised fist"),
+    toned("👏", "clapping hands"),
+    toned("🙌", "raising hands hooray"),
+    toned("🙏", "folded hands please thanks"),
+    toned("💪", "flexed biceps strong"),
+    emoji("👀", "eyes", People),
+    toned("🧑", "person"),
+    toned("👶", "baby"),
+    toned("🧒", "child"),
+    toned("👩", "woman"),
+    toned("👨", "man"),
+    toned("🧓", "older person"),
+    toned("🤷", "shrug"),
+    toned("🙋", "person raising hand"),
+    toned("🙇", "bowing"),
+
+    emoji("🐶", "dog", Nature),
+    emoji("🐱", "cat", Nature),
+    emoji("🐭", "mouse", Nature),
+    emoji("🐰", "rabbit", Nature),
+    emoji("🦊", "fox", Nature),
+    emoji("🐻", "bear", Nature),
+    emoji("🐼", "panda", Nature),
+    emoji("🐨", "koala", Nature),
+    emoji("🐯", "tiger", Nature),
+    emoji("🦁", "lion", Nature),
+    emoji("🐮", "cow", Nature),
+    emoji("🐷", "pig", Nature),
+    emoji("🐸", "frog", Nature),
+    emoji("🐵", "monkey", Nature),
+    emoji("🐔", "chicken", Nature),
+    emoji("🐧", "penguin", Nature),
+    emoji("🐦", "bird", Nature),
+    emoji("🦋", "butterfly", Nature),
+    emoji("🐝", "honeybee", Nature),
+    emoji("🐢", "turtle", Nature),
+    emoji("🐍", "snake", Nature),
+    emoji("🦀", "crab rust", Nature),
+    emoji("🐙", "octopus", Nature),
+    emoji("🐳", "whale", Nature),
+    emoji("🌵", "cactus", Nature),
+    emoji("🌲", "evergreen tree", Nature),
+    emoji("🌻", "sunflower", Nature),
+    emoji("🌹", "rose", Nature),
+    emoji("🍀", "four leaf clover", Nature),
+    emoji("🍁", "maple leaf autumn", Nature),
+    emoji("☀️", "sun", Nature),
+    emoji("🌙", "crescent moon", Nature),
+    emoji("⭐", "star", Nature),
+    emoji("🌈", "rainbow", Nature),
+    emoji("☁️", "cloud", Nature),
+    emoji("⚡", "lightning high voltage", Nature),
+    emoji("❄️", "snowflake", Nature),
+    emoji("🔥", "fire", Nature),
+    emoji("💧", "droplet water", Nature),
+    emoji("🌊", "water wave", Nature),
+
+    emoji("🍎", "red apple", Food),
+    emoji("🍐", "pear", Food),
+    emoji("🍊", "orange tangerine", Food),
+    emoji("🍋", "lemon", Food),
+    emoji("🍌", "banana", Food),
+    emoji("🍉", "watermelon", Food),
+    emoji("🍇", "grapes", Food),
+    emoji("🍓", "strawberry", Food),
+    emoji("🍒", "cherries", Food),
+    emoji("🥑", "avocado", Food),
+    emoji("🥕", "carrot", Food),
+    emoji("🌽", "corn", Food),
+    emoji("🌶️", "hot pepper", Food),
+    emoji("🥐", "croissant", Food),
+    emoji("🍞", "bread", Food),
+    emoji("🧀", "cheese", Food),
+    emoji("🍳", "cooking egg", Food),
+    emoji("🍔", "hamburger", Food),
+    emoji("🍟", "french fries", Food),
+    emoji("🍕", "pizza", Food),
+    emoji("🌮", "taco", Food),
+    emoji("🍣", "sushi", Food),
+    emoji("🍜", "noodles ramen", Food),
+    emoji("🍩", "doughnut", Food),
+    emoji("🍪", "cookie", Food),
+    emoji("🎂", "birthday cake", Food),
+    emoji("🍫", "chocolate", Food),
+    emoji("☕", "coffee hot beverage", Food),
+    emoji("🍵", "tea", Food),
+    emoji("🍺", "beer", Food),
+    emoji("🍷", "wine", Food),
+    emoji("🥂", "clinking glasses cheers", Food),
+
+    emoji("⚽", "soccer football", Activities),
+    emoji("🏀", "basketball", Activities),
+    emoji("🏈", "american football", Activities),
+    emoji("⚾", "baseball", Activities),
+    emoji("🎾", "tennis", Activities),
+    emoji("🏐", "volleyball", Activities),
+    emoji("🏓", "ping pong", Activities),
+    emoji("⛳", "golf", Activities),
+    emoji("🎣", "fishing", Activities),
+    emoji("🎿", "skis", Activities),
+    emoji("🏆", "trophy", Activities),
+    emoji("🥇", "gold medal first", Activities),
+    emoji("🎯", "bullseye target", Activities),
+    emoji("🎮", "video game", Activities),
+    emoji("🎲", "dice game", Activities),
+    emoji("🧩", "puzzle piece", Activities),
+    emoji("🎨", "artist palette", Activities),
+    emoji("🎬", "clapper board movie", Activities),
+    emoji("🎤", "microphone", Activities),
+    emoji("🎧", "headphones", Activities),
+    emoji("🎸", "guitar", Activities),
+    emoji("🎹", "piano keyboard", Activities),
+    emoji("🎉", "party popper tada", Activities),
+    emoji("🎁", "wrapped gift present", Activities),
+
+    emoji("🚗", "car", Travel),
+    emoji("🚕", "taxi", Travel),
+    emoji("🚌", "bus", Travel),
+    emoji("🚑", "ambulance", Travel),
+    emoji("🚒", "fire engine", Travel),
+    emoji("🚲", "bicycle", Travel),
+    emoji("🛴", "kick scooter", Travel),
+    emoji("🚂", "locomotive train", Travel),
+    emoji("✈️", "airplane", Travel),
+    emoji("🚀", "rocket launch", Travel),
+    emoji("🛸", "flying saucer ufo", Travel),
+    emoji("🚢", "ship", Travel),
+    emoji("⛵", "sailboat", Travel),
+    emoji("⚓", "anchor", Travel),
+    emoji("🏠", "house home", Travel),
+    emoji("🏢", "office building", Travel),
+    emoji("🏥", "hospital", Travel),
+    emoji("🏫", "school", Travel),
+    emoji("🏰", "castle", Travel),
+    emoji("🗽", "statue of liberty", Travel),
+    emoji("🗼", "tokyo tower", Travel),
+    emoji("⛰️", "mountain", Travel),
+    emoji("🏖️", "beach umbrella", Travel),
+    emoji("🌍", "globe earth", Travel),
+    emoji("🗺️", "world map", Travel),
+
+    emoji("⌚", "watch", Objects),
+    emoji("📱", "mobile phone", Objects),
+    emoji("💻", "laptop computer", Objects),
+    emoji("⌨️", "keyboard", Objects),
+    emoji("🖥️", "desktop computer", Objects),
+    emoji("🖨️", "printer", Objects),
+    emoji("💾", "floppy disk save", Objects),
+    emoji("📷", "camera", Objects),
+    emoji("📞", "telephone", Objects),
+    emoji("🔋", "battery", Objects),
+    emoji("🔌", "electric plug", Objects),
+    emoji("💡", "light bulb idea", Objects),
+    emoji("🔦", "flashlight", Objects),
+    emoji("📚", "books", Objects),
+    emoji("📝", "memo note", Objects),
+    emoji("✏️", "pencil", Objects),
+    emoji("📎", "paperclip attachment", Objects),
+    emoji("📌", "pushpin", Objects),
+    emoji("📅", "calendar", Objects),
+    emoji("📦", "package box", Objects),
+    emoji("✉️", "envelope mail", Objects),
+    emoji("🔒", "locked", Objects),
+    emoji("🔑", "key", Objects),
+    emoji("🔨", "hammer", Objects),
+    emoji("🔧", "wrench", Objects),
+    emoji("⚙️", "gear settings", Objects),
+    emoji("🧪", "test tube", Objects),
+    emoji("🔬", "microscope", Objects),
+    emoji("💰", "money bag", Objects),
+    emoji("🛒", "shopping cart", Objects),
+
+    emoji("❤️", "red heart love", Symbols),
+    emoji("🧡", "orange heart", Symbols),
+    emoji("💛", "yellow heart", Symbols),
+    emoji("💚", "green heart", Symbols),
+    emoji("💙", "blue heart", Symbols),
+    emoji("💜", "purple heart", Symbols),
+    emoji("🖤", "black heart", Symbols),
+    emoji("💔", "broken heart", Symbols),
+    emoji("💯", "hundred points", Symbols),
+    emoji("✅", "check mark button done", Symbols),
+    emoji("✔️", "check mark", Symbols),
+    emoji("❌", "cross mark", Symbols),
+    emoji("❓", "question mark", Symbols),
+    emoji("❗", "exclamation mark", Symbols),
+    emoji("⚠️", "warning", Symbols),
+    emoji("🚫", "prohibited", Symbols),
+    emoji("⛔", "no entry", Symbols),
+    emoji("♻️", "recycling", Symbols),
+    emoji("➕", "plus", Symbols),
+    emoji("➖", "minus", Symbols),
+    emoji("➡️", "right arrow", Symbols),
+    emoji("⬅️", "left arrow", Symbols),
+    emoji("⬆️", "up arrow", Symbols),
+    emoji("⬇️", "down arrow", Symbols),
+    emoji("🔄", "counterclockwise arrows refresh", Symbols),
+    emoji("🔴", "red circle", Symbols),
+    emoji("🟢", "green circle", Symbols),
+    emoji("🔵", "blue circle", Symbols),
+    emoji("💬", "speech balloon", Symbols),
+    emoji("💤", "zzz sleep", Symbols),
+    emoji("✨", "sparkles", Symbols),
+    emoji("🏁", "chequered flag finish", Symbols),
+];
+
+// Give `emoji` a skin tone, if it takes one. A trailing variation
+// selector (U+FE0F) has to make way for the modifier.
+fn with_skin_tone(emoji: &Emoji, tone: Option<usize>) -> String {
+    match tone.and_then(|tone| SKIN_TONES.get(tone)) {
+        Some(modifier) if emoji.toned => {
+            format!("{}{modifier}", emoji.emoji.trim_end_matches('\u{FE0F}'))
+        }
+        _ => emoji.emoji.to_string(),
+    }
+}
+
+// ======================================================================
+// EMOJI PICKER
+// ======================================================================
+
+#[derive(Default)]
+pub struct EmojiPicker {
+    title: Option<SharedString>,
+}
+
+impl EmojiPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Replaces the default "Emoji" title
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        Dialog::custom("", |_window, cx| cx.new(EmojiPanel::new))
+            .title(self.title.unwrap_or_else(|| "Emoji".into()))
+            .buttons([
+                DialogButton::cancel("Cancel"),
+                DialogButton::primary("Insert"),
+            ])
+            .size(size(px(460.0), px(480.0)))
+            .show(cx, move |mut result, cx| {
+                if !result.is_confirmed() {
+                    // Cancelled: the highlighted emoji wasn't picked
+                    result.values.clear();
+                } else if let Some(emoji) = result.value("emoji") {
+                    remember(emoji, result.value("skin_tone"));
+                }
+                on_resolve(result, cx);
+            })
+    }
+}
+
+// What's kept between runs: {"recent": ["🚀", ...], "skin_tone": 2}
+struct Remembered {
+    recent: Vec<String>,
+    skin_tone: Option<usize>,
+}
+
+fn remembered() -> Remembered {
+    let stored = storage::load(STORAGE_FILE).unwrap_or_default();
+    Remembered {
+        recent: stored["recent"]
+            .as_array()
+            .map(|recent| {
+                recent
+                    .iter()
+                    .filter_map(|emoji| emoji.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        skin_tone: stored["skin_tone"].as_u64().map(|tone| tone as usize),
+    }
+}
+
+// Move the picked emoji to the front of the recent list
+fn remember(emoji: &str, skin_tone: Option<&str>) {
+    let mut recent = remembered().recent;
+    recent.retain(|other| other != emoji);
+    recent.insert(0, emoji.to_string());
+    recent.truncate(MAX_RECENT);
+
+    let skin_tone = skin_tone.and_then(|tone| tone.parse::<usize>().ok());
+    storage::save(
+        STORAGE_FILE,
+        &json!({ "recent": recent, "skin_tone": skin_tone }),
+    );
+}
+
+// ======================================================================
+// EMOJI PANEL COMPONENT
+// ======================================================================
+
+struct EmojiPanel {
+    search: Entity<TextInput>,
+    category: Category,
+    // Index into SKIN_TONES, None for the default yellow
+    skin_tone: Option<usize>,
+    recent: Vec<String>,
+    // The emoji currently shown in the grid, and the highlighted one
+    visible: Vec<String>,
+    selected: usize,
+    scroll_handle: ScrollHandle,
+}
+
+impl EventEmitter<ContentEvent> for EmojiPanel {}
+
+impl EmojiPanel {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let search = cx.new(|cx| TextInput::new(cx).placeholder("Search emoji"));
+        cx.observe(&search, |this, _, cx| this.update_visible(cx))
+            .detach();
+
+        let remembered = remembered();
+        let category = if remembered.recent.is_empty() {
+            Category::Smileys
+        } else {
+            Category::Recent
+        };
+
+        let mut panel = Self {
+            search,
+            category,
+            skin_tone: remembered.skin_tone,
+            recent: remembered.recent,
+            visible: Vec::new(),
+            selected: 0,
+            scroll_handle: ScrollHandle::new(),
+        };
+        panel.update_visible(cx);
+        panel
+    }
+
+    // A search looks through every category; otherwise the grid shows
+    // the current tab
+    fn update_visible(&mut self, cx: &mut Context<Self>) {
+        let query = self.search.read(cx).text().trim().to_lowercase();
+
+        self.visible = if query.is_empty() && self.category == Category::Recent {
+            self.recent.clone()
+        } else {
+            EMOJI
+                .iter()
+                .filter(|emoji| {
+                    if query.is_empty() {
+                        emoji.category == self.category
+                    } else {
+                        emoji.name.contains(&query)
+                    }
+                })
+                .map(|emoji| with_skin_tone(emoji, self.skin_tone))
+                .collect()
+        };
+        self.selected = 0;
+        self.scroll_handle.scroll_to_item(0);
+        cx.notify();
+    }
+
+    fn set_category(&mut self, category: Category, cx: &mut Context<Self>) {
+        self.category = category;
+        // Picking a tab means browsing, so leave the search behind
+        self.search.update(cx, |search, cx| search.set_text("", cx));
+        self.update_visible(cx);
+    }
+
+    fn set_skin_tone(&mut self, skin_tone: Option<usize>, cx: &mut Context<Self>) {
+        self.skin_tone = skin_tone;
+        let selected = self.selected;
+        self.update_visible(cx);
+        self.selected = selected.min(self.visible.len().saturating_sub(1));
+    }
+
+    fn select(&mut self, selected: usize, cx: &mut Context<Self>) {
+        self.selected = selected;
+        // The grid's children are its rows
+        self.scroll_handle.scroll_to_item(selected / COLUMNS);
+        cx.notify();
+    }
+
+    // Move the highlight by `step` cells, stopping at either end
+    fn step(&mut self, step: isize, cx: &mut Context<Self>) {
+        let Some(last) = self.visible.len().checked_sub(1) else {
+            return;
+        };
+        let selected = self.selected.saturating_add_signed(step).min(last);
+        self.select(selected, cx);
+    }
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .justify_between()
+            .children(Category::ALL.into_iter().map(|category| {
+                let active = category == self.category;
+                div()
+                    .id(SharedString::from(format!("tab-{category:?}")))
+                    .px_2()
+                    .py_1()
+                    .rounded(px(5.0))
+                    .text_size(px(16.0))
+                    .cursor_pointer()
+                    .when(active, |tab| tab.bg(rgb(0xD6D6D6)))
+                    .when(!active, |tab| tab.hover(|style| style.bg(rgb(0xE4E4E4))))
+                    .on_click(cx.listener(move |this, _, _, cx| this.set_category(category, cx)))
+                    .child(category.icon())
+            }))
+    }
+
+    fn render_grid(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows = self
+            .visible
+            .chunks(COLUMNS)
+            .enumerate()
+            .map(|(row, emoji)| {
+                div()
+                    .flex()
+                    .children(emoji.iter().enumerate().map(|(column, emoji)| {
+                        let index = row * COLUMNS + column;
+                        let selected = index == self.selected;
+                        div()
+                            .id(index)
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .size(px(42.0))
+                            .rounded(px(6.0))
+                            .text_size(px(24.0))
+                            .cursor_pointer()
+                            .when(selected, |cell| cell.bg(rgb(0xCCE4FF)))
+                            .when(!selected, |cell| {
+                                cell.hover(|style| style.bg(rgb(0xF0F0F0)))
+                            })
+                            // Click selects, double-click picks
+                            .on_click(cx.listener(move |this, event: &ClickEvent, _, cx| {
+                                this.select(index, cx);
+                                if event.click_count() > 1 {
+                                    cx.emit(ContentEvent::Confirm);
+                                }
+                            }))
+                            .child(emoji.clone())
+                    }))
+            });
+
+        div()
+            .id("emoji-grid")
+            .flex_1()
+            .flex()
+            .flex_col()
+            .p_1()
+            .overflow_y_scroll()
+            .track_scroll(&self.scroll_handle)
+            .bg(rgb(0xFFFFFF))
+            .border_1()
+            .border_color(rgb(0xB8B8B8))
+            .rounded(px(5.0))
+            .children(rows)
+            .when(self.visible.is_empty(), |grid| {
+                grid.child(if self.category == Category::Recent {
+                    EmptyState::new("No Recent Emoji").hint("Emoji you pick will show up here")
+                } else {
+                    EmptyState::new("No Emoji Found")
+                        .icon("🔍")
+                        .hint("Try a different search or category")
+                })
+            })
+    }
+
+    // The default yellow, then the five skin tones, shown on a waving hand
+    fn render_skin_tones(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let hand = &EMOJI[EMOJI.iter().position(|e| e.emoji == "👋").unwrap_or(0)];
+        let tones = std::iter::once(None).chain((0..SKIN_TONES.len()).map(Some));
+
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .text_size(px(12.0))
+            .text_color(rgb(0x4D4D4D))
+            .child(div().mr_1().child("Skin tone"))
+            .children(tones.map(|tone| {
+                let active = tone == self.skin_tone;
+                div()
+                    .id(SharedString::from(format!("tone-{tone:?}")))
+                    .px_1()
+                    .rounded(px(5.0))
+                    .text_size(px(16.0))
+                    .cursor_pointer()
+                    .when(active, |swatch| swatch.bg(rgb(0xD6D6D6)))
+                    .on_click(cx.listener(move |this, _, _, cx| this.set_skin_tone(tone, cx)))
+                    .child(with_skin_tone(hand, tone))
+            }))
+    }
+}
+
+impl Render for EmojiPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .size_full()
+            .text_size(px(13.0))
+            .key_context("EmojiPicker")
+            .on_action(cx.listener(|this, _: &SelectLeft, _, cx| this.step(-1, cx)))
+            .on_action(cx.listener(|this, _: &SelectRight, _, cx| this.step(1, cx)))
+            .on_action(cx.listener(|this, _: &SelectUp, _, cx| this.step(-(COLUMNS as isize), cx)))
+            .on_action(cx.listener(|this, _: &SelectDown, _, cx| this.step(COLUMNS as isize, cx)))
+            .child(self.search.clone())
+            .child(self.render_tabs(cx))
+            .child(self.render_grid(cx))
+            .child(self.render_skin_tones(cx))
+    }
+}
+
+impl DialogContent for EmojiPanel {
+    fn values(&self, _cx: &App) -> BTreeMap<String, String> {
+        let mut values = BTreeMap::new();
+        if let Some(emoji) = self.visible.get(self.selected) {
+            values.insert("emoji".to_string(), emoji.clone());
+        }
+        if let Some(tone) = self.skin_tone {
+            values.insert("skin_tone".to_string(), tone.to_string());
+        }
+        values
+    }
+
+    fn can_confirm(&self, _cx: &App) -> bool {
+        !self.visible.is_empty()
+    }
+
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle> {
+        Some(self.search.focus_handle(cx))
+    }
+}
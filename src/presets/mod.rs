@@ -0,0 +1,18 @@
+// Ready-made dialogs for common jobs, built on Dialog::custom(). Each
+// preset is a small builder with a show() method, like Dialog itself.
+
+pub mod about;
+pub mod color_picker;
+pub mod command_palette;
+pub mod directory;
+pub mod emoji;
+pub mod print;
+pub mod shortcuts;
+
+pub use about::AboutDialog;
+pub use color_picker::ColorPicker;
+pub use command_palette::CommandPalette;
+pub use directory::DirectoryPicker;
+pub use emoji::EmojiPicker;
+pub use print::PrintDialog;
+pub use shortcuts::ShortcutsDialog;
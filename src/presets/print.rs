@@ -0,0 +1,632 @@
+// ======================================================================
+// PRINT DIALOG PRESET
+// ======================================================================
+// A print dialog for a document on disk: pick a printer, choose which
+// pages and how many copies, check the live preview, then Print.
+//
+//     PrintDialog::new("report.txt").show(cx, |result, cx| { ... });
+//
+// Printers are listed, and jobs submitted, through the platform's print
+// system: CUPS (`lpstat` / `lp`) on macOS and Linux, and PowerShell's
+// printing cmdlets on Windows. The result carries the chosen "printer",
+// "pages" and "copies", plus either the "job" the print system reported
+// or an "error". Windows can only print the whole document, so there the
+// Pages field has to be left empty.
+//
+// The preview paginates plain text. Other formats are still printed, but
+// previewed as a blank page with the file name, and as their length isn't
+// known any page range is taken as it is.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::TextInput;
+use crate::dialog::{ContentEvent, Dialog, DialogButton, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::layout::{grid, Track};
+use crate::rendering;
+use crate::result::DialogResult;
+
+// Lines of text that fit on one printed page
+const LINES_PER_PAGE: usize = 60;
+
+// Past any real document, to keep ranges of unknown documents bounded
+const MAX_PAGES: usize = 9999;
+
+// Why the Pages field has to be empty on Windows
+const PAGES_UNSUPPORTED: &str = "Windows can only print every page; leave Pages empty";
+
+// A4 proportions, scaled down to fit the preview panel
+const PAGE_WIDTH: f32 = 150.0;
+const PAGE_HEIGHT: f32 = PAGE_WIDTH * 1.414;
+
+pub struct PrintDialog {
+    document: PathBuf,
+}
+
+impl PrintDialog {
+    pub fn new(document: impl Into<PathBuf>) -> Self {
+        Self {
+            document: document.into(),
+        }
+    }
+
+    // Show the dialog. When the user clicks Print, the job is submitted
+    // in the background and on_resolve runs once the print system has
+    // taken it.
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        let document = self.document;
+        let name = document
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| document.display().to_string());
+
+        let preview_document = document.clone();
+        Dialog::custom(format!("Print “{name}”"), move |window, cx| {
+            cx.new(|cx| PrintPanel::new(&preview_document, window, cx))
+        })
+        .title("Print")
+        .buttons([
+            DialogButton::cancel("Cancel"),
+            DialogButton::primary("Print"),
+        ])
+        .size(size(px(680.0), px(470.0)))
+        .show(cx, move |mut result, cx| {
+            if !result.is_confirmed() {
+                return on_resolve(result, cx);
+            }
+            // Spooling runs lp (or a PowerShell per copy), which can take
+            // a while
+            let values = result.values.clone();
+            let submitted = cx.background_spawn(async move { submit(&document, &values) });
+            cx.spawn(async move |cx| {
+                let (key, value) = match submitted.await {
+                    Ok(job) => ("job", job),
+                    Err(err) => ("error", err),
+                };
+                result.values.insert(key.to_string(), value);
+                cx.update(|cx| on_resolve(result, cx)).ok();
+            })
+            .detach();
+        })
+    }
+}
+
+// ======================================================================
+// PRINTERS
+// ======================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Printer {
+    pub name: String,
+    pub is_default: bool,
+}
+
+// Ask the print system for its printers, default first. Any failure (no
+// CUPS, no permission, ...) just means an empty list.
+pub fn available_printers() -> Vec<Printer> {
+    let (names, default) = if cfg!(target_os = "windows") {
+        (
+            command_lines(powershell(
+                "Get-Printer | Select-Object -ExpandProperty Name",
+            )),
+            command_lines(powershell(
+                "(Get-CimInstance Win32_Printer | Where-Object Default).Name",
+            ))
+            .into_iter()
+            .next(),
+        )
+    } else {
+        let mut lpstat = Command::new("lpstat");
+        lpstat.arg("-e");
+        let mut lpstat_default = Command::new("lpstat");
+        lpstat_default.arg("-d");
+        (
+            command_lines(lpstat),
+            // "system default destination: Office"
+            command_lines(lpstat_default)
+                .into_iter()
+                .find_map(|line| line.split_once(": ").map(|(_, name)| name.to_string())),
+        )
+    };
+
+    let mut printers: Vec<_> = names
+        .into_iter()
+        .map(|name| Printer {
+            is_default: default.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect();
+    printers.sort_by_key(|printer| !printer.is_default);
+    printers
+}
+
+// Send the document to the print system, returning its job description
+fn submit(document: &Path, values: &BTreeMap<String, String>) -> Result<String, String> {
+    let printer = values.get("printer").filter(|name| !name.is_empty());
+    let printer = printer.ok_or("no printer selected")?;
+    let copies = values
+        .get("copies")
+        .map_or(Ok(1), |copies| copies.parse::<usize>())
+        .map_err(|_| "copies isn't a number")?;
+    let pages = values.get("pages").map(String::as_str).unwrap_or_default();
+
+    let mut command = if cfg!(target_os = "windows") {
+        // The shell's PrintTo verb can't select pages; it prints them all.
+        // The panel doesn't let a range through, but say so rather than
+        // print more than was asked for.
+        if !pages.is_empty() {
+            return Err(PAGES_UNSUPPORTED.to_string());
+        }
+        // The file and printer names go in through the environment, as
+        // either could hold quotes that would end a string in the script.
+        let mut command = powershell(&format!(
+            "1..{copies} | ForEach-Object {{ Start-Process -FilePath $env:PRINT_DOCUMENT -Verb PrintTo -ArgumentList ('\"' + $env:PRINT_PRINTER + '\"') -Wait }}"
+        ));
+        command
+            .env("PRINT_DOCUMENT", document)
+            .env("PRINT_PRINTER", printer);
+        command
+    } else {
+        let mut lp = Command::new("lp");
+        lp.arg("-d").arg(printer).arg("-n").arg(copies.to_string());
+        if !pages.is_empty() {
+            lp.arg("-P").arg(pages);
+        }
+        lp.arg("--").arg(document);
+        lp
+    };
+
+    let output = command.output().map_err(|err| err.to_string())?;
+    if output.status.success() {
+        // lp answers with "request id is Office-42 (1 file(s))"
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn powershell(script: &str) -> Command {
+    let mut command = Command::new("powershell");
+    command.args(["-NoProfile", "-Command", script]);
+    command
+}
+
+// Run a command and return its non-empty stdout lines
+fn command_lines(mut command: Command) -> Vec<String> {
+    let Ok(output) = command.output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// ======================================================================
+// PAGE RANGES
+// ======================================================================
+
+// Parse "1-3, 5" into 1-based page numbers. Empty means every page,
+// which is none in particular when the page count isn't known: ranges
+// are then taken as they are, without checking them against the count.
+pub fn parse_page_range(text: &str, page_count: Option<usize>) -> Result<Vec<usize>, String> {
+    if text.trim().is_empty() {
+        return Ok((1..=page_count.unwrap_or(0)).collect());
+    }
+
+    let mut pages = Vec::new();
+    for part in text.split(',').map(str::trim) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (part, part),
+        };
+        let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) else {
+            return Err(format!("“{part}” is not a page or range"));
+        };
+        if start == 0 || start > end {
+            return Err(format!("“{part}” is not a valid range"));
+        }
+        if end > MAX_PAGES {
+            return Err(format!("“{part}” is past page {MAX_PAGES}"));
+        }
+        match page_count {
+            Some(1) if end > 1 => return Err("The document has only 1 page".to_string()),
+            Some(count) if end > count => {
+                return Err(format!("The document has only {count} pages"))
+            }
+            _ => {}
+        }
+        pages.extend(start..=end);
+    }
+    pages.sort_unstable();
+    pages.dedup();
+    Ok(pages)
+}
+
+// ======================================================================
+// PRINT PANEL COMPONENT
+// ======================================================================
+// The custom content between the message and the buttons: settings on
+// the left, preview on the right.
+
+struct PrintPanel {
+    printers: Vec<Printer>,
+    loading_printers: bool,
+    selected_printer: Option<usize>,
+    // The document split into pages of text lines (empty if it isn't text)
+    pages: Vec<Vec<SharedString>>,
+    file_name: SharedString,
+    page_range: Entity<TextInput>,
+    copies: Entity<TextInput>,
+    // Index into the selected pages of the page being previewed
+    preview_index: usize,
+    _load_printers: Task<()>,
+}
+
+impl EventEmitter<ContentEvent> for PrintPanel {}
+
+impl PrintPanel {
+    fn new(document: &Path, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let page_range = cx.new(|cx| TextInput::new(cx).placeholder("All"));
+        let copies = cx.new(|cx| {
+            let mut input = TextInput::new(cx);
+            input.set_text("1", cx);
+            input
+        });
+
+        // Re-render the preview whenever a field changes
+        cx.observe(&page_range, |this, _, cx| {
+            this.preview_index = 0;
+            cx.notify();
+        })
+        .detach();
+        cx.observe(&copies, |_, _, cx| cx.notify()).detach();
+
+        // Querying the print system spawns processes, so keep it off the
+        // main thread
+        let load_printers = cx.spawn(async move |this, cx| {
+            let printers = cx
+                .background_executor()
+                .spawn(async { available_printers() })
+                .await;
+            this.update(cx, |this, cx| {
+                this.selected_printer = (!printers.is_empty()).then_some(0);
+                this.printers = printers;
+                this.loading_printers = false;
+                cx.notify();
+            })
+            .ok();
+        });
+
+        Self {
+            printers: Vec::new(),
+            loading_printers: true,
+            selected_printer: None,
+            pages: paginate(document),
+            file_name: document
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+                .into(),
+            page_range,
+            copies,
+            preview_index: 0,
+            _load_printers: load_printers,
+        }
+    }
+
+    // None when the document isn't text we could paginate
+    fn page_count(&self) -> Option<usize> {
+        (!self.pages.is_empty()).then_some(self.pages.len())
+    }
+
+    fn selected_pages(&self, cx: &App) -> Result<Vec<usize>, String> {
+        let text = self.page_range.read(cx).text();
+        if cfg!(target_os = "windows") && !text.trim().is_empty() {
+            return Err(PAGES_UNSUPPORTED.to_string());
+        }
+        parse_page_range(text, self.page_count())
+    }
+
+    fn copies(&self, cx: &App) -> Result<usize, String> {
+        match self.copies.read(cx).text().trim().parse::<usize>() {
+            Ok(copies) if (1..=999).contains(&copies) => Ok(copies),
+            _ => Err("Copies must be between 1 and 999".to_string()),
+        }
+    }
+
+    // The first problem with the current settings, shown under the fields
+    fn error(&self, cx: &App) -> Option<String> {
+        self.selected_pages(cx)
+            .err()
+            .or_else(|| self.copies(cx).err())
+    }
+
+    fn select_printer(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.selected_printer = Some(index);
+        cx.notify();
+    }
+
+    fn step_preview(&mut self, delta: isize, cx: &mut Context<Self>) {
+        let count = self
+            .selected_pages(cx)
+            .map(|pages| pages.len())
+            .unwrap_or(0);
+        let index = self.preview_index as isize + delta;
+        if (0..count as isize).contains(&index) {
+            self.preview_index = index as usize;
+            cx.notify();
+        }
+    }
+
+    fn render_settings(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = |text: &'static str| {
+            div()
+                .text_size(px(12.0))
+                .text_color(rgb(0x3C3C3C))
+                .child(text)
+        };
+
+        let printer_list = if self.loading_printers {
+            div()
+                .p_2()
+                .text_color(rgb(0x8E8E8E))
+                .child("Looking for printers…")
+        } else if self.printers.is_empty() {
+            div()
+                .p_2()
+                .text_color(rgb(0x8E8E8E))
+                .child("No printers found")
+        } else {
+            div().children(self.printers.iter().enumerate().map(|(index, printer)| {
+                let selected = self.selected_printer == Some(index);
+                div()
+                    .id(("printer", index))
+                    .flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    .cursor_pointer()
+                    .when(selected, |row| {
+                        row.bg(rgb(0x007AFF)).text_color(rgb(0xFFFFFF))
+                    })
+                    .when(!selected, |row| row.hover(|style| style.bg(rgb(0xF0F0F0))))
+                    .on_click(cx.listener(move |this, _, _, cx| this.select_printer(index, cx)))
+                    .child(printer.name.clone())
+                    .when(printer.is_default, |row| row.child("Default"))
+            }))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .gap_1()
+            .text_size(px(13.0))
+            .child(label("Printer"))
+            .child(
+                // The printer list: a white box with selectable rows
+                div()
+                    .id("printers")
+                    .h(px(120.0))
+                    .overflow_y_scroll()
+                    .bg(rgb(0xFFFFFF))
+                    .border_1()
+                    .border_color(rgb(0xB8B8B8))
+                    .rounded(px(5.0))
+                    .child(printer_list),
+            )
+            .child(
+                // Labels to the left of their inputs
+                div().mt_2().child(
+                    grid([Track::Fixed(px(52.0)), Track::Fraction(1.0)])
+                        .gap(px(8.0))
+                        .cell(label("Pages"))
+                        .cell(self.page_range.clone())
+                        .cell(label("Copies"))
+                        .cell(self.copies.clone()),
+                ),
+            )
+            .children(self.error(cx).map(|error| {
+                div()
+                    .mt_1()
+                    .text_size(px(11.0))
+                    .text_color(rgb(0xD70015))
+                    .child(error)
+            }))
+    }
+
+    fn render_preview(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = self.selected_pages(cx).unwrap_or_default();
+        let page_number = selected.get(self.preview_index).copied();
+        let copies = self.copies(cx).unwrap_or(1);
+
+        // The lines of the previewed page, in a tiny font
+        let page_lines = page_number
+            .and_then(|number| self.pages.get(number - 1))
+            .cloned()
+            .unwrap_or_default();
+
+        let page = div()
+            .w(px(PAGE_WIDTH))
+            .h(px(PAGE_HEIGHT))
+            .p(px(10.0))
+            .bg(rgb(0xFFFFFF))
+            .map(|this| {
+                if rendering::lite(cx) {
+                    this.border_1().border_color(rgb(0xB8B8B8))
+                } else {
+                    this.shadow_md()
+                }
+            })
+            .overflow_hidden()
+            .font_family("monospace")
+            .text_size(px(2.4))
+            .line_height(px(3.1))
+            .text_color(rgb(0x303030))
+            .when(self.pages.is_empty(), |page| {
+                // Nothing we can preview: show the file name instead
+                page.flex()
+                    .items_center()
+                    .justify_center()
+                    .text_size(px(9.0))
+                    .line_height(relative(1.4))
+                    .text_color(rgb(0x8E8E8E))
+                    .child(self.file_name.clone())
+            })
+            .children(page_lines.into_iter().map(|line| div().child(line)));
+
+        let pages = match (page_number, self.page_count()) {
+            (Some(number), Some(count)) => format!("Page {number} of {count}"),
+            (Some(number), None) => format!("Page {number}"),
+            (None, Some(_)) => "No pages selected".to_string(),
+            (None, None) => "All pages".to_string(),
+        };
+        let caption = if copies > 1 {
+            format!("{pages} · {copies} copies")
+        } else {
+            pages
+        };
+
+        // ‹ and › flip through the pages that will be printed
+        let arrow = |id: &'static str, text: &'static str, delta: isize| {
+            div()
+                .id(id)
+                .px_2()
+                .rounded(px(4.0))
+                .cursor_pointer()
+                .hover(|style| style.bg(rgb(0xDADADA)))
+                .on_click(cx.listener(move |this, _, _, cx| this.step_preview(delta, cx)))
+                .child(text)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .justify_center()
+            .gap_2()
+            .w(px(220.0))
+            .py_3()
+            .bg(rgb(0xE2E2E2))
+            .rounded(px(6.0))
+            .child(page)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .text_size(px(11.0))
+                    .text_color(rgb(0x4D4D4D))
+                    .child(arrow("previous-page", "‹", -1))
+                    .child(caption)
+                    .child(arrow("next-page", "›", 1)),
+            )
+    }
+}
+
+impl Render for PrintPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .gap_4()
+            .size_full()
+            .child(self.render_settings(cx))
+            .child(self.render_preview(cx))
+    }
+}
+
+impl DialogContent for PrintPanel {
+    fn values(&self, cx: &App) -> BTreeMap<String, String> {
+        let printer = self
+            .selected_printer
+            .and_then(|index| self.printers.get(index))
+            .map(|printer| printer.name.clone())
+            .unwrap_or_default();
+
+        BTreeMap::from([
+            ("printer".to_string(), printer),
+            (
+                "pages".to_string(),
+                self.page_range.read(cx).text().trim().to_string(),
+            ),
+            (
+                "copies".to_string(),
+                self.copies.read(cx).text().trim().to_string(),
+            ),
+        ])
+    }
+
+    fn can_confirm(&self, cx: &App) -> bool {
+        self.selected_printer.is_some() && self.error(cx).is_none()
+    }
+}
+
+// Split a text document into pages of lines. Anything that isn't UTF-8
+// text gets no pages (and no preview).
+fn paginate(document: &Path) -> Vec<Vec<SharedString>> {
+    let Ok(text) = std::fs::read_to_string(document) else {
+        return Vec::new();
+    };
+    let lines: Vec<SharedString> = text.lines().map(|line| line.to_string().into()).collect();
+    if lines.is_empty() {
+        return vec![Vec::new()];
+    }
+    lines
+        .chunks(LINES_PER_PAGE)
+        .map(|page| page.to_vec())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_page_range;
+
+    #[test]
+    fn empty_means_every_page() {
+        assert_eq!(parse_page_range("  ", Some(3)), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_page_range("", None), Ok(vec![]));
+    }
+
+    #[test]
+    fn pages_and_ranges_are_sorted_without_repeats() {
+        assert_eq!(parse_page_range("5, 1-3, 2", Some(5)), Ok(vec![1, 2, 3, 5]));
+        assert_eq!(parse_page_range(" 2 - 4 ", Some(9)), Ok(vec![2, 3, 4]));
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_range() {
+        assert!(parse_page_range("a", Some(5)).is_err());
+        assert!(parse_page_range("0", Some(5)).is_err());
+        assert!(parse_page_range("3-1", Some(5)).is_err());
+        assert!(parse_page_range("1,,2", Some(5)).is_err());
+    }
+
+    #[test]
+    fn checks_the_page_count_only_when_known() {
+        assert_eq!(
+            parse_page_range("2", Some(1)),
+            Err("The document has only 1 page".to_string())
+        );
+        assert_eq!(
+            parse_page_range("4-6", Some(5)),
+            Err("The document has only 5 pages".to_string())
+        );
+        assert_eq!(parse_page_range("4-6", None), Ok(vec![4, 5, 6]));
+        assert!(parse_page_range("1-99999999", None).is_err());
+    }
+}
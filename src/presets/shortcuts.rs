@@ -0,0 +1,342 @@
+// ======================================================================
+// KEYBOARD SHORTCUTS PRESET
+// ======================================================================
+// A cheat-sheet of every key binding registered with the app, grouped by
+// action namespace and searchable by name or key:
+//
+//     ShortcutsDialog::new().show(cx, |_, _| {});
+//
+// Or let a key open and close it from any window:
+//
+//     ShortcutsDialog::bind_toggle("?", cx);
+//
+// A key that types something, like "?", is left alone in text inputs so
+// it can still be typed there.
+//
+// The sheet reads the keymap when it opens, so bindings registered later
+// (or by other crates) show up without any extra wiring. Bindings from
+// keymap.toml are marked as custom, and entries of it that were skipped
+// are listed above the shortcuts (see keymap.rs).
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use crate::components::kbd::keystroke_labels;
+use crate::components::{EmptyState, Kbd, TextInput};
+use crate::dialog::{ContentEvent, Dialog, DialogButton, DialogContent, DialogHandle};
+use crate::error::DialogError;
+use crate::keymap;
+use crate::result::DialogResult;
+
+actions!(shortcuts, [ToggleShortcuts]);
+
+#[derive(Default)]
+pub struct ShortcutsDialog {
+    title: Option<SharedString>,
+}
+
+impl ShortcutsDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Replaces the default "Keyboard Shortcuts" title
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn show(
+        self,
+        cx: &mut App,
+        on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+    ) -> Result<DialogHandle, DialogError> {
+        let title = self.title.unwrap_or_else(|| "Keyboard Shortcuts".into());
+
+        Dialog::custom("", |_window, cx| {
+            let panel = cx.new(ShortcutsPanel::new);
+            cx.set_global(OpenSheet(Some(panel.downgrade())));
+            panel
+        })
+        .title(title)
+        .buttons([DialogButton::primary("Close")])
+        .size(size(px(520.0), px(540.0)))
+        .show(cx, move |result, cx| {
+            cx.set_global(OpenSheet(None));
+            on_resolve(result, cx);
+        })
+    }
+
+    // Bind `key` (e.g. "?" or "secondary-/") to show the sheet, or close
+    // it again if it's already open. Calling it again adds another key.
+    pub fn bind_toggle(key: &str, cx: &mut App) {
+        cx.bind_keys([KeyBinding::new(key, ToggleShortcuts, None)]);
+        if keymap::types_text(key) {
+            // Unbound again where it's text
+            cx.bind_keys([KeyBinding::new(key, NoAction, Some("TextInput"))]);
+        }
+        // One handler however many keys, or a press would open the sheet
+        // and close it again
+        if cx.has_global::<ToggleHandled>() {
+            return;
+        }
+        cx.set_global(ToggleHandled);
+        cx.on_action(|_: &ToggleShortcuts, cx| {
+            let open = cx
+                .try_global::<OpenSheet>()
+                .and_then(|sheet| sheet.0.as_ref())
+                .and_then(WeakEntity::upgrade);
+            match open {
+                Some(panel) => panel.update(cx, |_, cx| cx.emit(ContentEvent::Dismiss)),
+                None => {
+                    if let Err(err) = ShortcutsDialog::new().show(cx, |_, _| {}) {
+                        log::error!("couldn't show the shortcuts: {err}");
+                    }
+                }
+            }
+        });
+    }
+}
+
+// The sheet currently on screen, so the toggle key can close it
+struct OpenSheet(Option<WeakEntity<ShortcutsPanel>>);
+
+impl Global for OpenSheet {}
+
+// Set once bind_toggle() has installed the ToggleShortcuts handler
+struct ToggleHandled;
+
+impl Global for ToggleHandled {}
+
+// ======================================================================
+// SHORTCUT LIST
+// ======================================================================
+
+struct Shortcut {
+    // Human-readable namespace and action name
+    group: SharedString,
+    action: SharedString,
+    // The key sequence, usually a single keystroke
+    keystrokes: Vec<KeybindingKeystroke>,
+    // From keymap.toml rather than the code
+    custom: bool,
+    // Lower-cased text the search matches against
+    search_text: String,
+}
+
+// Every binding in the keymap, sorted into groups
+fn collect_shortcuts(cx: &App) -> Vec<Shortcut> {
+    let keymap = cx.key_bindings();
+    let keymap = keymap.borrow();
+
+    let mut shortcuts: Vec<Shortcut> = Vec::new();
+    for binding in keymap.bindings() {
+        // NoAction bindings exist only to disable other bindings
+        if is_no_action(binding.action()) {
+            continue;
+        }
+
+        let (group, action) = match binding.action().name().rsplit_once("::") {
+            Some((namespace, name)) => (humanize(namespace), humanize(name)),
+            None => ("General".to_string(), humanize(binding.action().name())),
+        };
+        let keystrokes = binding.keystrokes().to_vec();
+
+        // The same binding can appear in several contexts; list it once
+        let duplicate = shortcuts
+            .iter()
+            .any(|shortcut| shortcut.action == action && shortcut.keystrokes == keystrokes);
+        if duplicate {
+            continue;
+        }
+
+        let keys: Vec<_> = keystrokes.iter().flat_map(keystroke_labels).collect();
+        let search_text = format!("{group} {action} {}", keys.join(" ")).to_lowercase();
+        shortcuts.push(Shortcut {
+            group: group.into(),
+            action: action.into(),
+            keystrokes,
+            custom: binding.meta() == Some(keymap::FROM_FILE),
+            search_text,
+        });
+    }
+
+    // Stable, so bindings keep their keymap order within a group
+    shortcuts.sort_by(|a, b| a.group.cmp(&b.group));
+    shortcuts
+}
+
+// "text_input" → "Text input", "SelectAll" → "Select all"
+fn humanize(name: &str) -> String {
+    let mut words = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c == '_' {
+            words.push(' ');
+        } else if c.is_uppercase() && index > 0 {
+            words.push(' ');
+            words.extend(c.to_lowercase());
+        } else if index == 0 {
+            words.extend(c.to_uppercase());
+        } else {
+            words.push(c);
+        }
+    }
+    words
+}
+
+// ======================================================================
+// SHORTCUTS PANEL COMPONENT
+// ======================================================================
+
+struct ShortcutsPanel {
+    shortcuts: Vec<Shortcut>,
+    search: Entity<TextInput>,
+}
+
+impl EventEmitter<ContentEvent> for ShortcutsPanel {}
+
+impl ShortcutsPanel {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let search = cx.new(|cx| TextInput::new(cx).placeholder("Search shortcuts"));
+        // Filter as the user types
+        cx.observe(&search, |_, _, cx| cx.notify()).detach();
+
+        Self {
+            shortcuts: collect_shortcuts(cx),
+            search,
+        }
+    }
+
+    fn render_row(shortcut: &Shortcut) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .py_1()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(shortcut.action.clone())
+                    .when(shortcut.custom, |this| {
+                        this.child(
+                            div()
+                                .text_size(px(11.0))
+                                .text_color(rgb(0x8E8E8E))
+                                .child("custom"),
+                        )
+                    }),
+            )
+            .child(
+                // One group of chips per keystroke in the sequence
+                div().flex().gap_2().children(
+                    shortcut
+                        .keystrokes
+                        .iter()
+                        .map(|keystroke| div().flex().gap_1().children(Kbd::keystroke(keystroke))),
+                ),
+            )
+    }
+}
+
+impl Render for ShortcutsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let query = self.search.read(cx).text().trim().to_lowercase();
+        let matches: Vec<&Shortcut> = self
+            .shortcuts
+            .iter()
+            .filter(|shortcut| shortcut.search_text.contains(&query))
+            .collect();
+
+        // Rows, with a header wherever the group changes
+        let mut rows: Vec<AnyElement> = Vec::new();
+        let mut current_group: Option<&SharedString> = None;
+        for shortcut in &matches {
+            if current_group != Some(&shortcut.group) {
+                current_group = Some(&shortcut.group);
+                rows.push(
+                    div()
+                        .mt_3()
+                        .mb_1()
+                        .text_size(px(11.0))
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(rgb(0x8E8E8E))
+                        .child(shortcut.group.to_uppercase())
+                        .into_any_element(),
+                );
+            }
+            rows.push(Self::render_row(shortcut).into_any_element());
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .size_full()
+            .text_size(px(13.0))
+            .child(self.search.clone())
+            // What keymap.toml had that couldn't be bound
+            .children(keymap::problems(cx).iter().map(|problem| {
+                div()
+                    .text_size(px(11.0))
+                    .text_color(rgb(0xD70015))
+                    .child(format!("keymap.toml: {problem}"))
+            }))
+            .child(
+                div()
+                    .id("shortcuts")
+                    .flex_1()
+                    .px_3()
+                    .pb_2()
+                    .overflow_y_scroll()
+                    .bg(rgb(0xFFFFFF))
+                    .border_1()
+                    .border_color(rgb(0xB8B8B8))
+                    .rounded(px(5.0))
+                    .children(rows)
+                    .when(matches.is_empty(), |list| {
+                        list.child(
+                            EmptyState::new(format!("No shortcuts match “{query}”")).icon("🔍"),
+                        )
+                    }),
+            )
+    }
+}
+
+impl DialogContent for ShortcutsPanel {
+    fn initial_focus(&self, cx: &App) -> Option<FocusHandle> {
+        Some(self.search.focus_handle(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+
+    use super::{ShortcutsDialog, ToggleShortcuts};
+    use crate::dialog::DialogBox;
+
+    #[gpui::test]
+    fn more_keys_still_toggle_once(cx: &mut TestAppContext) {
+        let open = |cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                cx.windows()
+                    .iter()
+                    .filter(|window| window.downcast::<DialogBox>().is_some())
+                    .count()
+            })
+        };
+        cx.update(|cx| {
+            ShortcutsDialog::bind_toggle("f1", cx);
+            ShortcutsDialog::bind_toggle("?", cx);
+            cx.dispatch_action(&ToggleShortcuts);
+        });
+        cx.run_until_parked();
+        assert_eq!(open(cx), 1);
+
+        cx.update(|cx| cx.dispatch_action(&ToggleShortcuts));
+        cx.run_until_parked();
+        assert_eq!(open(cx), 0);
+    }
+}
@@ -0,0 +1,76 @@
+// ======================================================================
+// SCREEN CAPTURE PROTECTION
+// ======================================================================
+// Dialogs marked sensitive (password prompts, recovery codes) ask the
+// system to leave their window out of screenshots, screen recordings and
+// screen sharing. The window shows as blank or not at all in the capture,
+// while the person in front of the screen sees it as usual.
+//
+// macOS and Windows support this; Linux has no way to ask, so there the
+// dialog says it's visible to screen capture instead.
+
+use gpui::Window;
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+// Whether the window is now excluded from capture
+pub(crate) fn exclude_from_capture(window: &Window) -> bool {
+    // Window has an inherent window_handle() too, for gpui's own handle
+    match HasWindowHandle::window_handle(window) {
+        Ok(handle) => exclude(handle.as_raw()),
+        Err(_) => false,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn exclude(handle: RawWindowHandle) -> bool {
+    use std::ffi::{c_char, c_void};
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn sel_registerName(name: *const c_char) -> *const c_void;
+        fn objc_msgSend();
+    }
+
+    let RawWindowHandle::AppKit(handle) = handle else {
+        return false;
+    };
+    // objc_msgSend has to be called through a pointer of the right type
+    // for each message
+    unsafe {
+        let get: unsafe extern "C" fn(*mut c_void, *const c_void) -> *mut c_void =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let set: unsafe extern "C" fn(*mut c_void, *const c_void, usize) =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+        let window = get(
+            handle.ns_view.as_ptr(),
+            sel_registerName(c"window".as_ptr()),
+        );
+        if window.is_null() {
+            return false;
+        }
+        // NSWindowSharingNone
+        set(window, sel_registerName(c"setSharingType:".as_ptr()), 0);
+    }
+    true
+}
+
+#[cfg(target_os = "windows")]
+fn exclude(handle: RawWindowHandle) -> bool {
+    #[link(name = "user32")]
+    extern "system" {
+        fn SetWindowDisplayAffinity(hwnd: isize, affinity: u32) -> i32;
+    }
+    // Windows 10 2004 and later; older versions fail and we say so
+    const WDA_EXCLUDEFROMCAPTURE: u32 = 0x11;
+
+    let RawWindowHandle::Win32(handle) = handle else {
+        return false;
+    };
+    unsafe { SetWindowDisplayAffinity(handle.hwnd.get(), WDA_EXCLUDEFROMCAPTURE) != 0 }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn exclude(_handle: RawWindowHandle) -> bool {
+    false
+}
@@ -0,0 +1,80 @@
+// ======================================================================
+// LITE RENDERING
+// ======================================================================
+// Soft shadows and antialiased rounded corners are cheap on a GPU but
+// slow to draw in software, as in virtual machines without graphics
+// acceleration, and they make every repaint bigger over a remote desktop.
+// In lite mode dialogs draw plain rectangles instead: square corners, and
+// a one-pixel border where there would be a shadow.
+//
+//     if rendering::lite(cx) { ... }
+//
+// init() turns it on by itself under a software OpenGL renderer
+// (llvmpipe, softpipe, or LIBGL_ALWAYS_SOFTWARE), in a virtual machine,
+// or over a remote desktop.
+// GPUI_DIALOGS_LITE=1 forces it on and GPUI_DIALOGS_LITE=0 off, and an
+// app can decide for itself:
+//
+//     rendering::set_lite(true, cx);
+
+use std::env;
+use std::fs;
+
+use gpui::{App, Global};
+
+use crate::backdrop;
+
+const OVERRIDE: &str = "GPUI_DIALOGS_LITE";
+
+// Words in the DMI product and vendor names of common hypervisors
+const HYPERVISORS: [&str; 7] = [
+    "virtualbox",
+    "vmware",
+    "qemu",
+    "kvm",
+    "bochs",
+    "parallels",
+    "virtual machine", // Hyper-V
+];
+
+struct Lite(bool);
+
+impl Global for Lite {}
+
+pub fn lite(cx: &App) -> bool {
+    cx.try_global::<Lite>().is_some_and(|lite| lite.0)
+}
+
+pub fn set_lite(lite: bool, cx: &mut App) {
+    cx.set_global(Lite(lite));
+    cx.refresh_windows();
+}
+
+pub(crate) fn detect(cx: &mut App) {
+    let lite = match env::var(OVERRIDE).as_deref() {
+        Ok("1") => true,
+        Ok("0") => false,
+        _ => software_rendering() || virtual_machine() || backdrop::remote_session(),
+    };
+    set_lite(lite, cx);
+}
+
+fn software_rendering() -> bool {
+    env::var("LIBGL_ALWAYS_SOFTWARE").is_ok_and(|value| value != "0")
+        || env::var("GALLIUM_DRIVER")
+            .is_ok_and(|driver| matches!(driver.as_str(), "llvmpipe" | "softpipe" | "swrast"))
+}
+
+fn virtual_machine() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    ["product_name", "sys_vendor"].iter().any(|name| {
+        fs::read_to_string(format!("/sys/class/dmi/id/{name}")).is_ok_and(|text| {
+            let text = text.to_lowercase();
+            HYPERVISORS
+                .iter()
+                .any(|hypervisor| text.contains(hypervisor))
+        })
+    })
+}
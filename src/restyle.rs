@@ -0,0 +1,76 @@
+// ======================================================================
+// RESTYLING
+// ======================================================================
+// An app embedding these dialogs can restyle components without forking
+// them. An interceptor registered under a component's name runs after the
+// component's own styling, on its outermost element, and whatever it sets
+// wins:
+//
+//     restyle::register("Button::Primary", |style| style.bg(rgb(0x2E7D32)), cx);
+//     restyle::register("Dialog", |style| style.rounded_none(), cx);
+//
+// A name with a variant also gets what's registered for the bare name,
+// first: "Button" restyles every button, "Button::Primary" only the
+// primary ones. The names:
+//
+//   - Button, Button::Primary, Button::Secondary
+//   - Badge, Checkbox, EmptyState, Kbd, ProgressBar
+//   - Menu (each level), StatusBar, TextInput
+//   - Dialog (the window's rounded box), Dialog::Titlebar
+//
+// Interceptors stay for the life of the app; register them once, after
+// init(). Their styles can't depend on a component's state, so hover and
+// focus looks come through untouched unless an interceptor sets them too.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gpui::{App, Global, Refineable, StyleRefinement, Styled};
+
+type Interceptor = Rc<dyn Fn(StyleRefinement) -> StyleRefinement>;
+
+#[derive(Default)]
+struct Interceptors(HashMap<&'static str, Vec<Interceptor>>);
+
+impl Global for Interceptors {}
+
+pub fn register(
+    component: &'static str,
+    interceptor: impl Fn(StyleRefinement) -> StyleRefinement + 'static,
+    cx: &mut App,
+) {
+    cx.default_global::<Interceptors>()
+        .0
+        .entry(component)
+        .or_default()
+        .push(Rc::new(interceptor));
+    cx.refresh_windows();
+}
+
+// Forget the interceptors registered under `component`
+pub fn clear(component: &str, cx: &mut App) {
+    cx.default_global::<Interceptors>().0.remove(component);
+    cx.refresh_windows();
+}
+
+// Called by the components on their outermost element, last
+pub(crate) fn apply<E: Styled>(component: &str, mut element: E, cx: &App) -> E {
+    let Some(interceptors) = cx.try_global::<Interceptors>() else {
+        return element;
+    };
+    let base = component.split("::").next().unwrap_or(component);
+    let names = if base == component {
+        vec![component]
+    } else {
+        vec![base, component]
+    };
+    for interceptor in names
+        .into_iter()
+        .filter_map(|name| interceptors.0.get(name))
+        .flatten()
+    {
+        let refinement = interceptor(StyleRefinement::default());
+        element.style().refine(&refinement);
+    }
+    element
+}
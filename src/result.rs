@@ -0,0 +1,365 @@
+// ======================================================================
+// DIALOG RESULT
+// ======================================================================
+// What a dialog hands back when it closes: how it was closed (the
+// outcome), any values the user entered keyed by field id, and some
+// metrics about how the user got there.
+//
+// Results serialize with serde, to the same JSON the CLI prints with
+// --result-format json:
+//
+//     {"outcome": "button", "button": "Save", "role": "primary",
+//      "values": {"name": "notes.txt"}, "metrics": {...}}
+//
+// The values are text as typed. parse() reads them into a struct of
+// your own, converting each to its field's type:
+//
+//     #[derive(Deserialize)]
+//     struct Connection {
+//         host: String,
+//         port: u16,
+//         key_file: Option<PathBuf>,
+//     }
+//
+//     let connection: Connection = result.parse()?;
+//
+// An empty value reads as None, and one holding commas as a list (the
+// "selected" indices of a list dialog, say). selection() gives the rows
+// picked in a list dialog.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use gpui::SharedString;
+use serde::de::value::{Error as ValueError, MapDeserializer, SeqDeserializer};
+use serde::de::{DeserializeOwned, Error as _, IntoDeserializer, Unexpected, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::dialog::ButtonRole;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    // One of the dialog's buttons was clicked (or triggered with Enter)
+    Button {
+        #[serde(rename = "button")]
+        label: SharedString,
+        role: ButtonRole,
+    },
+    // Closed without picking a button: ESC or the red close button
+    Dismissed,
+    // A progress dialog ran to completion
+    Completed,
+    // Nobody answered before the dialog's timeout
+    #[serde(rename = "timeout")]
+    TimedOut,
+    // Left unanswered past its max age, so the answer would no longer
+    // mean anything (see Dialog::max_age)
+    Expired,
+}
+
+// How the user interacted with the dialog, for UX analysis of prompts.
+// An "interaction" is a key press or a mouse button press anywhere in the
+// dialog window.
+// Durations are written in whole milliseconds.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogMetrics {
+    // From the dialog opening to the first interaction (None if the user
+    // never touched it, e.g. it timed out)
+    #[serde(rename = "time_to_first_interaction_ms", with = "optional_millis")]
+    pub time_to_first_interaction: Option<Duration>,
+    // From the dialog opening to it resolving
+    #[serde(rename = "open_duration_ms", with = "millis")]
+    pub open_duration: Duration,
+    pub used_keyboard: bool,
+    pub used_mouse: bool,
+    pub timed_out: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogResult {
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    // What the user entered or picked: form values keyed by field id, a
+    // list's "selection", whatever custom content reports. Empty when
+    // there's nothing to report.
+    pub values: BTreeMap<String, String>,
+    pub metrics: DialogMetrics,
+}
+
+impl DialogResult {
+    pub fn new(outcome: Outcome) -> Self {
+        Self {
+            outcome,
+            values: BTreeMap::new(),
+            metrics: DialogMetrics::default(),
+        }
+    }
+
+    // Label of the clicked button, if a button closed the dialog
+    pub fn button(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Button { label, .. } => Some(label),
+            _ => None,
+        }
+    }
+
+    // True when the user went ahead: the primary button was chosen or the
+    // operation completed
+    pub fn is_confirmed(&self) -> bool {
+        matches!(
+            self.outcome,
+            Outcome::Button {
+                role: ButtonRole::Primary,
+                ..
+            } | Outcome::Completed
+        )
+    }
+
+    pub fn is_dismissed(&self) -> bool {
+        self.outcome == Outcome::Dismissed
+    }
+
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    // The values read into `T`, typically a struct with a field per form
+    // field, or what doesn't fit
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, String> {
+        let values = self
+            .values
+            .iter()
+            .map(|(key, value)| (key.as_str(), Value(value)));
+        T::deserialize(MapDeserializer::<_, ValueError>::new(values)).map_err(|err| err.to_string())
+    }
+
+    // The rows picked in a list dialog, in list order
+    pub fn selection(&self) -> Vec<&str> {
+        self.value("selection")
+            .filter(|selection| !selection.is_empty())
+            .map(|selection| selection.split('\n').collect())
+            .unwrap_or_default()
+    }
+}
+
+// ======================================================================
+// TYPED VALUES
+// ======================================================================
+
+// One value as typed, deserializing into whatever type asks for it
+struct Value<'a>(&'a str);
+
+// Numbers, booleans and chars parse from the text
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+                match self.0.trim().parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(ValueError::invalid_value(Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Value<'_> {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        visitor.visit_str(self.0)
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        let items = self.0.split(',').filter(|_| !self.0.is_empty()).map(Value);
+        visitor.visit_seq(SeqDeserializer::new(items))
+    }
+
+    // Unit variants by name
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ValueError> {
+        IntoDeserializer::<ValueError>::into_deserializer(self.0)
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct newtype_struct
+        tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl IntoDeserializer<'_, ValueError> for Value<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+mod millis {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+mod optional_millis {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        duration
+            .map(|duration| duration.as_millis() as u64)
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<u64>::deserialize(deserializer).map(|millis| millis.map(Duration::from_millis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::{DialogResult, Outcome};
+
+    fn result(values: &[(&str, &str)]) -> DialogResult {
+        let mut result = DialogResult::new(Outcome::Completed);
+        for (key, value) in values {
+            result.values.insert(key.to_string(), value.to_string());
+        }
+        result
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Protocol {
+        Ssh,
+        Sftp,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Connection {
+        host: String,
+        port: u16,
+        ratio: f32,
+        verbose: bool,
+        protocol: Protocol,
+        key_file: Option<String>,
+        comment: Option<String>,
+        selected: Vec<usize>,
+    }
+
+    #[test]
+    fn reads_values_into_their_types() {
+        let connection: Connection = result(&[
+            ("host", "example.com"),
+            ("port", " 22 "),
+            ("ratio", "0.5"),
+            ("verbose", "true"),
+            ("protocol", "sftp"),
+            ("comment", ""),
+            ("selected", "0,2,5"),
+        ])
+        .parse()
+        .unwrap();
+        assert_eq!(
+            connection,
+            Connection {
+                host: "example.com".to_string(),
+                port: 22,
+                ratio: 0.5,
+                verbose: true,
+                protocol: Protocol::Sftp,
+                // Missing
+                key_file: None,
+                // Empty
+                comment: None,
+                selected: vec![0, 2, 5],
+            }
+        );
+    }
+
+    #[test]
+    fn an_empty_list_has_no_items() {
+        #[derive(Deserialize)]
+        struct Picked {
+            selected: Vec<usize>,
+        }
+        let picked: Picked = result(&[("selected", "")]).parse().unwrap();
+        assert!(picked.selected.is_empty());
+    }
+
+    #[test]
+    fn says_what_doesnt_fit() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Port {
+            port: u16,
+        }
+        let err = result(&[("port", "ssh")]).parse::<Port>().unwrap_err();
+        assert!(err.contains("invalid value: string \"ssh\""), "{err}");
+        let err = result(&[("port", "70000")]).parse::<Port>().unwrap_err();
+        assert!(err.contains("70000"), "{err}");
+        let err = result(&[]).parse::<Port>().unwrap_err();
+        assert_eq!(err, "missing field `port`");
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Choice {
+            protocol: Protocol,
+        }
+        let err = result(&[("protocol", "ftp")])
+            .parse::<Choice>()
+            .unwrap_err();
+        assert!(err.contains("unknown variant `ftp`"), "{err}");
+    }
+
+    #[test]
+    fn selection_is_one_row_per_line() {
+        assert_eq!(
+            result(&[("selection", "a.txt\nb c.txt")]).selection(),
+            ["a.txt", "b c.txt"]
+        );
+        assert!(result(&[("selection", "")]).selection().is_empty());
+        assert!(result(&[]).selection().is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+// ======================================================================
+// WORK AREA
+// ======================================================================
+// The part of a display that windows should use: its full bounds minus
+// the menu bar, the Dock or taskbar, and panels. On Macs with a notch
+// the menu bar covers it, so the work area is below the notch too.
+//
+// Dialogs are centered in it, and anything docked to an edge should be
+// placed against it rather than the raw display bounds, so it isn't
+// hidden behind the taskbar:
+//
+//     let area = screen::work_area(display.as_ref());
+//     let origin = point(area.right() - width, area.bottom() - height);
+//
+// Bounds are relative to the display's top-left corner, like the window
+// bounds GPUI takes together with a display_id. Asked of the system each
+// time, since the Dock and panels can move; when it can't tell, the work
+// area is the whole display.
+
+use gpui::{point, px, size, Bounds, Pixels, PlatformDisplay, Size};
+
+// Room taken away from each edge of the display
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Insets {
+    top: f32,
+    right: f32,
+    bottom: f32,
+    left: f32,
+}
+
+pub fn work_area(display: &dyn PlatformDisplay) -> Bounds<Pixels> {
+    let display_size = display.bounds().size;
+    let insets = system_insets(display_size).unwrap_or_default();
+    let insets = Insets {
+        top: insets.top.max(0.0),
+        right: insets.right.max(0.0),
+        bottom: insets.bottom.max(0.0),
+        left: insets.left.max(0.0),
+    };
+    let width = f32::from(display_size.width) - insets.left - insets.right;
+    let height = f32::from(display_size.height) - insets.top - insets.bottom;
+    // Nonsense from the system would leave nothing to show a window in
+    if width < 200.0 || height < 200.0 {
+        return Bounds {
+            origin: point(px(0.0), px(0.0)),
+            size: display_size,
+        };
+    }
+    Bounds {
+        origin: point(px(insets.left), px(insets.top)),
+        size: size(px(width), px(height)),
+    }
+}
+
+// Bounds of `window_size` centered in the work area, shrunk to fit if it
+// is bigger than that
+pub fn centered(display: &dyn PlatformDisplay, window_size: Size<Pixels>) -> Bounds<Pixels> {
+    let area = work_area(display);
+    let window_size = size(
+        window_size.width.min(area.size.width),
+        window_size.height.min(area.size.height),
+    );
+    Bounds {
+        origin: point(
+            area.origin.x + (area.size.width - window_size.width) / 2.0,
+            area.origin.y + (area.size.height - window_size.height) / 2.0,
+        ),
+        size: window_size,
+    }
+}
+
+// NSScreen knows the visible frame of the screen with the menu bar, the
+// one GPUI lists first
+#[cfg(target_os = "macos")]
+fn system_insets(_display_size: Size<Pixels>) -> Option<Insets> {
+    use std::ffi::{c_char, c_void};
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct NSRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    }
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> *mut c_void;
+        fn sel_registerName(name: *const c_char) -> *const c_void;
+        fn objc_msgSend();
+        #[cfg(target_arch = "x86_64")]
+        fn objc_msgSend_stret();
+    }
+
+    unsafe {
+        let get: unsafe extern "C" fn(*mut c_void, *const c_void) -> *mut c_void =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        // Structs this big come back through a different entry point on
+        // Intel Macs
+        #[cfg(target_arch = "x86_64")]
+        let get_rect: unsafe extern "C" fn(*mut c_void, *const c_void) -> NSRect =
+            std::mem::transmute(objc_msgSend_stret as unsafe extern "C" fn());
+        #[cfg(not(target_arch = "x86_64"))]
+        let get_rect: unsafe extern "C" fn(*mut c_void, *const c_void) -> NSRect =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+        let screens = get(
+            objc_getClass(c"NSScreen".as_ptr()),
+            sel_registerName(c"screens".as_ptr()),
+        );
+        if screens.is_null() {
+            return None;
+        }
+        let screen = get(screens, sel_registerName(c"firstObject".as_ptr()));
+        if screen.is_null() {
+            return None;
+        }
+        let frame = get_rect(screen, sel_registerName(c"frame".as_ptr()));
+        let visible = get_rect(screen, sel_registerName(c"visibleFrame".as_ptr()));
+
+        // Cocoa measures from the bottom-left corner
+        Some(Insets {
+            top: ((frame.y + frame.height) - (visible.y + visible.height)) as f32,
+            right: ((frame.x + frame.width) - (visible.x + visible.width)) as f32,
+            bottom: (visible.y - frame.y) as f32,
+            left: (visible.x - frame.x) as f32,
+        })
+    }
+}
+
+// The primary monitor's work area, in physical pixels
+#[cfg(target_os = "windows")]
+fn system_insets(display_size: Size<Pixels>) -> Option<Insets> {
+    #[repr(C)]
+    #[derive(Default)]
+    struct Rect {
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    }
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn SystemParametersInfoW(action: u32, param: u32, value: *mut Rect, flags: u32) -> i32;
+        fn GetDpiForSystem() -> u32;
+    }
+    const SPI_GETWORKAREA: u32 = 0x0030;
+
+    let mut area = Rect::default();
+    let (found, dpi) = unsafe {
+        (
+            SystemParametersInfoW(SPI_GETWORKAREA, 0, &mut area, 0) != 0,
+            GetDpiForSystem(),
+        )
+    };
+    if !found {
+        return None;
+    }
+    let scale = dpi.max(96) as f32 / 96.0;
+    Some(Insets {
+        top: area.top as f32 / scale,
+        right: f32::from(display_size.width) - area.right as f32 / scale,
+        bottom: f32::from(display_size.height) - area.bottom as f32 / scale,
+        left: area.left as f32 / scale,
+    })
+}
+
+// Window managers that follow the EWMH spec publish the work area on the
+// root window, in device pixels across the whole desktop. Under Wayland
+// this is XWayland's idea of it, which the big desktops keep up to date.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn system_insets(display_size: Size<Pixels>) -> Option<Insets> {
+    use std::process::Command;
+
+    let output = Command::new("xprop")
+        .args(["-root", "_NET_DESKTOP_GEOMETRY", "_NET_WORKAREA"])
+        .output()
+        .ok()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    // "_NET_WORKAREA(CARDINAL) = 0, 27, 1920, 1053, ..." (one per desktop)
+    let numbers = |property: &str| -> Option<Vec<f32>> {
+        let line = output.lines().find(|line| line.starts_with(property))?;
+        let (_, values) = line.split_once('=')?;
+        values
+            .split(',')
+            .map(|value| value.trim().parse().ok())
+            .collect()
+    };
+    let desktop = numbers("_NET_DESKTOP_GEOMETRY")?;
+    let area = numbers("_NET_WORKAREA")?;
+    let (&[desktop_width, desktop_height, ..], &[x, y, width, height, ..]) =
+        (desktop.as_slice(), area.as_slice())
+    else {
+        return None;
+    };
+
+    // Device pixels to GPUI's, assuming the display is the desktop's
+    // height (true unless monitors are stacked)
+    let scale = (desktop_height / f32::from(display_size.height)).clamp(1.0, 4.0);
+    Some(Insets {
+        top: y / scale,
+        right: (desktop_width.min(f32::from(display_size.width) * scale) - (x + width)).max(0.0)
+            / scale,
+        bottom: (desktop_height - (y + height)) / scale,
+        left: x / scale,
+    })
+}
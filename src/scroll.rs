@@ -0,0 +1,254 @@
+// ======================================================================
+// SCROLL PHYSICS
+// ======================================================================
+// GPUI moves scrolling content exactly as far as each wheel or touchpad
+// event says, and stops dead at the ends. A Scroller adds the two things
+// that make scrolling feel native:
+//
+//   - momentum: a touchpad flick keeps the content gliding after the
+//     fingers lift, slowing down as it goes. macOS sends those glide
+//     events itself, so this is only switched on elsewhere by default.
+//   - overscroll: pulled past either end, the content follows a little
+//     way, stretching like a rubber band, and springs back on release.
+//
+// Give it the scroll handle of the element, feed it the element's wheel
+// events, and offset the content by overscroll():
+//
+//     div()
+//         .id("log")
+//         .overflow_y_scroll()
+//         .track_scroll(self.scroller.handle())
+//         .on_scroll_wheel(cx.listener(|this, event, _, cx| {
+//             this.scroller.on_scroll(event, cx)
+//         }))
+//         .child(div().relative().top(self.scroller.overscroll()).children(lines))
+//
+// reveal() scrolls, with a short glide, just far enough to bring part
+// of the content fully into view, such as the form field Tab moved to:
+//
+//     if let Some(bounds) = scroller.handle().bounds_for_item(index) {
+//         scroller.reveal(bounds, cx);
+//     }
+//
+// Only vertical scrolling, which is all the dialogs do.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gpui::{
+    ease_out_quint, point, px, Bounds, Context, Pixels, ScrollDelta, ScrollHandle,
+    ScrollWheelEvent, Task,
+};
+
+use crate::motion;
+
+const FRAME: Duration = Duration::from_millis(16);
+// With no wheel events for this long, the fingers have lifted
+const LIFT_DELAY: Duration = Duration::from_millis(50);
+// Slower than this (pixels per second) and the glide is over
+const MIN_SPEED: f32 = 20.0;
+// How far content can be pulled past an end
+const MAX_OVERSCROLL: f32 = 80.0;
+// The share of the overscroll left after each frame of springing back
+const SPRING_BACK: f32 = 0.75;
+// How long reveal() takes, and the room it leaves around what it reveals
+const REVEAL_DURATION: Duration = Duration::from_millis(150);
+const REVEAL_MARGIN: Pixels = px(8.0);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollPhysics {
+    // Keep gliding after a touchpad flick
+    pub momentum: bool,
+    // The share of the gliding speed left after one second. Lower stops
+    // sooner; 0.135 is close to what iOS and macOS use.
+    pub deceleration: f32,
+    // Rubber-band past the ends
+    pub overscroll: bool,
+}
+
+impl Default for ScrollPhysics {
+    fn default() -> Self {
+        Self {
+            momentum: !cfg!(target_os = "macos"),
+            deceleration: 0.135,
+            overscroll: true,
+        }
+    }
+}
+
+pub struct Scroller {
+    handle: ScrollHandle,
+    physics: ScrollPhysics,
+    // Shared with the task that animates the glide
+    state: Rc<RefCell<State>>,
+    _glide: Option<Task<()>>,
+}
+
+#[derive(Default)]
+struct State {
+    // Pixels per second, positive towards the top like wheel deltas
+    velocity: f32,
+    last_event: Option<Instant>,
+    // Positive past the top, negative past the bottom
+    overscroll: f32,
+}
+
+impl Scroller {
+    pub fn new(handle: ScrollHandle) -> Self {
+        Self {
+            handle,
+            physics: ScrollPhysics::default(),
+            state: Rc::default(),
+            _glide: None,
+        }
+    }
+
+    pub fn physics(mut self, physics: ScrollPhysics) -> Self {
+        self.physics = physics;
+        self
+    }
+
+    pub fn handle(&self) -> &ScrollHandle {
+        &self.handle
+    }
+
+    // How far to move the content from where the scroll position puts it
+    pub fn overscroll(&self) -> Pixels {
+        px(self.state.borrow().overscroll)
+    }
+
+    // Call with every wheel event the scrolling element gets. The element
+    // has scrolled by the event already; this measures the speed, takes
+    // up any pull past the ends, and once the events stop, glides and
+    // springs back.
+    pub fn on_scroll<T: 'static>(&mut self, event: &ScrollWheelEvent, cx: &mut Context<T>) {
+        // Mouse wheels click from line to line and never glide
+        let ScrollDelta::Pixels(delta) = event.delta else {
+            return;
+        };
+        let delta = f32::from(delta.y);
+        let physics = self.physics;
+        {
+            let mut state = self.state.borrow_mut();
+            let now = Instant::now();
+            if let Some(last) = state.last_event {
+                let elapsed = now.duration_since(last).as_secs_f32().max(0.001);
+                // Smoothed, as touchpads report unevenly
+                state.velocity = state.velocity * 0.2 + delta / elapsed * 0.8;
+            }
+            state.last_event = Some(now);
+
+            if physics.overscroll && self.past_end(delta) {
+                state.pull(delta);
+                cx.notify();
+            }
+        }
+
+        // Each event pushes the glide back until the events stop
+        let state = self.state.clone();
+        let handle = self.handle.clone();
+        let reduced_motion = motion::reduced_motion(cx);
+        self._glide = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(LIFT_DELAY).await;
+            {
+                let mut state = state.borrow_mut();
+                state.last_event = None;
+                if !physics.momentum || reduced_motion {
+                    state.velocity = 0.0;
+                }
+            }
+            loop {
+                let moving = state.borrow_mut().step(&handle, physics);
+                if this.update(cx, |_, cx| cx.notify()).is_err() || !moving {
+                    return;
+                }
+                cx.background_executor().timer(FRAME).await;
+            }
+        }));
+    }
+
+    // Scroll just far enough for `target` (in window coordinates) to be
+    // fully in view, gliding there unless motion is reduced
+    pub fn reveal<T: 'static>(&mut self, target: Bounds<Pixels>, cx: &mut Context<T>) {
+        let viewport = self.handle.bounds();
+        let start = self.handle.offset();
+        let shift = if target.top() < viewport.top() {
+            viewport.top() - target.top() + REVEAL_MARGIN
+        } else if target.bottom() > viewport.bottom() {
+            // Tall targets show their top rather than their bottom
+            let past_bottom = target.bottom() - viewport.bottom() + REVEAL_MARGIN;
+            -past_bottom.min(target.top() - viewport.top())
+        } else {
+            return;
+        };
+        let max = self.handle.max_offset().height;
+        let end = (start.y + shift).clamp(-max, px(0.0));
+
+        let handle = self.handle.clone();
+        if motion::reduced_motion(cx) {
+            handle.set_offset(point(start.x, end));
+            cx.notify();
+            return;
+        }
+        self._glide = Some(cx.spawn(async move |this, cx| {
+            let started = Instant::now();
+            loop {
+                let progress =
+                    (started.elapsed().as_secs_f32() / REVEAL_DURATION.as_secs_f32()).min(1.0);
+                let eased = ease_out_quint()(progress);
+                handle.set_offset(point(start.x, start.y + (end - start.y) * eased));
+                if this.update(cx, |_, cx| cx.notify()).is_err() || progress >= 1.0 {
+                    return;
+                }
+                cx.background_executor().timer(FRAME).await;
+            }
+        }));
+    }
+
+    // Whether scrolling by `delta` pushes against an end
+    fn past_end(&self, delta: f32) -> bool {
+        let offset = self.handle.offset().y;
+        let max = self.handle.max_offset().height;
+        (delta > 0.0 && offset >= px(0.0)) || (delta < 0.0 && offset <= -max)
+    }
+}
+
+impl State {
+    // Pulling gets harder the further past the end it goes
+    fn pull(&mut self, delta: f32) {
+        let resistance = 1.0 - (self.overscroll.abs() / MAX_OVERSCROLL).min(1.0);
+        self.overscroll =
+            (self.overscroll + delta * 0.5 * resistance).clamp(-MAX_OVERSCROLL, MAX_OVERSCROLL);
+    }
+
+    // One frame of gliding and springing back; returns whether there's
+    // more to come
+    fn step(&mut self, handle: &ScrollHandle, physics: ScrollPhysics) -> bool {
+        let frame = FRAME.as_secs_f32();
+        if self.velocity.abs() >= MIN_SPEED {
+            let max = handle.max_offset().height;
+            let offset = handle.offset();
+            let y = offset.y + px(self.velocity * frame);
+            let clamped = y.clamp(-max, px(0.0));
+            handle.set_offset(point(offset.x, clamped));
+            if clamped != y {
+                // Ran into an end: what speed is left turns into a bounce
+                if physics.overscroll {
+                    self.overscroll = (self.velocity * 0.05).clamp(-MAX_OVERSCROLL, MAX_OVERSCROLL);
+                }
+                self.velocity = 0.0;
+            } else {
+                self.velocity *= physics.deceleration.clamp(0.0, 1.0).powf(frame);
+            }
+        } else {
+            self.velocity = 0.0;
+        }
+
+        self.overscroll *= SPRING_BACK;
+        if self.overscroll.abs() < 0.5 {
+            self.overscroll = 0.0;
+        }
+        self.velocity != 0.0 || self.overscroll != 0.0
+    }
+}
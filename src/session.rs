@@ -0,0 +1,322 @@
+// ======================================================================
+// SESSION RESTORE
+// ======================================================================
+// Dialogs marked restorable leave a small record behind while they're
+// open: which template they were built from and its parameters. The
+// record goes away when the dialog is answered, so any records still
+// there at the next launch belong to prompts that never got an answer
+// because the app crashed or was killed. The app can offer them again:
+//
+//     Dialog::question(message)
+//         .restorable("question", [("message", message)])
+//         .show(cx, ...);
+//
+//     // At the next launch
+//     let unanswered = session::unanswered();
+//     if !unanswered.is_empty() {
+//         session::offer_restore(unanswered, cx, |record| match record.template.as_str() {
+//             "question" => Some(Dialog::question(record.param("message"))),
+//             _ => None,
+//         }, |results, cx| { ... });
+//     }
+//
+// Only the template and parameters are kept, never what the user had
+// typed so far. Records live in session.json next to the other stored
+// state (see storage.rs), which every instance changes under a lock.
+// Waiting for that lock is no job for the UI thread, so records are
+// written in the background, one after the other, and quitting waits
+// for the last write.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::future::{FutureExt, Shared};
+use gpui::{App, AppContext, Global, Task};
+use serde_json::{json, Value};
+
+use crate::dialog::{Dialog, DialogButton};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+use crate::storage;
+
+const FILE: &str = "session.json";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionRecord {
+    pub template: String,
+    pub params: BTreeMap<String, String>,
+}
+
+impl SessionRecord {
+    pub fn new(
+        template: impl Into<String>,
+        params: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        Self {
+            template: template.into(),
+            params: params
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    // A parameter, empty if it's missing
+    pub fn param(&self, key: &str) -> &str {
+        self.params.get(key).map_or("", String::as_str)
+    }
+
+    fn to_json(&self, id: &str) -> Value {
+        json!({
+            "id": id,
+            "pid": std::process::id(),
+            "template": self.template,
+            "params": self.params,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let params = value["params"]
+            .as_object()?
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+            .collect();
+        Some(Self {
+            template: value["template"].as_str()?.to_string(),
+            params,
+        })
+    }
+}
+
+// The write to session.json started last
+struct LastWrite(Shared<Task<()>>);
+
+impl Global for LastWrite {}
+
+// Change the file in the background, after the writes before this one
+fn write(change: impl FnOnce(&mut Value) + Send + 'static, cx: &mut App) {
+    let previous = cx.try_global::<LastWrite>().map(|last| last.0.clone());
+    if previous.is_none() {
+        cx.on_app_quit(|cx| {
+            let last = cx.try_global::<LastWrite>().map(|last| last.0.clone());
+            async move {
+                if let Some(last) = last {
+                    last.await;
+                }
+            }
+        })
+        .detach();
+    }
+    let write = cx.background_spawn(async move {
+        if let Some(previous) = previous {
+            previous.await;
+        }
+        storage::update(FILE, change);
+    });
+    cx.set_global(LastWrite(write.shared()));
+}
+
+// The records in the file, taken out of it for update() to put back
+fn take(value: &mut Value) -> Vec<Value> {
+    match value.take() {
+        Value::Array(records) => records,
+        _ => Vec::new(),
+    }
+}
+
+// A fresh id for a record: unique within the process, and the pid keeps
+// it apart from other instances
+pub(crate) fn next_id() -> String {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    format!(
+        "{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+// Called when a restorable dialog opens
+pub(crate) fn remember(id: &str, record: &SessionRecord, cx: &mut App) {
+    let record = record.to_json(id);
+    write(
+        move |value| {
+            let mut records = take(value);
+            records.push(record);
+            *value = Value::Array(records);
+        },
+        cx,
+    );
+}
+
+// Called when it's answered, however that happens
+pub(crate) fn forget(id: &str, cx: &mut App) {
+    let id = id.to_string();
+    write(
+        move |value| {
+            let mut records = take(value);
+            records.retain(|record| record["id"].as_str() != Some(id.as_str()));
+            *value = Value::Array(records);
+        },
+        cx,
+    );
+}
+
+// Whether the process that left a record is still running, in which case
+// its dialog is still open and not ours to restore
+fn still_running(pid: u64) -> bool {
+    if pid == u64::from(std::process::id()) {
+        return true;
+    }
+    process_exists(pid)
+}
+
+#[cfg(unix)]
+fn process_exists(pid: u64) -> bool {
+    // 0 would mean our own process group
+    let Some(pid) = i32::try_from(pid).ok().filter(|pid| *pid > 0) else {
+        return false;
+    };
+    // Signal 0 only checks. EPERM is a process that's there but isn't
+    // ours to signal.
+    let signalled = unsafe { libc::kill(pid, 0) == 0 };
+    signalled || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(windows)]
+fn process_exists(pid: u64) -> bool {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> isize;
+        fn GetExitCodeProcess(process: isize, code: *mut u32) -> i32;
+        fn CloseHandle(handle: isize) -> i32;
+    }
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+    // What GetExitCodeProcess() says while the process hasn't exited
+    const STILL_ACTIVE: u32 = 259;
+
+    let Ok(pid) = u32::try_from(pid) else {
+        return false;
+    };
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process == 0 {
+            return false;
+        }
+        let mut code = 0;
+        let running = GetExitCodeProcess(process, &mut code) != 0 && code == STILL_ACTIVE;
+        CloseHandle(process);
+        running
+    }
+}
+
+// Nothing to ask on other platforms: assume it's gone
+#[cfg(not(any(unix, windows)))]
+fn process_exists(_pid: u64) -> bool {
+    false
+}
+
+// Prompts left unanswered by instances that are no longer running. They
+// are removed from the file as they're returned, so each is offered once.
+pub fn unanswered() -> Vec<SessionRecord> {
+    let mut left = Vec::new();
+    storage::update(FILE, |value| {
+        let (running, gone): (Vec<_>, Vec<_>) = take(value)
+            .into_iter()
+            .partition(|record| record["pid"].as_u64().is_some_and(still_running));
+        left = gone;
+        *value = Value::Array(running);
+    });
+    left.iter().filter_map(SessionRecord::from_json).collect()
+}
+
+// Ask whether to show the unanswered prompts again, then show each one
+// `rebuild` can make a dialog for, one after the other. on_done gets
+// their results (none if the user declined). A dialog that can't be
+// shown again is skipped.
+pub fn offer_restore(
+    records: Vec<SessionRecord>,
+    cx: &mut App,
+    rebuild: impl Fn(&SessionRecord) -> Option<Dialog> + 'static,
+    on_done: impl FnOnce(Vec<DialogResult>, &mut App) + 'static,
+) -> Result<(), DialogError> {
+    let message = match records.len() {
+        1 => {
+            "A dialog wasn't answered before the app quit unexpectedly. Show it again?".to_string()
+        }
+        count => format!(
+            "{count} dialogs weren't answered before the app quit unexpectedly. \
+             Show them again?"
+        ),
+    };
+    Dialog::question(message)
+        .title("Restore Dialogs")
+        .buttons([
+            DialogButton::cancel("Discard"),
+            DialogButton::primary("Restore"),
+        ])
+        .show(cx, move |result, cx| {
+            if result.is_confirmed() {
+                // The window of this dialog closes right after we return
+                cx.defer(move |cx| restore_next(records, Vec::new(), rebuild, on_done, cx));
+            } else {
+                on_done(Vec::new(), cx);
+            }
+        })?;
+    Ok(())
+}
+
+fn restore_next(
+    mut records: Vec<SessionRecord>,
+    results: Vec<DialogResult>,
+    rebuild: impl Fn(&SessionRecord) -> Option<Dialog> + 'static,
+    on_done: impl FnOnce(Vec<DialogResult>, &mut App) + 'static,
+    cx: &mut App,
+) {
+    while !records.is_empty() {
+        let record = records.remove(0);
+        let Some(dialog) = rebuild(&record) else {
+            continue; // A template this version doesn't know
+        };
+        // Shared with the callback, so it's still ours if the dialog
+        // doesn't open
+        let state = Rc::new(RefCell::new(Some((records, results, rebuild, on_done))));
+        let owner = state.clone();
+        let shown =
+            dialog
+                .restorable(record.template, record.params)
+                .show(cx, move |result, cx| {
+                    let Some((records, mut results, rebuild, on_done)) = owner.borrow_mut().take()
+                    else {
+                        return;
+                    };
+                    results.push(result);
+                    cx.defer(move |cx| restore_next(records, results, rebuild, on_done, cx));
+                });
+        if let Err(err) = shown {
+            log::error!("couldn't restore a dialog: {err}");
+            let taken = state.borrow_mut().take();
+            if let Some((records, results, rebuild, on_done)) = taken {
+                restore_next(records, results, rebuild, on_done, cx);
+            }
+        }
+        return;
+    }
+    on_done(results, cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::process_exists;
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_running_processes_only() {
+        assert!(process_exists(u64::from(std::process::id())));
+        // Init, which isn't ours to signal
+        assert!(process_exists(1));
+        assert!(!process_exists(0));
+        assert!(!process_exists(u64::from(u32::MAX)));
+    }
+}
@@ -0,0 +1,210 @@
+// ======================================================================
+// SETTINGS
+// ======================================================================
+// Preferences shared by every dialog and component: the theme, the text
+// size, dialogs the user asked not to see again (see
+// Dialog::suppressible()), touch mode and the order of dialog buttons.
+// They live in one global, loaded by init() and written back on every
+// change:
+//
+//     if settings::get(cx).is_suppressed("confirm-delete") { ... }
+//
+//     settings::update(|settings| settings.theme = ThemeChoice::Dark, cx);
+//
+// Anything that depends on them can follow along:
+//
+//     cx.observe_global::<Settings>(|cx| { ... }).detach();
+//
+// Stored in settings.json next to the other remembered state (see
+// storage.rs). Keys the file doesn't have keep their defaults.
+
+use std::collections::BTreeSet;
+
+use gpui::{px, App, Global, Pixels};
+use serde_json::json;
+
+use crate::layout::DEFAULT_ROOT_FONT_SIZE;
+use crate::storage;
+
+const FILE: &str = "settings.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeChoice {
+    // Follow the system's light or dark appearance
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemeChoice {
+    fn name(self) -> &'static str {
+        match self {
+            ThemeChoice::System => "system",
+            ThemeChoice::Light => "light",
+            ThemeChoice::Dark => "dark",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "system" => Some(ThemeChoice::System),
+            "light" => Some(ThemeChoice::Light),
+            "dark" => Some(ThemeChoice::Dark),
+            _ => None,
+        }
+    }
+}
+
+// Which end of a dialog's button row the affirmative (primary) button
+// goes to. Apps list buttons affirmative last, and the row is rearranged
+// to match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ButtonOrder {
+    // Whatever this platform's dialogs do
+    #[default]
+    PlatformDefault,
+    // Cancel, then OK, as on macOS and GNOME
+    AffirmativeLast,
+    // OK, then Cancel, as on Windows
+    AffirmativeFirst,
+}
+
+impl ButtonOrder {
+    // PlatformDefault spelled out for this platform
+    pub fn resolve(self) -> Self {
+        match self {
+            ButtonOrder::PlatformDefault if cfg!(target_os = "windows") => {
+                ButtonOrder::AffirmativeFirst
+            }
+            ButtonOrder::PlatformDefault => ButtonOrder::AffirmativeLast,
+            order => order,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ButtonOrder::PlatformDefault => "platform",
+            ButtonOrder::AffirmativeLast => "affirmative-last",
+            ButtonOrder::AffirmativeFirst => "affirmative-first",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "platform" => Some(ButtonOrder::PlatformDefault),
+            "affirmative-last" => Some(ButtonOrder::AffirmativeLast),
+            "affirmative-first" => Some(ButtonOrder::AffirmativeFirst),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub theme: ThemeChoice,
+    // What rem(1.0) comes to (see layout.rs)
+    pub root_font_size: Pixels,
+    // Keys of dialogs the user said not to show again
+    pub suppressed: BTreeSet<String>,
+    // Bigger hit targets for fingers (see touch.rs). None follows
+    // whether there's a touch screen.
+    pub touch_mode: Option<bool>,
+    pub button_order: ButtonOrder,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        DEFAULT.clone()
+    }
+}
+
+// Until init() has loaded the real ones
+static DEFAULT: Settings = Settings {
+    theme: ThemeChoice::System,
+    root_font_size: DEFAULT_ROOT_FONT_SIZE,
+    suppressed: BTreeSet::new(),
+    touch_mode: None,
+    button_order: ButtonOrder::PlatformDefault,
+};
+
+impl Global for Settings {}
+
+impl Settings {
+    pub fn is_suppressed(&self, key: &str) -> bool {
+        self.suppressed.contains(key)
+    }
+
+    // Read settings.json, falling back to the defaults for anything
+    // missing or malformed
+    pub fn load() -> Self {
+        let stored = storage::load(FILE).unwrap_or_default();
+        let mut settings = Settings::default();
+        if let Some(theme) = stored["theme"].as_str().and_then(ThemeChoice::from_name) {
+            settings.theme = theme;
+        }
+        if let Some(size) = stored["root_font_size"].as_f64() {
+            settings.root_font_size = px((size as f32).clamp(8.0, 32.0));
+        }
+        if let Some(suppressed) = stored["suppressed"].as_array() {
+            settings.suppressed = suppressed
+                .iter()
+                .filter_map(|key| key.as_str().map(str::to_string))
+                .collect();
+        }
+        if let Some(touch_mode) = stored["touch_mode"].as_bool() {
+            settings.touch_mode = Some(touch_mode);
+        }
+        if let Some(order) = stored["button_order"]
+            .as_str()
+            .and_then(ButtonOrder::from_name)
+        {
+            settings.button_order = order;
+        }
+        settings
+    }
+
+    pub fn save(&self) {
+        storage::save(
+            FILE,
+            &json!({
+                "theme": self.theme.name(),
+                "root_font_size": f32::from(self.root_font_size),
+                "suppressed": self.suppressed,
+                "touch_mode": self.touch_mode,
+                "button_order": self.button_order.name(),
+            }),
+        );
+    }
+}
+
+pub fn get(cx: &App) -> &Settings {
+    cx.try_global::<Settings>().unwrap_or(&DEFAULT)
+}
+
+// Change the settings, save them, and let observers and open windows know
+pub fn update(change: impl FnOnce(&mut Settings), cx: &mut App) {
+    let mut settings = get(cx).clone();
+    change(&mut settings);
+    if settings == *get(cx) {
+        return;
+    }
+    settings.save();
+    cx.set_global(settings);
+    cx.refresh_windows();
+}
+
+// Shorthands for the common changes
+pub fn suppress(key: impl Into<String>, cx: &mut App) {
+    let key = key.into();
+    update(
+        |settings| {
+            settings.suppressed.insert(key);
+        },
+        cx,
+    );
+}
+
+pub(crate) fn load(cx: &mut App) {
+    cx.set_global(Settings::load());
+}
@@ -0,0 +1,138 @@
+// ======================================================================
+// STORAGE
+// ======================================================================
+// Small bits of state remembered between runs (recently used emoji, ...),
+// kept as JSON files in the platform's data directory:
+//
+//     Linux:   ~/.local/share/gpui-dialogs/
+//     macOS:   ~/Library/Application Support/gpui-dialogs/
+//     Windows: %APPDATA%\gpui-dialogs\
+//
+// This is a convenience, not a database: a missing or unreadable file
+// reads as nothing, and a failed write is silently dropped. Writes go to
+// a temporary file first, so a reader never sees half of one.
+//
+// Files that several instances change at once (session.json) go through
+// update(), which holds a lock file while it reads and writes.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use serde_json::Value;
+
+// How long update() waits for another instance to finish with a file
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+// A lock older than this was left by an instance that died holding it
+const LOCK_STALE: Duration = Duration::from_secs(10);
+
+fn path(name: &str) -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("gpui-dialogs").join(name))
+}
+
+pub(crate) fn load(name: &str) -> Option<Value> {
+    serde_json::from_str(&load_text(name)?).ok()
+}
+
+// The file as it is, for the ones that aren't JSON
+pub(crate) fn load_text(name: &str) -> Option<String> {
+    fs::read_to_string(path(name)?).ok()
+}
+
+// When the file was last written, to notice changes
+pub(crate) fn modified(name: &str) -> Option<SystemTime> {
+    fs::metadata(path(name)?).ok()?.modified().ok()
+}
+
+pub(crate) fn save(name: &str, value: &Value) {
+    let Some(path) = path(name) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    if let Ok(text) = serde_json::to_string_pretty(value) {
+        write_atomically(&path, &text).ok();
+    }
+}
+
+// Change a file in place, with `change` getting what's in it (null when
+// there's nothing) to modify. Nothing is written if the lock can't be
+// had.
+pub(crate) fn update(name: &str, change: impl FnOnce(&mut Value)) {
+    let Some(path) = path(name) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    let Some(_lock) = Lock::acquire(&path) else {
+        log::warn!("{name} is locked by another instance; not updating it");
+        return;
+    };
+    let mut value = fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or(Value::Null);
+    change(&mut value);
+    if let Ok(text) = serde_json::to_string_pretty(&value) {
+        write_atomically(&path, &text).ok();
+    }
+}
+
+// Write to a temporary file next to `path` and rename it into place
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    // Per process, so two instances don't write the same one
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        fs::remove_file(&tmp_path).ok();
+    })
+}
+
+// A "<file>.lock" next to the file, there while it's held
+struct Lock(PathBuf);
+
+impl Lock {
+    fn acquire(path: &Path) -> Option<Self> {
+        let mut name = path.file_name()?.to_os_string();
+        name.push(".lock");
+        let lock = path.with_file_name(name);
+
+        let started = SystemTime::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&lock) {
+                Ok(_) => return Some(Self(lock)),
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => {}
+                Err(_) => return None,
+            }
+            let age = fs::metadata(&lock)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            if age.is_some_and(|age| age > LOCK_STALE) {
+                fs::remove_file(&lock).ok();
+                continue;
+            }
+            if started.elapsed().unwrap_or_default() > LOCK_WAIT {
+                return None;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        fs::remove_file(&self.0).ok();
+    }
+}
@@ -0,0 +1,184 @@
+// ======================================================================
+// STRESS TEST
+// ======================================================================
+// `gpui --stress <N>` opens and answers N dialogs one after another to
+// check that nothing piles up under load. They go through the dialog
+// manager like a daemon's requests would, each one answers itself by
+// timing out shortly after it's drawn, and the next opens once it's gone.
+// At the end it reports:
+//
+//     stress: 200 dialogs
+//     open latency: 4.1 ms average, 12.7 ms at most
+//     heap: +18.2 KB since the first dialog (91 bytes per dialog)
+//     leaks: none
+//
+// and exits with 1 if any dialog, backdrop or pill was left behind.
+// Opening is timed from the request to its windows being created; the
+// heap is measured from after the first dialog, once fonts and other
+// caches have warmed up, and only in a build with the count-allocations
+// feature:
+//
+//     cargo run --features count-allocations -- --stress 200
+//
+// With --stress-random the dialogs vary: light or dark, different sizes,
+// and messages from a few words to a few paragraphs.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+use gpui::*;
+use gpui_dialogs::manager::{self, Limits};
+use gpui_dialogs::{testing, Dialog, ThemeChoice};
+
+// How long each dialog stays up before it times out
+const HOLD: Duration = Duration::from_millis(50);
+// For the last dialog's windows to be removed before counting leaks
+const SETTLE: Duration = Duration::from_millis(200);
+
+const WORDS: [&str; 12] = [
+    "the", "file", "could", "not", "be", "saved", "because", "disk", "is", "full", "try", "again",
+];
+
+struct Stress {
+    count: usize,
+    // Only with --stress-random
+    rng: Option<Rng>,
+    latencies: Vec<Duration>,
+    // The heap after the first dialog
+    baseline: Option<usize>,
+}
+
+impl Stress {
+    fn dialog(&mut self, index: usize) -> Dialog {
+        // Numbered, so the manager doesn't coalesce them
+        let mut message = format!("Dialog {} of {}.", index + 1, self.count);
+        let Some(rng) = &mut self.rng else {
+            return Dialog::question(message).timeout(HOLD);
+        };
+        for _ in 0..rng.below(300) {
+            message.push(' ');
+            message.push_str(WORDS[rng.below(WORDS.len() as u64) as usize]);
+        }
+        let theme = if rng.below(2) == 0 {
+            ThemeChoice::Light
+        } else {
+            ThemeChoice::Dark
+        };
+        let width = px(320.0 + rng.below(320) as f32);
+        let height = px(160.0 + rng.below(320) as f32);
+        Dialog::question(message)
+            .theme(theme)
+            .size(size(width, height))
+            .timeout(HOLD)
+    }
+}
+
+pub fn run(count: usize, random: bool, cx: &mut App) {
+    // The manager would refuse this many as a flood
+    manager::set_limits(
+        Limits {
+            max_dialogs: usize::MAX,
+            ..manager::limits(cx)
+        },
+        cx,
+    );
+    let stress = Rc::new(RefCell::new(Stress {
+        count,
+        rng: random.then(Rng::new),
+        latencies: Vec::with_capacity(count),
+        baseline: None,
+    }));
+    next(stress, 0, cx);
+}
+
+fn next(stress: Rc<RefCell<Stress>>, index: usize, cx: &mut App) {
+    if index == stress.borrow().count {
+        cx.spawn(async move |cx| {
+            cx.background_executor().timer(SETTLE).await;
+            cx.update(|cx| finish(&stress.borrow(), cx)).ok();
+        })
+        .detach();
+        return;
+    }
+    if index == 1 {
+        stress.borrow_mut().baseline = Some(testing::memory().live_bytes);
+    }
+
+    let dialog = stress.borrow_mut().dialog(index);
+    let started = Instant::now();
+    let answered = stress.clone();
+    let requested = manager::request(dialog, cx, move |_, cx| {
+        // Once this one's windows are gone
+        cx.defer(move |cx| next(answered, index + 1, cx));
+    });
+    if let Err(err) = requested {
+        eprintln!("stress: dialog {} failed to open: {err}", index + 1);
+        std::process::exit(err.exit_code());
+    }
+    stress.borrow_mut().latencies.push(started.elapsed());
+}
+
+fn finish(stress: &Stress, cx: &mut App) {
+    println!("stress: {} dialogs", stress.count);
+
+    let total: Duration = stress.latencies.iter().sum();
+    let max = stress.latencies.iter().max().copied().unwrap_or_default();
+    println!(
+        "open latency: {:.1} ms average, {:.1} ms at most",
+        millis(total) / stress.latencies.len().max(1) as f64,
+        millis(max)
+    );
+
+    if testing::memory().allocations == 0 {
+        println!("heap: not counted (build with --features count-allocations)");
+        return report_leaks(cx);
+    }
+    // Zero when there was only one dialog to go by
+    let growth = stress
+        .baseline
+        .map(|baseline| testing::memory().live_bytes as i64 - baseline as i64)
+        .unwrap_or(0);
+    let per_dialog = growth / stress.count.saturating_sub(1).max(1) as i64;
+    println!(
+        "heap: {:+.1} KB since the first dialog ({per_dialog} bytes per dialog)",
+        growth as f64 / 1024.0
+    );
+    report_leaks(cx);
+}
+
+fn report_leaks(cx: &mut App) {
+    let leaks = testing::entity_leaks(cx);
+    if leaks.is_empty() {
+        println!("leaks: none");
+        cx.quit();
+    } else {
+        println!("leaks: {}", leaks.join(", "));
+        std::process::exit(1);
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// xorshift64: enough to vary the dialogs, seeded from the clock
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        Self(u64::from(nanos) | 1)
+    }
+
+    // A number in 0..bound
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
@@ -0,0 +1,225 @@
+// ======================================================================
+// TESTING
+// ======================================================================
+// Helpers for the tests of apps that show dialogs.
+//
+// Once a dialog resolves, everything it opened should be gone: its
+// window, its backdrop window, the pill it may have folded into, and the
+// entities behind them. Holding on to one (an Entity<Backdrop> kept in a
+// struct, say) is an easy mistake that leaves a dimmed screen or a dead
+// window around, so a test can check for it:
+//
+//     // ... show a dialog and answer it ...
+//     cx.run_until_parked();
+//     cx.update(testing::assert_no_entity_leaks);
+//
+// For memory, CountingAllocator installed as a test binary's allocator
+// counts the live heap, and memory() reads it; showing and answering the
+// same dialog many times over shouldn't make it grow:
+//
+//     #[global_allocator]
+//     static ALLOCATOR: testing::CountingAllocator = testing::CountingAllocator;
+//
+//     let before = testing::memory().live_bytes;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use gpui::App;
+
+use crate::backdrop::Backdrop;
+use crate::dialog::DialogBox;
+use crate::pill::ProgressPill;
+
+// ======================================================================
+// LIVE ENTITIES
+// ======================================================================
+
+#[derive(Clone, Copy)]
+pub(crate) enum Kind {
+    Dialog,
+    Backdrop,
+    Pill,
+}
+
+impl Kind {
+    const ALL: [Kind; 3] = [Kind::Dialog, Kind::Backdrop, Kind::Pill];
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Dialog => "dialog",
+            Kind::Backdrop => "backdrop",
+            Kind::Pill => "progress pill",
+        }
+    }
+}
+
+thread_local! {
+    // How many of each kind are alive. Per thread, as each test runs its
+    // app on its own thread.
+    static LIVE: Cell<[usize; 3]> = const { Cell::new([0; 3]) };
+}
+
+fn count(kind: Kind, change: impl FnOnce(usize) -> usize) {
+    LIVE.with(|live| {
+        let mut counts = live.get();
+        counts[kind as usize] = change(counts[kind as usize]);
+        live.set(counts);
+    });
+}
+
+// Held by each DialogBox, Backdrop and ProgressPill, counting them while
+// they're alive
+pub(crate) struct Live(Kind);
+
+impl Live {
+    pub(crate) fn new(kind: Kind) -> Self {
+        count(kind, |count| count + 1);
+        Self(kind)
+    }
+}
+
+impl Drop for Live {
+    fn drop(&mut self) {
+        count(self.0, |count| count.saturating_sub(1));
+    }
+}
+
+// Panic, saying what's left, if any dialog, backdrop or pill window is
+// still open or any of their entities is still alive
+pub fn assert_no_entity_leaks(cx: &mut App) {
+    let leaks = entity_leaks(cx);
+    assert!(leaks.is_empty(), "leaked: {}", leaks.join(", "));
+}
+
+// What assert_no_entity_leaks() would complain about, one line per kind
+// of leak
+pub fn entity_leaks(cx: &App) -> Vec<String> {
+    let mut leaks = Vec::new();
+
+    let windows = cx.windows();
+    let open = |kind: Kind| {
+        windows
+            .iter()
+            .filter(|window| match kind {
+                Kind::Dialog => window.downcast::<DialogBox>().is_some(),
+                Kind::Backdrop => window.downcast::<Backdrop>().is_some(),
+                Kind::Pill => window.downcast::<ProgressPill>().is_some(),
+            })
+            .count()
+    };
+    let live = LIVE.with(Cell::get);
+    for kind in Kind::ALL {
+        let windows = open(kind);
+        if windows > 0 {
+            leaks.push(format!("{windows} {} window(s) still open", kind.name()));
+        }
+        // Alive without a window: something holds on to the entity
+        let orphans = live[kind as usize].saturating_sub(windows);
+        if orphans > 0 {
+            leaks.push(format!(
+                "{orphans} {} entity(ies) alive without a window",
+                kind.name()
+            ));
+        }
+    }
+    leaks
+}
+
+// ======================================================================
+// MEMORY
+// ======================================================================
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// The system allocator, counting as it goes. Counts are for the whole
+// process, whichever thread allocated.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            LIVE_BYTES.fetch_add(new_size, Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        new
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    // Heap bytes allocated and not yet freed
+    pub live_bytes: usize,
+    // Allocations made so far, freed or not
+    pub allocations: usize,
+}
+
+// What CountingAllocator has counted; all zeros when it isn't the
+// global allocator
+pub fn memory() -> Memory {
+    Memory {
+        live_bytes: LIVE_BYTES.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+
+    use super::{assert_no_entity_leaks, entity_leaks};
+    use crate::result::Outcome;
+    use crate::Dialog;
+
+    #[gpui::test]
+    fn answered_dialogs_leave_nothing_behind(cx: &mut TestAppContext) {
+        let handle = cx
+            .update(|cx| Dialog::question("Save changes?").show(cx, |_, _| {}))
+            .unwrap();
+        cx.run_until_parked();
+        assert!(!cx.update(|cx| entity_leaks(cx)).is_empty());
+
+        cx.update(|cx| handle.close(Outcome::Dismissed, cx));
+        cx.run_until_parked();
+        cx.update(assert_no_entity_leaks);
+    }
+
+    #[gpui::test]
+    fn finds_a_dialog_held_after_its_window_closed(cx: &mut TestAppContext) {
+        let handle = cx
+            .update(|cx| Dialog::question("Save changes?").show(cx, |_, _| {}))
+            .unwrap();
+        cx.run_until_parked();
+        let held = cx.update(|cx| handle.entity(cx));
+        assert!(held.is_some());
+
+        cx.update(|cx| handle.close(Outcome::Dismissed, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            cx.update(|cx| entity_leaks(cx)),
+            ["1 dialog entity(ies) alive without a window"]
+        );
+        // Entities are released when the app next flushes its effects
+        drop(held);
+        cx.update(|_| {});
+        cx.update(assert_no_entity_leaks);
+    }
+}
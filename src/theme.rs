@@ -0,0 +1,496 @@
+// ======================================================================
+// THEME
+// ======================================================================
+// The colors of a dialog (its titlebar, background, text and borders,
+// and the buttons, inputs, lists and key caps inside it) in light and
+// dark. Which one a dialog gets follows the theme setting, "system"
+// going by the window's appearance, unless Dialog::theme() picks one.
+// Components ask for whatever their window uses:
+//
+//     let palette = theme::window_palette(window, cx);
+//     div().bg(palette.well).text_color(palette.well_text)
+//
+// A theme.json next to the other stored state (see storage.rs) overrides
+// any of the colors, as hex, per appearance:
+//
+//     {
+//         "dark": { "background": "#1E1E2E", "text": "#CDD6F4" },
+//         "light": { "titlebar": "#DCE0E8" }
+//     }
+//
+// It is read by init(). While authoring one, run `gpui --theme-preview`:
+// the dialog opens twice side by side, light and dark, and both redraw
+// whenever the file is saved.
+//
+// Text has to stay readable: every text color is held to a WCAG contrast
+// ratio of at least MIN_CONTRAST against what it's drawn on. Colors from
+// theme.json that fall short are made darker or lighter until they pass,
+// with a warning in the log. `gpui --check-theme <PATH>` checks a theme
+// file without changing anything, for CI.
+//
+// Accent colors (the default button, focus rings, selected rows) are
+// the same in both.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use gpui::*;
+use serde_json::Value;
+
+use crate::components::color_swatches::parse_hex;
+use crate::dialog::{Dialog, DialogHandle, ResolveCallback};
+use crate::error::DialogError;
+use crate::result::DialogResult;
+use crate::settings::{self, ThemeChoice};
+use crate::storage;
+
+const FILE: &str = "theme.json";
+// How often the preview checks the file for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+// Between the two dialogs of the preview
+const PREVIEW_GAP: Pixels = px(16.0);
+
+// The WCAG AA minimum for body text
+pub const MIN_CONTRAST: f32 = 4.5;
+
+// The colors a palette has, by their names in theme.json
+const COLORS: [&str; 9] = [
+    "background",
+    "titlebar",
+    "titlebar_border",
+    "text",
+    "secondary_text",
+    "muted_text",
+    "border",
+    "well",
+    "well_text",
+];
+
+// Text colors and the backgrounds they're drawn on
+const TEXT_ON: [(&str, &str); 5] = [
+    ("text", "background"),
+    ("secondary_text", "background"),
+    ("muted_text", "background"),
+    ("secondary_text", "titlebar"), // The title
+    ("well_text", "well"),
+];
+
+// A text color too close to its background
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContrastProblem {
+    // "light" or "dark"
+    pub appearance: &'static str,
+    pub text: &'static str,
+    pub background: &'static str,
+    pub ratio: f32,
+}
+
+impl fmt::Display for ContrastProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} on {} has a contrast of {:.2}:1, below {MIN_CONTRAST}:1",
+            self.appearance, self.text, self.background, self.ratio
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub background: Rgba,
+    pub titlebar: Rgba,
+    // The line under the titlebar
+    pub titlebar_border: Rgba,
+    pub text: Rgba,
+    // Titles, field labels and status lines
+    pub secondary_text: Rgba,
+    // Hints under the message
+    pub muted_text: Rgba,
+    pub border: Rgba,
+    // Sunken areas such as the log, and their text
+    pub well: Rgba,
+    pub well_text: Rgba,
+}
+
+impl Palette {
+    pub fn light() -> Self {
+        Self {
+            background: rgb(0xEFEFEF),
+            titlebar: rgb(0xE8E8E8),
+            titlebar_border: rgb(0xD0D0D0),
+            text: rgb(0x000000),
+            secondary_text: rgb(0x4D4D4D),
+            muted_text: rgb(0x6C6C6C),
+            border: rgb(0xB8B8B8),
+            well: rgb(0xFFFFFF),
+            well_text: rgb(0x1E1E1E),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            background: rgb(0x2B2B2B),
+            titlebar: rgb(0x383838),
+            titlebar_border: rgb(0x1F1F1F),
+            text: rgb(0xEEEEEE),
+            secondary_text: rgb(0xB4B4B4),
+            muted_text: rgb(0x949494),
+            border: rgb(0x4A4A4A),
+            well: rgb(0x1E1E1E),
+            well_text: rgb(0xDDDDDD),
+        }
+    }
+
+    // Behind whatever the pointer is over in a well
+    pub fn hover(&self) -> Rgba {
+        self.well.blend(Rgba {
+            a: 0.05,
+            ..self.well_text
+        })
+    }
+
+    fn color_mut(&mut self, name: &str) -> &mut Rgba {
+        match name {
+            "background" => &mut self.background,
+            "titlebar" => &mut self.titlebar,
+            "titlebar_border" => &mut self.titlebar_border,
+            "text" => &mut self.text,
+            "secondary_text" => &mut self.secondary_text,
+            "muted_text" => &mut self.muted_text,
+            "border" => &mut self.border,
+            "well" => &mut self.well,
+            "well_text" => &mut self.well_text,
+            _ => unreachable!("no color named {name}"),
+        }
+    }
+
+    fn color(mut self, name: &str) -> Rgba {
+        *self.color_mut(name)
+    }
+
+    // Take whichever colors `overrides` gives. Returns what it couldn't
+    // make sense of.
+    fn apply(&mut self, overrides: &Value) -> Vec<String> {
+        let mut invalid = Vec::new();
+        let Some(overrides) = overrides.as_object() else {
+            return invalid;
+        };
+        for (name, value) in overrides {
+            let Some(name) = COLORS.iter().find(|color| *color == name) else {
+                invalid.push(format!("unknown color \"{name}\""));
+                continue;
+            };
+            match value.as_str().and_then(parse_hex) {
+                Some(color) => *self.color_mut(name) = color,
+                None => invalid.push(format!("{name} isn't a hex color: {value}")),
+            }
+        }
+        invalid
+    }
+
+    // The text colors that don't stand out enough from their background
+    pub fn contrast_problems(&self, appearance: &'static str) -> Vec<ContrastProblem> {
+        TEXT_ON
+            .iter()
+            .map(|&(text, background)| ContrastProblem {
+                appearance,
+                text,
+                background,
+                ratio: contrast_ratio(self.color(text), self.color(background)),
+            })
+            .filter(|problem| problem.ratio < MIN_CONTRAST)
+            .collect()
+    }
+
+    // Move each text color that falls short away from its background's
+    // lightness until it reaches MIN_CONTRAST (or black or white)
+    fn fix_contrast(&mut self) {
+        for (text, background) in TEXT_ON {
+            let background = self.color(background);
+            // Past this luminance, black text contrasts more than white
+            let darken = luminance(background) > 0.18;
+            let mut color = Hsla::from(self.color(text));
+            while contrast_ratio(color.into(), background) < MIN_CONTRAST {
+                let lightness = if darken {
+                    color.l - 0.01
+                } else {
+                    color.l + 0.01
+                };
+                if !(0.0..=1.0).contains(&lightness) {
+                    break;
+                }
+                color.l = lightness;
+            }
+            *self.color_mut(text) = color.into();
+        }
+    }
+}
+
+// The WCAG contrast ratio between two colors, from 1:1 up to 21:1
+pub fn contrast_ratio(a: Rgba, b: Rgba) -> f32 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+// WCAG relative luminance
+fn luminance(color: Rgba) -> f32 {
+    let linear = |channel: f32| {
+        if channel <= 0.03928 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Theme {
+    light: Palette,
+    dark: Palette,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            light: Palette::light(),
+            dark: Palette::dark(),
+        }
+    }
+}
+
+impl Global for Theme {}
+
+// The colors for `choice` in `window`
+pub fn palette(choice: ThemeChoice, window: &Window, cx: &App) -> Palette {
+    let theme = cx.try_global::<Theme>().copied().unwrap_or_default();
+    let dark = match choice {
+        ThemeChoice::Light => false,
+        ThemeChoice::Dark => true,
+        ThemeChoice::System => matches!(
+            window.appearance(),
+            WindowAppearance::Dark | WindowAppearance::VibrantDark
+        ),
+    };
+    if dark {
+        theme.dark
+    } else {
+        theme.light
+    }
+}
+
+// Windows shown with a theme of their own, such as dialogs given one
+// with Dialog::theme()
+#[derive(Default)]
+struct WindowThemes(HashMap<WindowId, ThemeChoice>);
+
+impl Global for WindowThemes {}
+
+// Draw everything in `window` with `choice`, whatever the theme setting
+// says
+pub fn set_window_theme(window: &Window, choice: ThemeChoice, cx: &mut App) {
+    let open: Vec<WindowId> = cx.windows().iter().map(|w| w.window_id()).collect();
+    let themes = &mut cx.default_global::<WindowThemes>().0;
+    // Forget the windows that have closed since
+    themes.retain(|id, _| open.contains(id));
+    themes.insert(window.window_handle().window_id(), choice);
+}
+
+// The colors for whatever is drawn in `window`
+pub fn window_palette(window: &Window, cx: &App) -> Palette {
+    let id = window.window_handle().window_id();
+    let choice = cx
+        .try_global::<WindowThemes>()
+        .and_then(|themes| themes.0.get(&id).copied())
+        .unwrap_or(settings::get(cx).theme);
+    palette(choice, window, cx)
+}
+
+impl Theme {
+    fn from_json(json: &Value) -> (Self, Vec<String>) {
+        let mut theme = Theme::default();
+        let mut invalid = theme.light.apply(&json["light"]);
+        invalid.extend(theme.dark.apply(&json["dark"]));
+        (theme, invalid)
+    }
+
+    fn contrast_problems(&self) -> Vec<ContrastProblem> {
+        let mut problems = self.light.contrast_problems("light");
+        problems.extend(self.dark.contrast_problems("dark"));
+        problems
+    }
+}
+
+// Read theme.json, if there is one
+pub(crate) fn load(cx: &mut App) {
+    let mut theme = Theme::default();
+    if let Some(stored) = storage::load(FILE) {
+        let invalid;
+        (theme, invalid) = Theme::from_json(&stored);
+        for problem in invalid {
+            log::warn!("{FILE}: {problem}");
+        }
+        for problem in theme.contrast_problems() {
+            log::warn!("{FILE}: {problem}; adjusting it");
+        }
+        theme.light.fix_contrast();
+        theme.dark.fix_contrast();
+    }
+    cx.set_global(theme);
+    cx.refresh_windows();
+}
+
+// Everything wrong with the theme file at `path`: colors that aren't
+// colors, and text that would be hard to read. Empty when it's fine.
+pub fn check(path: &Path) -> Result<Vec<String>, String> {
+    let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    check_text(&text)
+}
+
+// The same, for the file's contents. An Err is JSON that doesn't parse.
+pub fn check_text(text: &str) -> Result<Vec<String>, String> {
+    let json: Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    let (theme, mut problems) = Theme::from_json(&json);
+    problems.extend(
+        theme
+            .contrast_problems()
+            .iter()
+            .map(ContrastProblem::to_string),
+    );
+    Ok(problems)
+}
+
+// Reload theme.json whenever it changes
+fn watch(cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let mut seen: Option<SystemTime> = storage::modified(FILE);
+        loop {
+            cx.background_executor().timer(WATCH_INTERVAL).await;
+            let modified = storage::modified(FILE);
+            if modified == seen {
+                continue;
+            }
+            seen = modified;
+            if cx.update(load).is_err() {
+                return; // The app is shutting down
+            }
+        }
+    })
+    .detach();
+}
+
+// Open the dialog `build` makes twice, light on the left and dark on the
+// right, redrawing both as theme.json changes. Answering either closes
+// the other; on_resolve gets the answer.
+pub fn preview(
+    build: impl Fn() -> Dialog,
+    cx: &mut App,
+    on_resolve: impl FnOnce(DialogResult, &mut App) + 'static,
+) -> Result<(), DialogError> {
+    let on_resolve: Rc<RefCell<Option<ResolveCallback>>> =
+        Rc::new(RefCell::new(Some(Box::new(on_resolve))));
+    let shown: Rc<RefCell<Vec<DialogHandle>>> = Rc::default();
+    let answered = {
+        let shown = shown.clone();
+        move |result: DialogResult, cx: &mut App| {
+            // Closing the other one lands here again, with nothing left
+            // to do
+            let Some(on_resolve) = on_resolve.borrow_mut().take() else {
+                return;
+            };
+            let others = shown.borrow().clone();
+            for other in others {
+                other.close(result.outcome.clone(), cx);
+            }
+            on_resolve(result, cx);
+        }
+    };
+
+    let light = build().theme(ThemeChoice::Light);
+    let shift = (light.window_size(cx).width + PREVIEW_GAP) / 2.0;
+    for (dialog, offset) in [(light, -shift), (build().theme(ThemeChoice::Dark), shift)] {
+        // No backdrop: one would dim the other dialog
+        let window = dialog
+            .offset(point(offset, px(0.0)))
+            .open(None, cx, answered.clone())?;
+        shown.borrow_mut().push(DialogHandle::new(window));
+    }
+    watch(cx);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{rgb, EmptyView, TestAppContext};
+
+    use super::{contrast_ratio, set_window_theme, window_palette, Palette, MIN_CONTRAST};
+    use crate::settings::ThemeChoice;
+
+    #[test]
+    fn black_on_white_is_21_to_1() {
+        let ratio = contrast_ratio(rgb(0x000000), rgb(0xFFFFFF));
+        assert!((ratio - 21.0).abs() < 0.01, "{ratio}");
+        // Either way round
+        assert_eq!(ratio, contrast_ratio(rgb(0xFFFFFF), rgb(0x000000)));
+        assert_eq!(contrast_ratio(rgb(0x808080), rgb(0x808080)), 1.0);
+    }
+
+    #[test]
+    fn the_built_in_palettes_have_enough_contrast() {
+        assert_eq!(Palette::light().contrast_problems("light"), []);
+        assert_eq!(Palette::dark().contrast_problems("dark"), []);
+    }
+
+    #[test]
+    fn fix_contrast_darkens_on_light_and_lightens_on_dark() {
+        let mut light = Palette {
+            text: rgb(0xCCCCCC),
+            ..Palette::light()
+        };
+        light.fix_contrast();
+        assert_eq!(light.contrast_problems("light"), []);
+        assert!(contrast_ratio(light.text, light.background) >= MIN_CONTRAST);
+        assert!(light.text.r < 0.8);
+
+        let mut dark = Palette {
+            muted_text: rgb(0x333333),
+            ..Palette::dark()
+        };
+        dark.fix_contrast();
+        assert_eq!(dark.contrast_problems("dark"), []);
+        assert!(dark.muted_text.r > 0.2);
+    }
+
+    #[test]
+    fn fix_contrast_leaves_good_colors_alone() {
+        let mut palette = Palette::light();
+        palette.fix_contrast();
+        assert_eq!(palette.text, Palette::light().text);
+        assert_eq!(palette.well_text, Palette::light().well_text);
+    }
+
+    #[gpui::test]
+    fn components_follow_their_windows_theme(cx: &mut TestAppContext) {
+        let dark = cx.add_window(|window, cx| {
+            set_window_theme(window, ThemeChoice::Dark, cx);
+            EmptyView
+        });
+        let plain = cx.add_window(|_, _| EmptyView);
+        dark.update(cx, |_, window, cx| {
+            assert_eq!(window_palette(window, cx), Palette::dark());
+        })
+        .unwrap();
+        // The rest go by the theme setting, "system" by default, and test
+        // windows are light
+        plain
+            .update(cx, |_, window, cx| {
+                assert_eq!(window_palette(window, cx), Palette::light());
+            })
+            .unwrap();
+    }
+}
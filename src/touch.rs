@@ -0,0 +1,147 @@
+// ======================================================================
+// TOUCH AND PEN
+// ======================================================================
+// GPUI hands taps and pen strokes to us as mouse events, so tapping a
+// button or a row already works. What doesn't carry over from a mouse:
+//
+//   - A fingertip is much bigger than a pointer. In touch mode buttons
+//     and list rows grow to at least TOUCH_TARGET tall:
+//
+//         div().h(touch::target(px(28.0), cx))
+//
+//   - There's no scroll wheel. DragScroll scrolls a list by dragging its
+//     content, as on a phone (only in touch mode, where dragging doesn't
+//     mean selecting).
+//
+//   - Pinching. Touch screens and precision touchpads send a pinch as
+//     Ctrl+scroll, which ImagePreview zooms on.
+//
+// Touch mode is on when the settings say so, or when they don't say and
+// init() found a touch screen.
+
+use gpui::{px, App, Global, Pixels, Point, ScrollHandle};
+
+use crate::settings;
+
+// The smallest comfortable height for something to tap
+pub const TOUCH_TARGET: Pixels = px(44.0);
+// How far the pointer moves before a press counts as a drag, not a tap
+pub const DRAG_THRESHOLD: Pixels = px(6.0);
+
+struct TouchScreen(bool);
+
+impl Global for TouchScreen {}
+
+pub fn touch_mode(cx: &App) -> bool {
+    settings::get(cx).touch_mode.unwrap_or_else(|| {
+        cx.try_global::<TouchScreen>()
+            .is_some_and(|screen| screen.0)
+    })
+}
+
+// `size` in touch mode made at least TOUCH_TARGET, `size` otherwise
+pub fn target(size: Pixels, cx: &App) -> Pixels {
+    if touch_mode(cx) {
+        size.max(TOUCH_TARGET)
+    } else {
+        size
+    }
+}
+
+// Look for a touch screen, quietly answering "no" when we can't tell
+pub(crate) fn detect(cx: &mut App) {
+    cx.set_global(TouchScreen(has_touch_screen()));
+}
+
+#[cfg(target_os = "windows")]
+fn has_touch_screen() -> bool {
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetSystemMetrics(index: i32) -> i32;
+    }
+    const SM_MAXIMUMTOUCHES: i32 = 95;
+    unsafe { GetSystemMetrics(SM_MAXIMUMTOUCHES) > 0 }
+}
+
+// The kernel lists input devices by name; touch screens (and pen
+// tablets, which want the same treatment) say so
+#[cfg(target_os = "linux")]
+fn has_touch_screen() -> bool {
+    let Ok(devices) = std::fs::read_to_string("/proc/bus/input/devices") else {
+        return false;
+    };
+    devices
+        .lines()
+        .filter_map(|line| line.strip_prefix("N: Name="))
+        .map(str::to_lowercase)
+        .any(|name| name.contains("touchscreen") || name.contains("stylus"))
+}
+
+// Macs don't have touch screens
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn has_touch_screen() -> bool {
+    false
+}
+
+// ======================================================================
+// DRAG TO SCROLL
+// ======================================================================
+// Feed it the pointer's downs, moves and ups over a scrolling element:
+//
+//     .on_mouse_down(MouseButton::Left, cx.listener(|this, event, _, cx| {
+//         this.drag_scroll.down(event.position, &this.scroll_handle)
+//     }))
+//     .on_mouse_move(cx.listener(|this, event, _, cx| {
+//         if this.drag_scroll.moved(event.position, &this.scroll_handle) {
+//             cx.notify();
+//         }
+//     }))
+//
+// and check dragged() in click handlers, so the row a drag ends on isn't
+// clicked too.
+
+#[derive(Debug, Default)]
+pub struct DragScroll {
+    // Where the press started, and the scroll offset then
+    start: Option<(Point<Pixels>, Point<Pixels>)>,
+    dragging: bool,
+}
+
+impl DragScroll {
+    pub fn down(&mut self, position: Point<Pixels>, handle: &ScrollHandle) {
+        self.start = Some((position, handle.offset()));
+        self.dragging = false;
+    }
+
+    // Scroll along with the pointer once it has moved far enough.
+    // Returns whether the offset changed.
+    pub fn moved(&mut self, position: Point<Pixels>, handle: &ScrollHandle) -> bool {
+        let Some((start, offset)) = self.start else {
+            return false;
+        };
+        let delta = position - start;
+        if !self.dragging {
+            if delta.x.abs() < DRAG_THRESHOLD && delta.y.abs() < DRAG_THRESHOLD {
+                return false;
+            }
+            self.dragging = true;
+        }
+        // Offsets run from 0 down to minus the scrollable length
+        let max = handle.max_offset();
+        handle.set_offset(Point {
+            x: (offset.x + delta.x).clamp(-max.width, px(0.0)),
+            y: (offset.y + delta.y).clamp(-max.height, px(0.0)),
+        });
+        true
+    }
+
+    pub fn up(&mut self) {
+        self.start = None;
+    }
+
+    // Whether the current (or just finished) press scrolled rather than
+    // tapped
+    pub fn dragged(&self) -> bool {
+        self.dragging
+    }
+}
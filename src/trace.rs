@@ -0,0 +1,393 @@
+// ======================================================================
+// TRACING
+// ======================================================================
+// For performance investigations. With tracing on, each dialog window
+// records how long its frames take (layout, prepaint and paint) and how
+// long each key and mouse event takes to dispatch, in the Chrome trace
+// event format. The file opens in Perfetto (ui.perfetto.dev) or
+// chrome://tracing, one track per window.
+//
+// Events are written out as they pile up and every FLUSH_INTERVAL, not
+// kept until the end, so a long session doesn't fill memory and a crash
+// or process::exit() loses only the last moments. The file is a JSON
+// array, which the viewers read even without the closing bracket that
+// only quitting normally adds.
+//
+//     trace::start("out.json", cx);
+//
+// `gpui --trace out.json` does this for the tutorial dialog.
+//
+// Only what's inside a traced element is measured, so a "layout" span is
+// the time spent describing its layout; gpui solves the layout between
+// "layout" and "prepaint", which the enclosing "frame" span takes in. An
+// event whose propagation a handler stops never comes back to be timed,
+// and shows up as an instant marker instead of a span.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use gpui::*;
+use serde_json::{json, Value};
+
+// How often recorded events are written out, and how many are held
+// before that at most
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_PENDING: usize = 4096;
+
+struct Recorder {
+    path: PathBuf,
+    // None once writing to it has failed
+    file: Option<File>,
+    started: Instant,
+    // Recorded but not written yet, and how many have been
+    pending: Vec<Value>,
+    written: usize,
+    // The tracks that have been given a name
+    named: HashSet<u64>,
+    // The event being dispatched, between its capture and bubble phases
+    dispatching: Option<(&'static str, u64, Instant)>,
+}
+
+impl Global for Recorder {}
+
+impl Recorder {
+    // Microseconds since tracing started, the format's time unit
+    fn micros(&self, instant: Instant) -> f64 {
+        instant.duration_since(self.started).as_secs_f64() * 1e6
+    }
+
+    fn push(&mut self, event: Value) {
+        self.pending.push(event);
+        if self.pending.len() >= MAX_PENDING {
+            self.flush();
+        }
+    }
+
+    // Append the pending events to the file
+    fn flush(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let Some(file) = &mut self.file else {
+            return;
+        };
+        let mut out = Vec::new();
+        for event in &pending {
+            if self.written > 0 {
+                out.extend_from_slice(b",\n");
+            }
+            // Serializing a Value can't fail
+            serde_json::to_writer(&mut out, event).ok();
+            self.written += 1;
+        }
+        if let Err(err) = file.write_all(&out) {
+            log::error!("couldn't write the trace to {}: {err}", self.path.display());
+            self.file = None;
+        }
+    }
+
+    fn span(&mut self, category: &str, name: &str, track: u64, from: Instant) {
+        let (ts, now) = (self.micros(from), self.micros(Instant::now()));
+        self.push(json!({
+            "name": name,
+            "cat": category,
+            "ph": "X",
+            "ts": ts,
+            "dur": now - ts,
+            "pid": 1,
+            "tid": track,
+        }));
+    }
+
+    fn instant(&mut self, category: &str, name: &str, track: u64, at: Instant) {
+        self.push(json!({
+            "name": name,
+            "cat": category,
+            "ph": "i",
+            "s": "t",
+            "ts": self.micros(at),
+            "pid": 1,
+            "tid": track,
+        }));
+    }
+
+    // Label a window's track the first time it records something
+    fn name_track(&mut self, track: u64, name: &str) {
+        if self.named.insert(track) {
+            self.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": track,
+                "args": { "name": format!("{name} {track}") },
+            }));
+        }
+    }
+}
+
+// Record into the file at `path` from now until the app quits
+pub fn start(path: impl Into<PathBuf>, cx: &mut App) {
+    let path = path.into();
+    let file = File::create(&path).and_then(|mut file| {
+        file.write_all(b"[\n")?;
+        Ok(file)
+    });
+    let file = match file {
+        Ok(file) => file,
+        Err(err) => {
+            log::error!("couldn't create the trace {}: {err}", path.display());
+            return;
+        }
+    };
+    cx.set_global(Recorder {
+        path,
+        file: Some(file),
+        started: Instant::now(),
+        pending: Vec::new(),
+        written: 0,
+        named: HashSet::new(),
+        dispatching: None,
+    });
+    cx.on_app_quit(|cx| {
+        finish(cx);
+        async {}
+    })
+    .detach();
+    cx.spawn(async move |cx| loop {
+        cx.background_executor().timer(FLUSH_INTERVAL).await;
+        let flushed =
+            cx.update(|cx| cx.update_global::<Recorder, _>(|recorder, _| recorder.flush()));
+        if flushed.is_err() {
+            return; // The app is shutting down
+        }
+    })
+    .detach();
+}
+
+pub fn enabled(cx: &App) -> bool {
+    cx.has_global::<Recorder>()
+}
+
+// Write what's left and close the array
+fn finish(cx: &mut App) {
+    if !enabled(cx) {
+        return;
+    }
+    cx.update_global::<Recorder, _>(|recorder, _| {
+        recorder.flush();
+        if let Some(file) = &mut recorder.file {
+            if let Err(err) = file.write_all(b"\n]\n") {
+                log::error!(
+                    "couldn't write the trace to {}: {err}",
+                    recorder.path.display()
+                );
+            }
+        }
+    });
+}
+
+// ======================================================================
+// TRACED ELEMENT
+// ======================================================================
+// Wraps a window's root element without changing it, timing its phases
+// and the events dispatched through it:
+//
+//     trace::traced("Dialog", div().child(...))
+
+pub fn traced(name: &'static str, child: impl IntoElement) -> Traced {
+    Traced {
+        name,
+        child: child.into_any_element(),
+    }
+}
+
+pub struct Traced {
+    name: &'static str,
+    child: AnyElement,
+}
+
+impl Traced {
+    fn span(&self, phase: &str, from: Instant, window: &Window, cx: &mut App) {
+        if !enabled(cx) {
+            return;
+        }
+        let track = window.window_handle().window_id().as_u64();
+        let name = self.name;
+        cx.update_global::<Recorder, _>(|recorder, _| {
+            recorder.name_track(track, name);
+            recorder.span("frame", phase, track, from);
+        });
+    }
+
+    // Time events from the capture phase, when they reach this element
+    // first, to the bubble phase, when they leave it last
+    fn listen(&self, window: &mut Window, cx: &App) {
+        if !enabled(cx) {
+            return;
+        }
+        let track = window.window_handle().window_id().as_u64();
+        window.on_mouse_event(move |_: &MouseDownEvent, phase, _, cx| {
+            dispatched("mouse_down", phase, track, cx)
+        });
+        window.on_mouse_event(move |_: &MouseUpEvent, phase, _, cx| {
+            dispatched("mouse_up", phase, track, cx)
+        });
+        window.on_mouse_event(move |_: &MouseMoveEvent, phase, _, cx| {
+            dispatched("mouse_move", phase, track, cx)
+        });
+        window.on_mouse_event(move |_: &ScrollWheelEvent, phase, _, cx| {
+            dispatched("scroll_wheel", phase, track, cx)
+        });
+        window.on_key_event(move |_: &KeyDownEvent, phase, _, cx| {
+            dispatched("key_down", phase, track, cx)
+        });
+        window.on_key_event(move |_: &KeyUpEvent, phase, _, cx| {
+            dispatched("key_up", phase, track, cx)
+        });
+    }
+}
+
+fn dispatched(event: &'static str, phase: DispatchPhase, track: u64, cx: &mut App) {
+    cx.update_global::<Recorder, _>(|recorder, _| match phase {
+        DispatchPhase::Capture => {
+            // The last one was stopped before it got back to us
+            if let Some((event, track, at)) = recorder.dispatching.take() {
+                recorder.instant("dispatch", event, track, at);
+            }
+            recorder.dispatching = Some((event, track, Instant::now()));
+        }
+        DispatchPhase::Bubble => {
+            if let Some((event, track, at)) = recorder.dispatching.take() {
+                recorder.span("dispatch", event, track, at);
+            }
+        }
+    });
+}
+
+impl IntoElement for Traced {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for Traced {
+    // When the frame started
+    type RequestLayoutState = Instant;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let started = Instant::now();
+        let layout_id = self.child.request_layout(window, cx);
+        self.span("layout", started, window, cx);
+        (layout_id, started)
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _frame_started: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let started = Instant::now();
+        self.child.prepaint(window, cx);
+        self.span("prepaint", started, window, cx);
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        frame_started: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.listen(window, cx);
+        let started = Instant::now();
+        self.child.paint(window, cx);
+        self.span("paint", started, window, cx);
+        self.span("frame", *frame_started, window, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Instant;
+
+    use gpui::{BorrowAppContext, TestAppContext};
+    use serde_json::Value;
+
+    use super::{finish, start, Recorder, FLUSH_INTERVAL, MAX_PENDING};
+
+    fn record(spans: usize, cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.update_global::<Recorder, _>(|recorder, _| {
+                for _ in 0..spans {
+                    recorder.span("frame", "paint", 1, Instant::now());
+                }
+            })
+        });
+    }
+
+    // The events in the file, closing the array if it isn't yet
+    fn events(text: &str) -> Vec<Value> {
+        let closed = if text.trim_end().ends_with(']') {
+            text.to_string()
+        } else {
+            format!("{text}]")
+        };
+        serde_json::from_str(&closed).unwrap()
+    }
+
+    #[gpui::test]
+    fn events_reach_the_file_before_the_app_quits(cx: &mut TestAppContext) {
+        let path =
+            std::env::temp_dir().join(format!("gpui-dialogs-trace-{}.json", std::process::id()));
+        cx.update(|cx| start(&path, cx));
+
+        record(3, cx);
+        assert!(events(&fs::read_to_string(&path).unwrap()).is_empty());
+        cx.executor().advance_clock(FLUSH_INTERVAL);
+        cx.run_until_parked();
+        assert_eq!(events(&fs::read_to_string(&path).unwrap()).len(), 3);
+
+        // Without waiting, once enough have piled up
+        record(MAX_PENDING, cx);
+        assert_eq!(
+            events(&fs::read_to_string(&path).unwrap()).len(),
+            3 + MAX_PENDING
+        );
+        let pending = cx.update(|cx| cx.global::<Recorder>().pending.len());
+        assert_eq!(pending, 0);
+
+        record(1, cx);
+        cx.update(finish);
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+        // Quitting closes the array
+        let trace: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(trace.as_array().unwrap().len(), 4 + MAX_PENDING);
+    }
+}
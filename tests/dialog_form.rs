@@ -0,0 +1,75 @@
+// derive(DialogForm) on a struct using every attribute, checked through
+// the trait it implements
+
+use std::collections::BTreeMap;
+
+use gpui_dialogs::DialogForm;
+
+#[derive(DialogForm, Debug, PartialEq)]
+struct Connection {
+    #[dialog(label = "Host name", placeholder = "example.com")]
+    host: String,
+    #[dialog(default = "22", validate = "valid_port")]
+    port: u16,
+    r#type: String,
+}
+
+fn valid_port(text: &str) -> Result<(), String> {
+    if text.trim() == "0" {
+        return Err("port 0 is reserved".to_string());
+    }
+    Ok(())
+}
+
+fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn one_field_per_struct_field() {
+    let fields = Connection::fields();
+    let ids: Vec<_> = fields.iter().map(|field| field.id.as_ref()).collect();
+    let labels: Vec<_> = fields.iter().map(|field| field.label.as_ref()).collect();
+    assert_eq!(ids, ["host", "port", "type"]);
+    assert_eq!(labels, ["Host name", "Port", "Type"]);
+    assert_eq!(fields[0].placeholder.as_ref(), "example.com");
+    assert_eq!(fields[1].default_value.as_ref(), "22");
+    assert_eq!(fields[2].placeholder.as_ref(), "");
+}
+
+#[test]
+fn fills_in_the_struct() {
+    let connection = Connection::from_values(&values(&[
+        ("host", "a.test"),
+        ("port", "2222"),
+        ("type", "sftp"),
+    ]));
+    assert_eq!(
+        connection,
+        Ok(Connection {
+            host: "a.test".to_string(),
+            port: 2222,
+            r#type: "sftp".to_string(),
+        })
+    );
+}
+
+#[test]
+fn says_which_field_doesnt_parse() {
+    let connection = Connection::from_values(&values(&[
+        ("host", "a.test"),
+        ("port", "ssh"),
+        ("type", ""),
+    ]));
+    assert_eq!(connection, Err("Port: “ssh” isn't valid".to_string()));
+}
+
+#[test]
+fn validators_run_before_parsing() {
+    let connection =
+        Connection::from_values(&values(&[("host", "a.test"), ("port", "0"), ("type", "")]));
+    assert_eq!(connection, Err("Port: port 0 is reserved".to_string()));
+}